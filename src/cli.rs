@@ -1,16 +1,30 @@
 //! CLI argument definitions
 
-use clap::{Parser, ValueEnum};
+use clap::{Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
 #[derive(Debug, Parser)]
 #[command(name = "claude-list")]
 #[command(author, version, about, long_about = None)]
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Custom .claude directory path
-    #[arg(short, long, value_name = "PATH")]
+    #[arg(short, long, value_name = "PATH", global = true)]
     pub config: Option<PathBuf>,
 
+    /// Also include a project's .claude directory and .mcp.json
+    /// (without =DIR, the project containing the current directory)
+    #[arg(
+        long,
+        value_name = "DIR",
+        num_args = 0..=1,
+        require_equals = true,
+        global = true
+    )]
+    pub project: Option<Option<PathBuf>>,
+
     /// Output mode: compact (default), detailed (via --output or -l)
     #[arg(long, value_enum)]
     pub output: Option<OutputMode>,
@@ -48,12 +62,74 @@ pub struct Args {
     /// Filter to show only commands
     #[arg(long)]
     pub commands: bool,
+    /// Filter to show only CLAUDE.md memory files
+    #[arg(long)]
+    pub memory: bool,
+    /// Filter to show only settings keys and permission rules
+    #[arg(long)]
+    pub settings: bool,
+    /// Filter to show only enabled plugins
+    #[arg(long)]
+    pub enabled: bool,
+    /// Filter to show only disabled plugins
+    #[arg(long)]
+    pub disabled: bool,
+    /// Filter to show only commands and skills never run in history.jsonl
+    #[arg(long)]
+    pub unused: bool,
 
-    /// Output in JSON format
+    /// Show registered plugin marketplaces and the plugins they offer
+    #[arg(long)]
+    pub marketplaces: bool,
+
+    /// Show only the components Claude Code would load, hiding shadowed ones
+    #[arg(long)]
+    pub effective: bool,
+
+    /// Fail if any file could not be parsed instead of only reporting it
     #[arg(long)]
+    pub strict: bool,
+
+    /// Output in JSON format
+    #[arg(long, global = true)]
     pub json: bool,
 }
 
+#[derive(Debug, Clone, Subcommand)]
+pub enum Command {
+    /// Validate every component and report problems (exits 1 on errors)
+    Doctor,
+    /// Write the full inventory as JSON for a later diff
+    Snapshot {
+        /// File to write (stdout if omitted)
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Show session counts, messages and activity per project
+    Sessions,
+    /// Sum token usage and estimated cost per day, project and model
+    Usage {
+        /// First day to include (YYYY-MM-DD, UTC)
+        #[arg(long, value_name = "DATE", value_parser = parse_day)]
+        since: Option<String>,
+        /// Last day to include (YYYY-MM-DD, UTC)
+        #[arg(long, value_name = "DATE", value_parser = parse_day)]
+        until: Option<String>,
+    },
+    /// Compare installed plugins with the local marketplace catalogs
+    /// (exits 1 if any are outdated)
+    Outdated,
+    /// Compare two inventories (snapshot files or .claude directories)
+    Diff {
+        /// Inventory before the change
+        #[arg(value_name = "OLD")]
+        old: PathBuf,
+        /// Inventory after the change
+        #[arg(value_name = "NEW")]
+        new: PathBuf,
+    },
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum OutputMode {
     /// Compact: name only
@@ -61,3 +137,11 @@ pub enum OutputMode {
     /// Detailed: name, version, source
     Detailed,
 }
+
+/// Accept a `YYYY-MM-DD` day for `--since` / `--until`
+fn parse_day(value: &str) -> Result<String, String> {
+    match crate::time::parse_date(value) {
+        Some(_) => Ok(value.to_string()),
+        None => Err("expected a date as YYYY-MM-DD".to_string()),
+    }
+}
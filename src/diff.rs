@@ -0,0 +1,503 @@
+//! Compare two inventories for the `diff` subcommand
+//!
+//! An inventory is either a live .claude directory or a JSON snapshot written
+//! by `claude-list snapshot`. Components are matched by name (and by level
+//! where the same name can exist more than once); paths are ignored since
+//! they differ between machines.
+
+use crate::error::{Error, Result};
+use crate::info::{
+    AgentInfo, ClaudeInfo, CommandInfo, ComponentKind, HookInfo, McpInfo, PluginInfo, SkillInfo,
+    SkillLocation,
+};
+use crate::parsers::parse_all;
+use crate::resolve::resolve_effective;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// How a component differs between the two inventories
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A single added, removed or changed component
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentChange {
+    pub component: ComponentKind,
+    pub name: String,
+    pub change: ChangeKind,
+    /// Field-level changes, e.g. `version: 1.0.0 -> 1.1.0`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<String>,
+}
+
+/// Every difference between two inventories, grouped by component kind
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InventoryDiff {
+    pub changes: Vec<ComponentChange>,
+}
+
+impl InventoryDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn count(&self, change: ChangeKind) -> usize {
+        self.changes.iter().filter(|c| c.change == change).count()
+    }
+}
+
+/// Load an inventory from a snapshot file or a live .claude directory
+pub fn load_inventory(path: &Path) -> Result<ClaudeInfo> {
+    if path.is_dir() {
+        let mut info = parse_all(path.to_path_buf())?;
+        resolve_effective(&mut info);
+        return Ok(info);
+    }
+    if !path.exists() {
+        return Err(Error::DirectoryNotFound(path.to_path_buf()));
+    }
+
+    let content = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// A component that can be matched across inventories
+trait Diffable {
+    const KIND: ComponentKind;
+    /// Identity used to match the same component in both inventories
+    fn key(&self) -> String;
+    /// Name shown in the report
+    fn label(&self) -> String;
+    /// Fields compared for `changed` entries
+    fn fields(&self) -> Vec<(&'static str, String)>;
+}
+
+fn opt(value: &Option<String>) -> String {
+    value.clone().unwrap_or_default()
+}
+
+impl Diffable for PluginInfo {
+    const KIND: ComponentKind = ComponentKind::Plugin;
+    fn key(&self) -> String {
+        self.name.clone()
+    }
+    fn label(&self) -> String {
+        self.name.clone()
+    }
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("version", opt(&self.version)),
+            ("description", opt(&self.description)),
+            ("enabled", self.enabled.to_string()),
+        ]
+    }
+}
+
+impl Diffable for SkillInfo {
+    const KIND: ComponentKind = ComponentKind::Skill;
+    fn key(&self) -> String {
+        let location = match &self.location_type {
+            SkillLocation::Global => "user".to_string(),
+            SkillLocation::Project => "project".to_string(),
+            SkillLocation::Plugin { plugin_name } => {
+                format!("plugin:{}", plugin_name.clone().unwrap_or_default())
+            }
+        };
+        format!("{} ({})", self.name, location)
+    }
+    fn label(&self) -> String {
+        self.key()
+    }
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("version", opt(&self.version)),
+            ("description", opt(&self.description)),
+            ("allowed_tools", self.allowed_tools.join(", ")),
+            ("license", opt(&self.license)),
+        ]
+    }
+}
+
+impl Diffable for McpInfo {
+    const KIND: ComponentKind = ComponentKind::Mcp;
+    fn key(&self) -> String {
+        match &self.project {
+            Some(project) => format!("{} ({}, {})", self.name, self.scope, project.display()),
+            None => format!("{} ({})", self.name, self.scope),
+        }
+    }
+    fn label(&self) -> String {
+        self.key()
+    }
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("transport", self.transport.to_string()),
+            ("command", opt(&self.command)),
+            ("args", self.args.join(" ")),
+            ("url", opt(&self.url)),
+            ("env", self.env.join(", ")),
+            ("headers", self.headers.join(", ")),
+        ]
+    }
+}
+
+impl Diffable for HookInfo {
+    const KIND: ComponentKind = ComponentKind::Hook;
+    fn key(&self) -> String {
+        format!("{} ({})", self.name, self.origin)
+    }
+    fn label(&self) -> String {
+        match &self.command {
+            Some(command) => format!("{} ({}): {}", self.name, self.origin, command),
+            None => format!("{} ({})", self.name, self.origin),
+        }
+    }
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("command", opt(&self.command)),
+            ("type", self.hook_type.clone()),
+            (
+                "timeout",
+                self.timeout.map(|t| t.to_string()).unwrap_or_default(),
+            ),
+        ]
+    }
+}
+
+impl Diffable for AgentInfo {
+    const KIND: ComponentKind = ComponentKind::Agent;
+    fn key(&self) -> String {
+        format!("{} ({})", self.name, self.origin)
+    }
+    fn label(&self) -> String {
+        self.key()
+    }
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("description", opt(&self.description)),
+            ("tools", self.tools.join(", ")),
+            ("model", opt(&self.model)),
+            ("color", opt(&self.color)),
+        ]
+    }
+}
+
+impl Diffable for CommandInfo {
+    const KIND: ComponentKind = ComponentKind::Command;
+    fn key(&self) -> String {
+        format!("{} ({})", self.name, self.origin)
+    }
+    fn label(&self) -> String {
+        self.key()
+    }
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("description", opt(&self.description)),
+            ("allowed-tools", opt(&self.allowed_tools)),
+            ("argument-hint", opt(&self.argument_hint)),
+            ("model", opt(&self.model)),
+        ]
+    }
+}
+
+/// Index a component list by key. Repeated keys, such as several hooks on
+/// one event, are numbered in list order (`Stop (user) #2`) so none is lost.
+fn keyed<T: Diffable>(items: &[T]) -> BTreeMap<String, &T> {
+    let mut map = BTreeMap::new();
+    for item in items {
+        let key = item.key();
+        let mut unique = key.clone();
+        let mut n = 1;
+        while map.contains_key(&unique) {
+            n += 1;
+            unique = format!("{} #{}", key, n);
+        }
+        map.insert(unique, item);
+    }
+    map
+}
+
+/// Compare one component list, appending changes sorted by name
+fn diff_list<T: Diffable>(old: &[T], new: &[T], changes: &mut Vec<ComponentChange>) {
+    let old = keyed(old);
+    let new = keyed(new);
+
+    let mut keys: Vec<&String> = old.keys().chain(new.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    for key in keys {
+        let change = match (old.get(key), new.get(key)) {
+            (Some(before), Some(after)) => {
+                let details: Vec<String> = before
+                    .fields()
+                    .into_iter()
+                    .zip(after.fields())
+                    .filter(|((_, a), (_, b))| a != b)
+                    .map(|((field, a), (_, b))| format!("{}: {} -> {}", field, show(&a), show(&b)))
+                    .collect();
+                if details.is_empty() {
+                    continue;
+                }
+                ComponentChange {
+                    component: T::KIND,
+                    name: after.label(),
+                    change: ChangeKind::Changed,
+                    details,
+                }
+            }
+            (None, Some(after)) => ComponentChange {
+                component: T::KIND,
+                name: after.label(),
+                change: ChangeKind::Added,
+                details: vec![],
+            },
+            (Some(before), None) => ComponentChange {
+                component: T::KIND,
+                name: before.label(),
+                change: ChangeKind::Removed,
+                details: vec![],
+            },
+            (None, None) => continue,
+        };
+        changes.push(change);
+    }
+}
+
+/// Render an empty field value so it is visible in the report
+fn show(value: &str) -> &str {
+    if value.is_empty() {
+        "(none)"
+    } else {
+        value
+    }
+}
+
+/// Compare two inventories
+pub fn diff_inventories(old: &ClaudeInfo, new: &ClaudeInfo) -> InventoryDiff {
+    let mut changes = Vec::new();
+    diff_list(&old.plugins, &new.plugins, &mut changes);
+    diff_list(&old.skills, &new.skills, &mut changes);
+    diff_list(&old.mcp_servers, &new.mcp_servers, &mut changes);
+    diff_list(&old.hooks, &new.hooks, &mut changes);
+    diff_list(&old.agents, &new.agents, &mut changes);
+    diff_list(&old.commands, &new.commands, &mut changes);
+    InventoryDiff { changes }
+}
+
+/// Print a diff as text, one section per component kind
+pub fn write_diff(diff: &InventoryDiff, output: &mut dyn Write) -> std::io::Result<()> {
+    if diff.is_empty() {
+        writeln!(output, "No differences")?;
+        return Ok(());
+    }
+
+    let sections = [
+        (ComponentKind::Plugin, "PLUGINS"),
+        (ComponentKind::Skill, "SKILLS"),
+        (ComponentKind::Mcp, "MCP"),
+        (ComponentKind::Hook, "HOOKS"),
+        (ComponentKind::Agent, "AGENTS"),
+        (ComponentKind::Command, "COMMANDS"),
+    ];
+
+    for (kind, title) in sections {
+        let changes: Vec<&ComponentChange> = diff
+            .changes
+            .iter()
+            .filter(|c| c.component == kind)
+            .collect();
+        if changes.is_empty() {
+            continue;
+        }
+
+        writeln!(output, "{}", title)?;
+        for change in changes {
+            let marker = match change.change {
+                ChangeKind::Added => '+',
+                ChangeKind::Removed => '-',
+                ChangeKind::Changed => '~',
+            };
+            writeln!(output, "  {} {}", marker, change.name)?;
+            for detail in &change.details {
+                writeln!(output, "      {}", detail)?;
+            }
+        }
+        writeln!(output)?;
+    }
+
+    writeln!(
+        output,
+        "{} added, {} removed, {} changed",
+        diff.count(ChangeKind::Added),
+        diff.count(ChangeKind::Removed),
+        diff.count(ChangeKind::Changed)
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::info::{Origin, SessionInfo, SettingsInfo, Source};
+    use std::path::PathBuf;
+
+    fn plugin(name: &str, version: &str) -> PluginInfo {
+        PluginInfo {
+            name: name.to_string(),
+            version: Some(version.to_string()),
+            source: Source::Official,
+            path: PathBuf::from("/test/.claude/plugins").join(name),
+            marketplace: None,
+            enabled: true,
+            description: None,
+            author: None,
+            homepage: None,
+            repository: None,
+            keywords: vec![],
+            installs: vec![],
+        }
+    }
+
+    fn agent(name: &str, description: &str) -> AgentInfo {
+        AgentInfo {
+            name: name.to_string(),
+            description: Some(description.to_string()),
+            path: PathBuf::from(format!("/test/.claude/agents/{}.md", name)),
+            origin: Origin::User,
+            shadowed_by: None,
+            tools: vec![],
+            model: None,
+            color: None,
+        }
+    }
+
+    fn hook(name: &str, command: &str) -> HookInfo {
+        HookInfo {
+            name: name.to_string(),
+            hook_type: "command".to_string(),
+            path: PathBuf::from("/test/.claude/settings.json"),
+            description: None,
+            event: Some(name.to_string()),
+            matcher: None,
+            command: Some(command.to_string()),
+            timeout: None,
+            settings_file: None,
+            origin: Origin::User,
+        }
+    }
+
+    fn inventory(plugins: Vec<PluginInfo>, agents: Vec<AgentInfo>) -> ClaudeInfo {
+        ClaudeInfo {
+            version: "0.1.0".to_string(),
+            config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
+            plugins,
+            skills: vec![],
+            sessions: SessionInfo {
+                count: 0,
+                last_session: None,
+            },
+            mcp_servers: vec![],
+            hooks: vec![],
+            agents,
+            commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
+        }
+    }
+
+    #[test]
+    fn test_diff_identical_inventories() {
+        let a = inventory(vec![plugin("context7", "1.0.0")], vec![]);
+        let diff = diff_inventories(&a, &a.clone());
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn test_diff_added_removed_and_version_change() {
+        let old = inventory(
+            vec![plugin("context7", "1.0.0"), plugin("old-plugin", "0.1.0")],
+            vec![agent("reviewer", "Reviews code")],
+        );
+        let new = inventory(
+            vec![plugin("context7", "1.1.0"), plugin("new-plugin", "2.0.0")],
+            vec![agent("reviewer", "Reviews code")],
+        );
+
+        let diff = diff_inventories(&old, &new);
+
+        assert_eq!(diff.changes.len(), 3);
+        assert_eq!(diff.count(ChangeKind::Added), 1);
+        assert_eq!(diff.count(ChangeKind::Removed), 1);
+        assert_eq!(diff.count(ChangeKind::Changed), 1);
+
+        let changed = diff
+            .changes
+            .iter()
+            .find(|c| c.change == ChangeKind::Changed)
+            .unwrap();
+        assert_eq!(changed.name, "context7");
+        assert_eq!(changed.details, vec!["version: 1.0.0 -> 1.1.0"]);
+    }
+
+    #[test]
+    fn test_diff_ignores_paths() {
+        let old = inventory(vec![], vec![agent("reviewer", "Reviews code")]);
+        let mut new = old.clone();
+        new.agents[0].path = PathBuf::from("/other/machine/agents/reviewer.md");
+
+        assert!(diff_inventories(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn test_diff_hook_command_edit_and_repeated_events() {
+        let mut old = inventory(vec![], vec![]);
+        old.hooks = vec![hook("Stop", "notify.sh"), hook("Stop", "log.sh")];
+        let mut new = old.clone();
+        new.hooks[0].command = Some("notify.sh --quiet".to_string());
+        new.hooks.push(hook("Stop", "cleanup.sh"));
+
+        let diff = diff_inventories(&old, &new);
+        let changes: Vec<(&str, ChangeKind)> = diff
+            .changes
+            .iter()
+            .map(|c| (c.name.as_str(), c.change))
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                ("Stop (user): notify.sh --quiet", ChangeKind::Changed),
+                ("Stop (user): cleanup.sh", ChangeKind::Added),
+            ]
+        );
+        assert_eq!(
+            diff.changes[0].details,
+            vec!["command: notify.sh -> notify.sh --quiet"]
+        );
+    }
+
+    #[test]
+    fn test_write_diff_text() {
+        let old = inventory(vec![plugin("context7", "1.0.0")], vec![]);
+        let new = inventory(vec![], vec![agent("reviewer", "Reviews code")]);
+
+        let mut buffer = Vec::new();
+        write_diff(&diff_inventories(&old, &new), &mut buffer).unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+
+        assert!(output.contains("PLUGINS\n  - context7"));
+        assert!(output.contains("AGENTS\n  + reviewer (user)"));
+        assert!(output.contains("1 added, 1 removed, 0 changed"));
+    }
+}
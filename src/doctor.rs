@@ -0,0 +1,453 @@
+//! Strict validation of a .claude directory for the `doctor` subcommand
+//!
+//! The regular parsers skip anything they cannot read so that listing never
+//! fails, recording what they skipped as warnings. Doctor runs the same
+//! parsers, reports those warnings (as errors, except for unreadable history
+//! lines) and adds the checks listing does not need, such as agents without
+//! a description.
+
+use crate::error::{Error, Result};
+use crate::info::{ClaudeInfo, ComponentKind, Diagnostic, Severity};
+use crate::parsers::parse_all_with_project;
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Validate every component of a config directory and, when given, of a
+/// project root. Diagnostics are returned errors first.
+pub fn run_doctor(config_dir: &Path, project_dir: Option<&Path>) -> Result<Vec<Diagnostic>> {
+    let info =
+        parse_all_with_project(config_dir.to_path_buf(), project_dir.map(Path::to_path_buf))?;
+
+    // A configuration file the parsers could not fully read is broken;
+    // session history is data, so a bad line there stays a warning
+    let mut diagnostics: Vec<Diagnostic> = info
+        .warnings
+        .iter()
+        .cloned()
+        .map(|mut diagnostic| {
+            if diagnostic.component != ComponentKind::Session {
+                diagnostic.severity = Severity::Error;
+            }
+            diagnostic
+        })
+        .collect();
+
+    // One problem per file is enough
+    let reported: HashSet<PathBuf> = diagnostics.iter().map(|d| d.path.clone()).collect();
+    let mut checks = Vec::new();
+    check_plugins(&info, &mut checks);
+    check_skills(&info, &mut checks);
+    check_agents(&info, &mut checks);
+    check_hooks(&info, &mut checks);
+    check_mcp(&info, &mut checks);
+    diagnostics.extend(checks.into_iter().filter(|d| !reported.contains(&d.path)));
+
+    diagnostics.sort_by_key(|d| d.severity);
+    Ok(diagnostics)
+}
+
+/// Every install record of a plugin needs an `installPath` that exists on
+/// disk
+fn check_plugins(info: &ClaudeInfo, diagnostics: &mut Vec<Diagnostic>) {
+    let installed_path = info
+        .config_dir
+        .join("plugins")
+        .join("installed_plugins.json");
+    for plugin in &info.plugins {
+        let full_name = match &plugin.marketplace {
+            Some(marketplace) => format!("{}@{}", plugin.name, marketplace),
+            None => plugin.name.clone(),
+        };
+
+        for install in &plugin.installs {
+            // Name the install when the plugin has several
+            let label = match (&install.scope, &install.project_path) {
+                _ if plugin.installs.len() == 1 => full_name.clone(),
+                (Some(scope), Some(project)) => {
+                    format!("{}, {} scope in {}", full_name, scope, project.display())
+                }
+                (Some(scope), None) => format!("{}, {} scope", full_name, scope),
+                (None, _) => full_name.clone(),
+            };
+
+            match &install.install_path {
+                Some(install_path) if !install_path.exists() => {
+                    diagnostics.push(Diagnostic::error(
+                        ComponentKind::Plugin,
+                        &installed_path,
+                        Error::InvalidComponent(
+                            install_path.clone(),
+                            format!("installPath of plugin {} does not exist", label),
+                        ),
+                    ))
+                }
+                Some(_) => {}
+                None => diagnostics.push(Diagnostic::warning(
+                    ComponentKind::Plugin,
+                    &installed_path,
+                    Error::MissingField(format!("installPath (plugin {})", label)),
+                )),
+            }
+        }
+    }
+}
+
+/// Skills need a SKILL.md (or legacy skill.yaml) with a description, and are
+/// invoked by folder name, so a different `name` in the frontmatter is
+/// misleading
+fn check_skills(info: &ClaudeInfo, diagnostics: &mut Vec<Diagnostic>) {
+    for skill in &info.skills {
+        let skill_md = skill.path.join("SKILL.md");
+        if !skill_md.is_file() {
+            if !skill.path.join("skill.yaml").is_file() {
+                diagnostics.push(Diagnostic::warning(
+                    ComponentKind::Skill,
+                    &skill.path,
+                    Error::InvalidComponent(skill.path.clone(), "no SKILL.md found".to_string()),
+                ));
+            }
+            continue;
+        }
+
+        if skill
+            .description
+            .as_deref()
+            .map_or(true, |d| d.trim().is_empty())
+        {
+            diagnostics.push(Diagnostic::warning(
+                ComponentKind::Skill,
+                &skill_md,
+                Error::MissingField("description".to_string()),
+            ));
+        }
+        if let Some(declared) = skill.declared_name.as_deref().filter(|d| *d != skill.name) {
+            diagnostics.push(Diagnostic::warning(
+                ComponentKind::Skill,
+                &skill_md,
+                Error::InvalidComponent(
+                    skill_md.clone(),
+                    format!(
+                        "declares name `{}` but its folder is `{}`",
+                        declared, skill.name
+                    ),
+                ),
+            ));
+        }
+    }
+}
+
+/// Agents are only loaded when their frontmatter has a description
+fn check_agents(info: &ClaudeInfo, diagnostics: &mut Vec<Diagnostic>) {
+    for agent in &info.agents {
+        if agent
+            .description
+            .as_deref()
+            .map_or(true, |d| d.trim().is_empty())
+        {
+            diagnostics.push(Diagnostic::error(
+                ComponentKind::Agent,
+                &agent.path,
+                Error::MissingField("description".to_string()),
+            ));
+        }
+    }
+}
+
+/// Every hook declared in a settings or hooks.json file needs something to
+/// run
+fn check_hooks(info: &ClaudeInfo, diagnostics: &mut Vec<Diagnostic>) {
+    for hook in &info.hooks {
+        let settings_file = match &hook.settings_file {
+            Some(path) => path,
+            None => continue,
+        };
+        if hook.command.is_none() {
+            diagnostics.push(Diagnostic::error(
+                ComponentKind::Hook,
+                settings_file,
+                Error::MissingField(format!(
+                    "command (hooks.{})",
+                    hook.event.as_deref().unwrap_or_default()
+                )),
+            ));
+        }
+    }
+}
+
+/// MCP servers need a way to launch them
+fn check_mcp(info: &ClaudeInfo, diagnostics: &mut Vec<Diagnostic>) {
+    for server in &info.mcp_servers {
+        if server.command.is_none() && server.url.is_none() {
+            diagnostics.push(Diagnostic::error(
+                ComponentKind::Mcp,
+                &server.path,
+                Error::InvalidComponent(
+                    server.path.clone(),
+                    format!(
+                        "MCP server {} has no launch config (command or url)",
+                        server.name
+                    ),
+                ),
+            ));
+        }
+    }
+}
+
+/// Print diagnostics grouped by severity
+pub fn write_report(diagnostics: &[Diagnostic], output: &mut dyn Write) -> std::io::Result<()> {
+    let errors: Vec<&Diagnostic> = diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .collect();
+    let warnings: Vec<&Diagnostic> = diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Warning)
+        .collect();
+
+    if !errors.is_empty() {
+        writeln!(output, "ERRORS     {} found", errors.len())?;
+        for diagnostic in &errors {
+            writeln!(output, "  {}", diagnostic)?;
+        }
+        writeln!(output)?;
+    }
+
+    if !warnings.is_empty() {
+        writeln!(output, "WARNINGS   {} found", warnings.len())?;
+        for diagnostic in &warnings {
+            writeln!(output, "  {}", diagnostic)?;
+        }
+        writeln!(output)?;
+    }
+
+    if diagnostics.is_empty() {
+        writeln!(output, "No problems found")?;
+    } else {
+        writeln!(
+            output,
+            "{} error(s), {} warning(s)",
+            errors.len(),
+            warnings.len()
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Whether any diagnostic should fail the run
+pub fn has_errors(diagnostics: &[Diagnostic]) -> bool {
+    diagnostics.iter().any(|d| d.severity == Severity::Error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{self, create_dir_all};
+    use tempfile::TempDir;
+
+    fn write(path: &Path, content: &str) {
+        create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_clean_config_has_no_diagnostics() {
+        let dir = TempDir::new().unwrap();
+        let base = dir.path().join(".claude");
+        write(
+            &base.join("skills/ok/SKILL.md"),
+            "---\nname: ok\ndescription: Fine\n---\n",
+        );
+        write(
+            &base.join("agents/helper.md"),
+            "---\nname: helper\ndescription: Helps\n---\n",
+        );
+        write(&base.join("commands/plain.md"), "# No frontmatter\n");
+
+        let diagnostics = run_doctor(&base, None).unwrap();
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    }
+
+    #[test]
+    fn test_malformed_skill_frontmatter() {
+        let dir = TempDir::new().unwrap();
+        let base = dir.path().join(".claude");
+        write(
+            &base.join("skills/broken/SKILL.md"),
+            "---\ndescription: [unclosed\n---\n",
+        );
+        write(
+            &base.join("skills/unterminated/SKILL.md"),
+            "---\ndescription: Never closed\n",
+        );
+
+        let diagnostics = run_doctor(&base, None).unwrap();
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics.iter().all(
+            |d| d.severity == Severity::Error && d.message.starts_with("Malformed frontmatter")
+        ));
+    }
+
+    #[test]
+    fn test_skill_name_differs_from_folder() {
+        let dir = TempDir::new().unwrap();
+        let base = dir.path().join(".claude");
+        write(
+            &base.join("skills/pdf/SKILL.md"),
+            "---\nname: pdf-tools\ndescription: PDFs\n---\n",
+        );
+
+        let diagnostics = run_doctor(&base, None).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert!(diagnostics[0]
+            .message
+            .contains("declares name `pdf-tools` but its folder is `pdf`"));
+    }
+
+    #[test]
+    fn test_agent_without_description() {
+        let dir = TempDir::new().unwrap();
+        let base = dir.path().join(".claude");
+        write(&base.join("agents/quiet.md"), "---\nname: quiet\n---\n");
+
+        let diagnostics = run_doctor(&base, None).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.starts_with("Missing required field"));
+        assert!(diagnostics[0].to_string().contains("quiet.md"));
+    }
+
+    #[test]
+    fn test_nested_command_frontmatter_is_checked() {
+        let dir = TempDir::new().unwrap();
+        let base = dir.path().join(".claude");
+        write(&base.join("commands/review.md"), "# Review\n");
+        write(
+            &base.join("commands/frontend/component.md"),
+            "---\ndescription: never closed\n",
+        );
+
+        let diagnostics = run_doctor(&base, None).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].to_string().contains("frontend"));
+    }
+
+    #[test]
+    fn test_plugin_install_path_missing() {
+        let dir = TempDir::new().unwrap();
+        let base = dir.path().join(".claude");
+        write(
+            &base.join("plugins/installed_plugins.json"),
+            r#"{"version": 2, "plugins": {
+                "gone@market": [{"installPath": "/nonexistent/plugin/path"}],
+                "nopath@market": [{"version": "1.0.0"}]
+            }}"#,
+        );
+
+        let diagnostics = run_doctor(&base, None).unwrap();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert!(diagnostics[0].to_string().contains("gone@market"));
+        assert_eq!(diagnostics[1].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_every_plugin_install_path_checked() {
+        let dir = TempDir::new().unwrap();
+        let base = dir.path().join(".claude");
+        let user_install = dir.path().join("cache/toolkit/1.0.0");
+        create_dir_all(&user_install).unwrap();
+        write(
+            &base.join("plugins/installed_plugins.json"),
+            &format!(
+                r#"{{"version": 2, "plugins": {{"toolkit@market": [
+                    {{"scope": "user", "installPath": "{}"}},
+                    {{"scope": "project", "projectPath": "/src/app", "installPath": "/nonexistent/toolkit"}}
+                ]}}}}"#,
+                user_install.to_string_lossy().replace('\\', "/")
+            ),
+        );
+
+        let diagnostics = run_doctor(&base, None).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert!(diagnostics[0]
+            .to_string()
+            .contains("toolkit@market, project scope in /src/app"));
+    }
+
+    #[test]
+    fn test_mcp_dir_without_launch_config() {
+        let dir = TempDir::new().unwrap();
+        let base = dir.path().join(".claude");
+        create_dir_all(base.join("mcp-servers/empty-server")).unwrap();
+        write(&base.join("mcp-servers/ok-server/package.json"), "{}");
+
+        let diagnostics = run_doctor(&base, None).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].to_string().contains("empty-server"));
+    }
+
+    #[test]
+    fn test_malformed_json_and_hook_without_command() {
+        let dir = TempDir::new().unwrap();
+        let base = dir.path().join(".claude");
+        write(
+            &base.join("settings.json"),
+            r#"{"hooks": {"Stop": [{"hooks": [{"type": "command"}]}]}}"#,
+        );
+        write(&dir.path().join(".claude.json"), "{ not json");
+
+        let diagnostics = run_doctor(&base, None).unwrap();
+        assert_eq!(diagnostics.len(), 2);
+        assert!(has_errors(&diagnostics));
+    }
+
+    #[test]
+    fn test_parser_warnings_are_reported() {
+        let dir = TempDir::new().unwrap();
+        let base = dir.path().join(".claude");
+        write(&base.join("history.jsonl"), "not json\n");
+        write(&base.join("agents/quiet.md"), "---\nname: quiet\n---\n");
+
+        // The config directory is also the project's .claude: checked once
+        let diagnostics = run_doctor(&base, Some(dir.path())).unwrap();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].component, ComponentKind::Agent);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[1].component, ComponentKind::Session);
+        assert_eq!(diagnostics[1].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_write_report_groups_by_severity() {
+        let diagnostics = vec![
+            Diagnostic::error(
+                ComponentKind::Agent,
+                Path::new("/a.md"),
+                Error::MalformedFrontmatter {
+                    path: PathBuf::from("/a.md"),
+                    line: 1,
+                    column: 1,
+                    reason: "no closing `---`".to_string(),
+                },
+            ),
+            Diagnostic::warning(
+                ComponentKind::Skill,
+                Path::new("/b/SKILL.md"),
+                Error::MissingField("description".to_string()),
+            ),
+        ];
+
+        let mut buffer = Vec::new();
+        write_report(&diagnostics, &mut buffer).unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+
+        assert!(output.contains("ERRORS     1 found"));
+        assert!(output.contains("Malformed frontmatter in /a.md"));
+        assert!(output.contains("WARNINGS   1 found"));
+        assert!(output.contains("Missing required field: description in /b/SKILL.md"));
+        assert!(output.contains("1 error(s), 1 warning(s)"));
+    }
+}
@@ -12,6 +12,12 @@ pub enum Error {
     #[error("Failed to read directory: {0}")]
     DirectoryReadError(#[from] std::io::Error),
 
+    #[error("Failed to read {}: {source}", .path.display())]
+    FileReadError {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
     #[error("Failed to parse JSON: {0}")]
     JsonParseError(#[from] serde_json::Error),
 
@@ -24,8 +30,14 @@ pub enum Error {
     #[error("Missing required field: {0}")]
     MissingField(String),
 
-    #[error("Malformed frontmatter in {0}")]
-    MalformedFrontmatter(PathBuf),
+    #[error("Malformed frontmatter in {}:{line}:{column}: {reason}", .path.display())]
+    MalformedFrontmatter {
+        path: PathBuf,
+        /// 1-based line and column in the markdown file
+        line: usize,
+        column: usize,
+        reason: String,
+    },
 }
 
 impl Error {
@@ -54,6 +66,22 @@ mod tests {
         assert!(!error.is_fatal());
     }
 
+    #[test]
+    fn test_error_display_file_read_error() {
+        let error = Error::FileReadError {
+            path: PathBuf::from("/test/agents/a.md"),
+            source: std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "stream did not contain valid UTF-8",
+            ),
+        };
+        assert!(!error.is_fatal());
+        assert_eq!(
+            error.to_string(),
+            "Failed to read /test/agents/a.md: stream did not contain valid UTF-8"
+        );
+    }
+
     #[test]
     fn test_error_is_fatal_json_parse_error() {
         let error = Error::JsonParseError(
@@ -82,10 +110,29 @@ mod tests {
 
     #[test]
     fn test_error_is_fatal_malformed_frontmatter() {
-        let error = Error::MalformedFrontmatter(PathBuf::from("/test/path"));
+        let error = Error::MalformedFrontmatter {
+            path: PathBuf::from("/test/path"),
+            line: 1,
+            column: 1,
+            reason: "no closing `---`".to_string(),
+        };
         assert!(!error.is_fatal());
     }
 
+    #[test]
+    fn test_error_display_malformed_frontmatter() {
+        let error = Error::MalformedFrontmatter {
+            path: PathBuf::from("/test/agent.md"),
+            line: 3,
+            column: 14,
+            reason: "mapping values are not allowed in this context".to_string(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Malformed frontmatter in /test/agent.md:3:14: mapping values are not allowed in this context"
+        );
+    }
+
     #[test]
     fn test_error_display_directory_not_found() {
         let error = Error::DirectoryNotFound(PathBuf::from("/test/path"));
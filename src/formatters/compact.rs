@@ -1,6 +1,9 @@
 //! Compact output formatter
 
-use crate::info::ClaudeInfo;
+use crate::formatters::{
+    group_commands, memory_label, memory_summary, settings_summary, write_warnings,
+};
+use crate::info::{ClaudeInfo, Timestamp};
 use crate::output::{colored_string, ColorScheme, ColorSettings, ComponentType};
 use std::io::Write;
 
@@ -13,12 +16,25 @@ pub fn format_compact(
     writeln!(output, "CLAUDE-LIST v{}", info.version)?;
     writeln!(output)?;
     writeln!(output, "CONFIG: {}", info.config_dir.display())?;
+    if let Some(ref project_dir) = info.project_dir {
+        writeln!(output, "PROJECT: {}", project_dir.display())?;
+    }
     writeln!(output)?;
 
     // PLUGINS
     if !info.plugins.is_empty() {
         writeln!(output, "PLUGINS    {} installed", info.plugins.len())?;
         for plugin in &info.plugins {
+            if !plugin.enabled {
+                let dimmed = colored_string(
+                    &format!("{} (disabled)", plugin.name),
+                    ComponentType::Disabled,
+                    color_scheme,
+                    color_settings,
+                );
+                writeln!(output, "  {}", dimmed)?;
+                continue;
+            }
             let colored = colored_string(
                 &plugin.name,
                 ComponentType::Plugin,
@@ -47,7 +63,11 @@ pub fn format_compact(
 
     // SESSIONS
     if info.sessions.count > 0 {
-        writeln!(output, "SESSIONS   {} recorded", info.sessions.count)?;
+        write!(output, "SESSIONS   {} recorded", info.sessions.count)?;
+        if let Some(last) = info.sessions.last_session {
+            write!(output, ", last {}", last.relative_to(Timestamp::now()))?;
+        }
+        writeln!(output)?;
         writeln!(output)?;
     }
 
@@ -92,27 +112,93 @@ pub fn format_compact(
         writeln!(output)?;
     }
 
-    // COMMANDS
-    if !info.commands.is_empty() {
-        writeln!(output, "COMMANDS   {} available", info.commands.len())?;
-        for cmd in &info.commands {
+    // SETTINGS
+    if !info.settings.is_empty() {
+        writeln!(output, "{}", settings_summary(&info.settings))?;
+        for setting in &info.settings.values {
+            let colored = colored_string(
+                &setting.key,
+                ComponentType::Settings,
+                color_scheme,
+                color_settings,
+            );
+            match setting.value {
+                Some(ref value) => {
+                    writeln!(output, "  {}: {} ({})", colored, value, setting.scope)?
+                }
+                None => writeln!(output, "  {} ({})", colored, setting.scope)?,
+            }
+        }
+        for permission in &info.settings.permissions {
             let colored = colored_string(
-                &cmd.name,
-                ComponentType::Command,
+                &permission.kind.to_string(),
+                ComponentType::Settings,
                 color_scheme,
                 color_settings,
             );
-            writeln!(output, "  /{}", colored)?;
+            writeln!(
+                output,
+                "  {} {} ({})",
+                colored, permission.rule, permission.scope
+            )?;
         }
+        writeln!(output)?;
     }
 
+    // MEMORY
+    if !info.memory.is_empty() {
+        writeln!(output, "{}", memory_summary(&info.memory))?;
+        for memory in &info.memory {
+            let colored = colored_string(
+                &memory_label(memory, info.project_dir.as_deref()),
+                ComponentType::Memory,
+                color_scheme,
+                color_settings,
+            );
+            writeln!(output, "  {}", colored)?;
+        }
+        writeln!(output)?;
+    }
+
+    // COMMANDS
+    if !info.commands.is_empty() {
+        writeln!(output, "COMMANDS   {} available", info.commands.len())?;
+        for (namespace, commands) in group_commands(&info.commands) {
+            let indent = match namespace {
+                Some(namespace) => {
+                    writeln!(output, "  {}:", namespace)?;
+                    "    "
+                }
+                None => "  ",
+            };
+            for cmd in commands {
+                let colored = colored_string(
+                    &cmd.name,
+                    ComponentType::Command,
+                    color_scheme,
+                    color_settings,
+                );
+                writeln!(output, "{}/{}", indent, colored)?;
+            }
+        }
+        if !info.warnings.is_empty() {
+            writeln!(output)?;
+        }
+    }
+
+    write_warnings(&info.warnings, output)?;
+
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::info::{PluginInfo, SessionInfo, SkillInfo, SkillLocation, Source};
+    use crate::info::{
+        ComponentKind, Diagnostic, MemoryInfo, MemoryScope, Origin, PluginInfo, SessionInfo,
+        SettingsInfo, SkillInfo, SkillLocation, Source,
+    };
+    use std::collections::BTreeMap;
     use std::path::PathBuf;
 
     #[test]
@@ -120,12 +206,20 @@ mod tests {
         let info = ClaudeInfo {
             version: "0.1.0".to_string(),
             config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
             plugins: vec![PluginInfo {
                 name: "context7".to_string(),
                 version: Some("2.1.0".to_string()),
                 source: Source::Official,
                 path: PathBuf::from("/test/.claude/settings.json"),
+                marketplace: None,
+                enabled: true,
                 description: None,
+                author: None,
+                homepage: None,
+                repository: None,
+                keywords: vec![],
+                installs: vec![],
             }],
             skills: vec![SkillInfo {
                 name: "test-skill".to_string(),
@@ -134,15 +228,25 @@ mod tests {
                 path: PathBuf::from("/test/.claude/skills/test-skill"),
                 description: Some("A test skill".to_string()),
                 location_type: SkillLocation::Global,
+                shadowed_by: None,
+                uses: 0,
+                resources: vec![],
+                declared_name: None,
+                allowed_tools: vec![],
+                license: None,
+                metadata: BTreeMap::new(),
             }],
             sessions: SessionInfo {
                 count: 42,
-                last_session: Some("2025-01-29T10:00:00Z".to_string()),
+                last_session: Timestamp::parse("2025-01-29T10:00:00Z"),
             },
             mcp_servers: vec![],
             hooks: vec![],
             agents: vec![],
             commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
         };
 
         let color_scheme = ColorScheme::default();
@@ -166,6 +270,7 @@ mod tests {
         let info = ClaudeInfo {
             version: "0.1.0".to_string(),
             config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
             plugins: vec![],
             skills: vec![],
             sessions: SessionInfo {
@@ -176,6 +281,9 @@ mod tests {
             hooks: vec![],
             agents: vec![],
             commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
         };
 
         let color_scheme = ColorScheme::default();
@@ -198,6 +306,7 @@ mod tests {
         let info = ClaudeInfo {
             version: "0.1.0".to_string(),
             config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
             plugins: vec![],
             skills: vec![],
             sessions: SessionInfo {
@@ -210,10 +319,22 @@ mod tests {
                 command: Some("npx".to_string()),
                 path: PathBuf::from("/test/.claude/mcp.json"),
                 description: None,
+                scope: crate::info::McpScope::User,
+                project: None,
+                plugin: None,
+                transport: crate::info::McpTransport::Stdio,
+                args: vec![],
+                env: vec![],
+                url: None,
+                headers: vec![],
+                shadowed_by: None,
             }],
             hooks: vec![],
             agents: vec![],
             commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
         };
 
         let color_scheme = ColorScheme::default();
@@ -233,6 +354,7 @@ mod tests {
         let info = ClaudeInfo {
             version: "0.1.0".to_string(),
             config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
             plugins: vec![],
             skills: vec![],
             sessions: SessionInfo {
@@ -245,9 +367,18 @@ mod tests {
                 hook_type: "pre-commit".to_string(),
                 path: PathBuf::from("/test/.claude/hooks/pre-commit.md"),
                 description: None,
+                event: None,
+                matcher: None,
+                command: None,
+                timeout: None,
+                settings_file: None,
+                origin: Origin::User,
             }],
             agents: vec![],
             commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
         };
 
         let color_scheme = ColorScheme::default();
@@ -267,6 +398,7 @@ mod tests {
         let info = ClaudeInfo {
             version: "0.1.0".to_string(),
             config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
             plugins: vec![],
             skills: vec![],
             sessions: SessionInfo {
@@ -279,8 +411,16 @@ mod tests {
                 name: "test-agent".to_string(),
                 description: Some("A test agent".to_string()),
                 path: PathBuf::from("/test/.claude/agents/test-agent.md"),
+                origin: Origin::User,
+                shadowed_by: None,
+                tools: vec![],
+                model: None,
+                color: None,
             }],
             commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
         };
 
         let color_scheme = ColorScheme::default();
@@ -300,6 +440,7 @@ mod tests {
         let info = ClaudeInfo {
             version: "0.1.0".to_string(),
             config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
             plugins: vec![],
             skills: vec![],
             sessions: SessionInfo {
@@ -315,7 +456,15 @@ mod tests {
                 allowed_tools: None,
                 argument_hint: None,
                 path: PathBuf::from("/test/.claude/commands/test-command.md"),
+                origin: Origin::User,
+                shadowed_by: None,
+                uses: 0,
+                model: None,
+                namespace: None,
             }],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
         };
 
         let color_scheme = ColorScheme::default();
@@ -330,25 +479,93 @@ mod tests {
         assert!(output.contains("/test-command")); // Commands are prefixed with /
     }
 
+    #[test]
+    fn test_format_compact_groups_commands_by_namespace() {
+        let command = |name: &str, namespace: Option<&str>| crate::info::CommandInfo {
+            name: name.to_string(),
+            namespace: namespace.map(String::from),
+            description: None,
+            allowed_tools: None,
+            argument_hint: None,
+            model: None,
+            path: PathBuf::from(format!("/test/.claude/commands/{}.md", name)),
+            origin: Origin::User,
+            uses: 0,
+            shadowed_by: None,
+        };
+        let info = ClaudeInfo {
+            version: "0.1.0".to_string(),
+            config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
+            plugins: vec![],
+            skills: vec![],
+            sessions: SessionInfo {
+                count: 0,
+                last_session: None,
+            },
+            mcp_servers: vec![],
+            hooks: vec![],
+            agents: vec![],
+            commands: vec![
+                command("frontend:component", Some("frontend")),
+                command("review", None),
+                command("backend:migrate", Some("backend")),
+                command("frontend:page", Some("frontend")),
+            ],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
+        };
+
+        let mut buffer = Vec::new();
+        format_compact(
+            &info,
+            &ColorScheme::default(),
+            &ColorSettings::from_env(),
+            &mut buffer,
+        )
+        .unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+
+        assert!(output.contains(
+            "COMMANDS   4 available\n  /review\n  backend:\n    /backend:migrate\n  frontend:\n    /frontend:component\n    /frontend:page\n"
+        ));
+    }
+
     #[test]
     fn test_format_compact_multiple_items() {
         let info = ClaudeInfo {
             version: "0.1.0".to_string(),
             config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
             plugins: vec![
                 PluginInfo {
                     name: "plugin1".to_string(),
                     version: Some("1.0.0".to_string()),
                     source: Source::Official,
                     path: PathBuf::from("/test"),
+                    marketplace: None,
+                    enabled: true,
                     description: None,
+                    author: None,
+                    homepage: None,
+                    repository: None,
+                    keywords: vec![],
+                    installs: vec![],
                 },
                 PluginInfo {
                     name: "plugin2".to_string(),
                     version: Some("2.0.0".to_string()),
                     source: Source::Official,
                     path: PathBuf::from("/test"),
+                    marketplace: None,
+                    enabled: true,
                     description: None,
+                    author: None,
+                    homepage: None,
+                    repository: None,
+                    keywords: vec![],
+                    installs: vec![],
                 },
             ],
             skills: vec![],
@@ -360,6 +577,9 @@ mod tests {
             hooks: vec![],
             agents: vec![],
             commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
         };
 
         let color_scheme = ColorScheme::default();
@@ -374,17 +594,203 @@ mod tests {
         assert!(output.contains("plugin2"));
     }
 
+    #[test]
+    fn test_format_compact_shows_project_dir() {
+        let info = ClaudeInfo {
+            version: "0.1.0".to_string(),
+            config_dir: PathBuf::from("/test/.claude"),
+            project_dir: Some(PathBuf::from("/work/repo")),
+            plugins: vec![],
+            skills: vec![],
+            sessions: SessionInfo {
+                count: 0,
+                last_session: None,
+            },
+            mcp_servers: vec![],
+            hooks: vec![],
+            agents: vec![],
+            commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
+        };
+
+        let mut buffer = Vec::new();
+        format_compact(
+            &info,
+            &ColorScheme::default(),
+            &ColorSettings::from_env(),
+            &mut buffer,
+        )
+        .unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+
+        assert!(output.contains("PROJECT: /work/repo"));
+    }
+
+    #[test]
+    fn test_format_compact_with_memory() {
+        let memory = |path: &str, depth: usize| MemoryInfo {
+            path: PathBuf::from(path),
+            scope: MemoryScope::Project,
+            size: 10,
+            lines: 3,
+            imported_from: None,
+            depth,
+        };
+        let info = ClaudeInfo {
+            version: "0.1.0".to_string(),
+            config_dir: PathBuf::from("/test/.claude"),
+            project_dir: Some(PathBuf::from("/work/repo")),
+            plugins: vec![],
+            skills: vec![],
+            sessions: SessionInfo {
+                count: 0,
+                last_session: None,
+            },
+            mcp_servers: vec![],
+            hooks: vec![],
+            agents: vec![],
+            commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![
+                memory("/test/.claude/CLAUDE.md", 0),
+                memory("/work/repo/CLAUDE.md", 0),
+                memory("/work/repo/docs/style.md", 1),
+            ],
+            warnings: vec![],
+        };
+
+        let mut buffer = Vec::new();
+        format_compact(
+            &info,
+            &ColorScheme::default(),
+            &ColorSettings {
+                enabled: false,
+                force_colors: false,
+            },
+            &mut buffer,
+        )
+        .unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+
+        assert!(output.contains(
+            "MEMORY     3 files, 9 lines\n  /test/.claude/CLAUDE.md\n  CLAUDE.md\n    docs/style.md\n"
+        ));
+    }
+
+    #[test]
+    fn test_format_compact_warnings_footer() {
+        let info = ClaudeInfo {
+            version: "0.1.0".to_string(),
+            config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
+            plugins: vec![],
+            skills: vec![],
+            sessions: SessionInfo {
+                count: 0,
+                last_session: None,
+            },
+            mcp_servers: vec![],
+            hooks: vec![],
+            agents: vec![],
+            commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![Diagnostic::warning(
+                ComponentKind::Plugin,
+                std::path::Path::new("/test/.claude/plugins/installed_plugins.json"),
+                crate::error::Error::MalformedFrontmatter {
+                    path: PathBuf::from("/x.md"),
+                    line: 1,
+                    column: 1,
+                    reason: "no closing `---`".to_string(),
+                },
+            )],
+        };
+
+        let mut buffer = Vec::new();
+        format_compact(
+            &info,
+            &ColorScheme::default(),
+            &ColorSettings::from_env(),
+            &mut buffer,
+        )
+        .unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+
+        assert!(output.contains("WARNINGS   1 found"));
+        assert!(output.contains("[plugin] Malformed frontmatter in /x.md"));
+    }
+
+    #[test]
+    fn test_format_compact_dims_disabled_plugins() {
+        let plugin = |name: &str, enabled: bool| PluginInfo {
+            name: name.to_string(),
+            version: None,
+            source: Source::Official,
+            path: PathBuf::from("/test"),
+            marketplace: None,
+            enabled,
+            description: None,
+            author: None,
+            homepage: None,
+            repository: None,
+            keywords: vec![],
+            installs: vec![],
+        };
+        let info = ClaudeInfo {
+            version: "0.1.0".to_string(),
+            config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
+            plugins: vec![plugin("active", true), plugin("parked", false)],
+            skills: vec![],
+            sessions: SessionInfo {
+                count: 0,
+                last_session: None,
+            },
+            mcp_servers: vec![],
+            hooks: vec![],
+            agents: vec![],
+            commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
+        };
+
+        let color_scheme = ColorScheme::default();
+        let mut buffer = Vec::new();
+        format_compact(&info, &color_scheme, &ColorSettings::force(), &mut buffer).unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+
+        let dimmed = format!(
+            "{}parked (disabled){}",
+            color_scheme.disabled.unwrap(),
+            anstyle::Reset
+        );
+        assert!(output.contains(&dimmed));
+        assert!(!output.contains("active (disabled)"));
+    }
+
     #[test]
     fn test_format_compact_no_color() {
         let info = ClaudeInfo {
             version: "0.1.0".to_string(),
             config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
             plugins: vec![PluginInfo {
                 name: "test-plugin".to_string(),
                 version: Some("1.0.0".to_string()),
                 source: Source::Official,
                 path: PathBuf::from("/test"),
+                marketplace: None,
+                enabled: true,
                 description: None,
+                author: None,
+                homepage: None,
+                repository: None,
+                keywords: vec![],
+                installs: vec![],
             }],
             skills: vec![],
             sessions: SessionInfo {
@@ -395,6 +801,9 @@ mod tests {
             hooks: vec![],
             agents: vec![],
             commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
         };
 
         let color_scheme = ColorScheme::default();
@@ -2,7 +2,12 @@
 //!
 //! Output format: NAME, PATH (plugins) or NAME, DESCRIPTION (other components)
 
-use crate::info::{ClaudeInfo, DescriptionProvider};
+use crate::formatters::{
+    group_commands, memory_label, memory_summary, settings_summary, write_warnings,
+};
+use crate::info::{
+    ClaudeInfo, DescriptionProvider, HookInfo, McpInfo, PluginInfo, SkillInfo, Timestamp,
+};
 use crate::output::{
     truncate_with_ellipsis, write_colored_padded_field, Alignment, ColorScheme, ColorSettings,
     ComponentType,
@@ -12,6 +17,11 @@ use std::io::Write;
 const NAME_WIDTH: usize = 30;
 const STATUS_WIDTH: usize = 18;
 const TYPE_WIDTH: usize = 18;
+const SCOPE_WIDTH: usize = 10;
+const ORIGIN_WIDTH: usize = 10;
+const USES_WIDTH: usize = 5;
+const LINES_WIDTH: usize = 5;
+const SIZE_WIDTH: usize = 9;
 const DESC_WIDTH: usize = 50;
 
 pub fn format_detailed(
@@ -23,6 +33,9 @@ pub fn format_detailed(
     writeln!(output, "CLAUDE-LIST v{}", info.version)?;
     writeln!(output)?;
     writeln!(output, "CONFIG: {}", info.config_dir.display())?;
+    if let Some(ref project_dir) = info.project_dir {
+        writeln!(output, "PROJECT: {}", project_dir.display())?;
+    }
     writeln!(output)?;
 
     // PLUGINS
@@ -48,6 +61,8 @@ pub fn format_detailed(
             )?;
             write!(output, " {}", plugin.path.display())?;
             writeln!(output)?;
+            write_plugin_manifest(output, plugin)?;
+            write_plugin_installs(output, plugin)?;
         }
         writeln!(output)?;
     }
@@ -55,18 +70,24 @@ pub fn format_detailed(
     // SKILLS
     if !info.skills.is_empty() {
         writeln!(output, "SKILLS     {} available", info.skills.len())?;
-        writeln!(output, "  {:<30} {:<25} DESCRIPTION", "NAME", "SOURCE")?;
         writeln!(
             output,
-            "  {:<30} {:<25} {}",
+            "  {:<30} {:<25} {:>5} DESCRIPTION",
+            "NAME", "SOURCE", "USES"
+        )?;
+        writeln!(
+            output,
+            "  {:<30} {:<25} {} {}",
             "-".repeat(NAME_WIDTH),
             "-".repeat(25),
+            "-".repeat(USES_WIDTH),
             "-".repeat(DESC_WIDTH)
         )?;
         for skill in &info.skills {
-            let description = skill.get_description().unwrap_or_default();
+            let description = shadowing_note(skill.shadowed_by.as_deref(), skill.get_description());
             let source = match &skill.location_type {
                 crate::info::SkillLocation::Global => "global".to_string(),
+                crate::info::SkillLocation::Project => "project".to_string(),
                 crate::info::SkillLocation::Plugin { plugin_name } => {
                     plugin_name.clone().unwrap_or_else(|| "plugin".to_string())
                 }
@@ -90,9 +111,11 @@ pub fn format_detailed(
                 25,
                 Alignment::Right,
             )?;
-            write!(output, " ")?;
+            write!(output, " {:>5} ", skill.uses)?;
             let truncated_desc = truncate_with_ellipsis(&description, DESC_WIDTH, "...");
             writeln!(output, "{}", truncated_desc)?;
+            write_skill_details(output, skill)?;
+            write_skill_resources(output, skill)?;
         }
         writeln!(output)?;
     }
@@ -100,8 +123,13 @@ pub fn format_detailed(
     // SESSIONS
     if info.sessions.count > 0 {
         writeln!(output, "SESSIONS   {} recorded", info.sessions.count)?;
-        if let Some(ref last) = info.sessions.last_session {
-            writeln!(output, "  Last session: {}", last)?;
+        if let Some(last) = info.sessions.last_session {
+            writeln!(
+                output,
+                "  Last session: {} ({})",
+                last,
+                last.relative_to(Timestamp::now())
+            )?;
         }
         writeln!(output)?;
     }
@@ -109,16 +137,21 @@ pub fn format_detailed(
     // MCP
     if !info.mcp_servers.is_empty() {
         writeln!(output, "MCP        {} servers", info.mcp_servers.len())?;
-        writeln!(output, "  {:<30} {:<18} DESCRIPTION", "NAME", "STATUS")?;
         writeln!(
             output,
-            "  {:<30} {:<18} {}",
+            "  {:<30} {:<18} {:<10} DESCRIPTION",
+            "NAME", "STATUS", "SCOPE"
+        )?;
+        writeln!(
+            output,
+            "  {:<30} {:<18} {:<10} {}",
             "-".repeat(NAME_WIDTH),
             "-".repeat(STATUS_WIDTH),
+            "-".repeat(SCOPE_WIDTH),
             "-".repeat(DESC_WIDTH)
         )?;
         for mcp in &info.mcp_servers {
-            let description = mcp.get_description().unwrap_or_default();
+            let description = shadowing_note(mcp.shadowed_by.as_deref(), mcp.get_description());
             write!(output, "  ")?;
             write_colored_padded_field(
                 output,
@@ -138,9 +171,10 @@ pub fn format_detailed(
                 STATUS_WIDTH,
                 Alignment::Left,
             )?;
-            write!(output, " ")?;
+            write!(output, " {:<10} ", mcp.scope.to_string())?;
             let truncated_desc = truncate_with_ellipsis(&description, DESC_WIDTH, "...");
             writeln!(output, "{}", truncated_desc)?;
+            write_mcp_launch(output, mcp)?;
         }
         writeln!(output)?;
     }
@@ -148,12 +182,17 @@ pub fn format_detailed(
     // HOOKS
     if !info.hooks.is_empty() {
         writeln!(output, "HOOKS      {} configured", info.hooks.len())?;
-        writeln!(output, "  {:<30} {:<18} DESCRIPTION", "NAME", "TYPE")?;
         writeln!(
             output,
-            "  {:<30} {:<18} {}",
+            "  {:<30} {:<18} {:<10} DESCRIPTION",
+            "NAME", "TYPE", "SOURCE"
+        )?;
+        writeln!(
+            output,
+            "  {:<30} {:<18} {:<10} {}",
             "-".repeat(NAME_WIDTH),
             "-".repeat(TYPE_WIDTH),
+            "-".repeat(ORIGIN_WIDTH),
             "-".repeat(DESC_WIDTH)
         )?;
         for hook in &info.hooks {
@@ -177,9 +216,10 @@ pub fn format_detailed(
                 TYPE_WIDTH,
                 Alignment::Left,
             )?;
-            write!(output, " ")?;
+            write!(output, " {:<10} ", hook.origin.to_string())?;
             let truncated_desc = truncate_with_ellipsis(&description, DESC_WIDTH, "...");
             writeln!(output, "{}", truncated_desc)?;
+            write_hook_details(output, hook)?;
         }
         writeln!(output)?;
     }
@@ -187,15 +227,16 @@ pub fn format_detailed(
     // AGENTS
     if !info.agents.is_empty() {
         writeln!(output, "AGENTS     {} defined", info.agents.len())?;
-        writeln!(output, "  {:<30} DESCRIPTION", "NAME")?;
+        writeln!(output, "  {:<30} {:<10} DESCRIPTION", "NAME", "SOURCE")?;
         writeln!(
             output,
-            "  {:<30} {}",
+            "  {:<30} {:<10} {}",
             "-".repeat(NAME_WIDTH),
+            "-".repeat(ORIGIN_WIDTH),
             "-".repeat(DESC_WIDTH)
         )?;
         for agent in &info.agents {
-            let description = agent.get_description().unwrap_or_default();
+            let description = shadowing_note(agent.shadowed_by.as_deref(), agent.get_description());
             write!(output, "  ")?;
             write_colored_padded_field(
                 output,
@@ -206,41 +247,278 @@ pub fn format_detailed(
                 NAME_WIDTH,
                 Alignment::Left,
             )?;
-            write!(output, " ")?;
+            write!(output, " {:<10} ", agent.origin.to_string())?;
             let truncated_desc = truncate_with_ellipsis(&description, DESC_WIDTH, "...");
             writeln!(output, "{}", truncated_desc)?;
         }
         writeln!(output)?;
     }
 
-    // COMMANDS
-    if !info.commands.is_empty() {
-        writeln!(output, "COMMANDS   {} available", info.commands.len())?;
-        writeln!(output, "  {:<30} DESCRIPTION", "NAME")?;
+    // SETTINGS
+    if !info.settings.is_empty() {
+        writeln!(output, "{}", settings_summary(&info.settings))?;
+        writeln!(output, "  {:<30} {:<10} VALUE", "KEY", "SCOPE")?;
         writeln!(
             output,
-            "  {:<30} {}",
+            "  {} {} {}",
             "-".repeat(NAME_WIDTH),
+            "-".repeat(SCOPE_WIDTH),
             "-".repeat(DESC_WIDTH)
         )?;
-        for cmd in &info.commands {
-            let description = cmd.get_description().unwrap_or_default();
+        let values = info.settings.values.iter().map(|v| {
+            let value = shadowing_note(v.shadowed_by.as_deref(), v.value.clone());
+            (v.key.clone(), v.scope, value)
+        });
+        let permissions = info.settings.permissions.iter().map(|p| {
+            let key = format!("permissions.{}", p.kind);
+            (key, p.scope, p.rule.clone())
+        });
+        for (key, scope, value) in values.chain(permissions) {
             write!(output, "  ")?;
             write_colored_padded_field(
                 output,
-                &cmd.name,
-                ComponentType::Command,
+                &key,
+                ComponentType::Settings,
                 color_scheme,
                 color_settings,
                 NAME_WIDTH,
                 Alignment::Left,
             )?;
-            write!(output, " ")?;
-            let truncated_desc = truncate_with_ellipsis(&description, DESC_WIDTH, "...");
-            writeln!(output, "{}", truncated_desc)?;
+            let truncated_value = truncate_with_ellipsis(&value, DESC_WIDTH, "...");
+            writeln!(output, " {:<10} {}", scope.to_string(), truncated_value)?;
+        }
+        writeln!(output)?;
+    }
+
+    // MEMORY
+    if !info.memory.is_empty() {
+        writeln!(output, "{}", memory_summary(&info.memory))?;
+        writeln!(
+            output,
+            "  {:<50} {:<10} {:>5} {:>9}",
+            "PATH", "SCOPE", "LINES", "SIZE"
+        )?;
+        writeln!(
+            output,
+            "  {} {} {} {}",
+            "-".repeat(DESC_WIDTH),
+            "-".repeat(SCOPE_WIDTH),
+            "-".repeat(LINES_WIDTH),
+            "-".repeat(SIZE_WIDTH)
+        )?;
+        for memory in &info.memory {
+            let label = memory_label(memory, info.project_dir.as_deref());
+            write!(output, "  ")?;
+            write_colored_padded_field(
+                output,
+                &truncate_with_ellipsis(&label, DESC_WIDTH, "..."),
+                ComponentType::Memory,
+                color_scheme,
+                color_settings,
+                DESC_WIDTH,
+                Alignment::Left,
+            )?;
+            writeln!(
+                output,
+                " {:<10} {:>5} {:>9}",
+                memory.scope.to_string(),
+                memory.lines,
+                format_size(memory.size)
+            )?;
+        }
+        writeln!(output)?;
+    }
+
+    // COMMANDS
+    if !info.commands.is_empty() {
+        writeln!(output, "COMMANDS   {} available", info.commands.len())?;
+        writeln!(
+            output,
+            "  {:<30} {:<10} {:>5} DESCRIPTION",
+            "NAME", "SOURCE", "USES"
+        )?;
+        writeln!(
+            output,
+            "  {:<30} {:<10} {} {}",
+            "-".repeat(NAME_WIDTH),
+            "-".repeat(ORIGIN_WIDTH),
+            "-".repeat(USES_WIDTH),
+            "-".repeat(DESC_WIDTH)
+        )?;
+        for (namespace, commands) in group_commands(&info.commands) {
+            let indent = match namespace {
+                Some(namespace) => {
+                    writeln!(output, "  {}:", namespace)?;
+                    "    "
+                }
+                None => "  ",
+            };
+            for cmd in commands {
+                let description = shadowing_note(cmd.shadowed_by.as_deref(), cmd.get_description());
+                // Indented names give up width so the columns stay aligned
+                write!(output, "{}", indent)?;
+                write_colored_padded_field(
+                    output,
+                    &cmd.name,
+                    ComponentType::Command,
+                    color_scheme,
+                    color_settings,
+                    NAME_WIDTH + 2 - indent.len(),
+                    Alignment::Left,
+                )?;
+                write!(output, " {:<10} {:>5} ", cmd.origin.to_string(), cmd.uses)?;
+                let truncated_desc = truncate_with_ellipsis(&description, DESC_WIDTH, "...");
+                writeln!(output, "{}", truncated_desc)?;
+            }
+        }
+        if !info.warnings.is_empty() {
+            writeln!(output)?;
+        }
+    }
+
+    write_warnings(&info.warnings, output)?;
+
+    Ok(())
+}
+
+/// Write a skill's allowed tools and, when it differs from the folder, the
+/// name its frontmatter declares
+fn write_skill_details(output: &mut dyn Write, skill: &SkillInfo) -> std::io::Result<()> {
+    if let Some(declared) = skill.mismatched_name() {
+        writeln!(
+            output,
+            "      declared name: {} (folder is {})",
+            declared, skill.name
+        )?;
+    }
+    if !skill.allowed_tools.is_empty() {
+        writeln!(
+            output,
+            "      allowed tools: {}",
+            skill.allowed_tools.join(", ")
+        )?;
+    }
+    Ok(())
+}
+
+/// Write the files a skill bundles below its table row, e.g.
+///
+/// ```text
+///       scripts/extract.py                       script      2.1 KB
+/// ```
+fn write_skill_resources(output: &mut dyn Write, skill: &SkillInfo) -> std::io::Result<()> {
+    for resource in &skill.resources {
+        let path = resource.path.to_string_lossy();
+        writeln!(
+            output,
+            "      {:<40} {:<9} {:>9}",
+            truncate_with_ellipsis(&path, 40, "..."),
+            resource.kind.to_string(),
+            format_size(resource.size)
+        )?;
+    }
+    Ok(())
+}
+
+/// `512 B`, `2.1 KB` or `3.4 MB`
+fn format_size(bytes: u64) -> String {
+    const KB: f64 = 1024.0;
+    let size = bytes as f64;
+    if size < KB {
+        format!("{} B", bytes)
+    } else if size < KB * KB {
+        format!("{:.1} KB", size / KB)
+    } else {
+        format!("{:.1} MB", size / (KB * KB))
+    }
+}
+
+/// Write the plugin manifest details below its table row
+fn write_plugin_manifest(output: &mut dyn Write, plugin: &PluginInfo) -> std::io::Result<()> {
+    if let Some(ref description) = plugin.description {
+        writeln!(output, "      {}", description)?;
+    }
+    if let Some(ref author) = plugin.author {
+        writeln!(output, "      author: {}", author)?;
+    }
+    if let Some(ref homepage) = plugin.homepage {
+        writeln!(output, "      homepage: {}", homepage)?;
+    }
+    if let Some(ref repository) = plugin.repository {
+        writeln!(output, "      repository: {}", repository)?;
+    }
+    if !plugin.keywords.is_empty() {
+        writeln!(output, "      keywords: {}", plugin.keywords.join(", "))?;
+    }
+    Ok(())
+}
+
+/// Write whether a plugin is disabled, where it came from and each place it
+/// is installed, e.g.
+/// `project scope (/work/app), installed 2026-01-14`
+fn write_plugin_installs(output: &mut dyn Write, plugin: &PluginInfo) -> std::io::Result<()> {
+    if !plugin.enabled {
+        writeln!(output, "      disabled")?;
+    }
+    if let Some(ref marketplace) = plugin.marketplace {
+        writeln!(output, "      marketplace: {}", marketplace)?;
+    }
+    for install in &plugin.installs {
+        let mut line = format!("{} scope", install.scope.as_deref().unwrap_or("unknown"));
+        if let Some(ref project_path) = install.project_path {
+            line.push_str(&format!(" ({})", project_path.display()));
+        }
+        if let Some(ref version) = install.version {
+            line.push_str(&format!(", version {}", version));
+        }
+        if let Some(ref installed_at) = install.installed_at {
+            // Timestamps are ISO 8601; the date is enough here
+            let date = installed_at.split('T').next().unwrap_or(installed_at);
+            line.push_str(&format!(", installed {}", date));
         }
+        writeln!(output, "      {}", line)?;
+    }
+    Ok(())
+}
+
+/// Prefix a description with a shadowing marker when the item is hidden
+fn shadowing_note(shadowed_by: Option<&str>, description: Option<String>) -> String {
+    let description = description.unwrap_or_default();
+    match shadowed_by {
+        Some(by) if description.is_empty() => format!("[shadowed by {}]", by),
+        Some(by) => format!("[shadowed by {}] {}", by, description),
+        None => description,
     }
+}
 
+/// Write the launch details of an MCP server below its table row.
+/// Only names of env vars and headers are shown, never their values.
+/// Write a settings hook's timeout and the file declaring it below its
+/// table row, e.g. `      timeout: 30s, settings: ~/.claude/settings.json`
+fn write_hook_details(output: &mut dyn Write, hook: &HookInfo) -> std::io::Result<()> {
+    let mut details = Vec::new();
+    if let Some(timeout) = hook.timeout {
+        details.push(format!("timeout: {}s", timeout));
+    }
+    if let Some(ref settings_file) = hook.settings_file {
+        details.push(format!("settings: {}", settings_file.display()));
+    }
+    if !details.is_empty() {
+        writeln!(output, "      {}", details.join(", "))?;
+    }
+    Ok(())
+}
+
+fn write_mcp_launch(output: &mut dyn Write, mcp: &McpInfo) -> std::io::Result<()> {
+    if let Some(launch) = mcp.launch_summary() {
+        writeln!(output, "      {}: {}", mcp.transport, launch)?;
+    }
+    if !mcp.env.is_empty() {
+        writeln!(output, "      env: {}", mcp.env.join(", "))?;
+    }
+    if !mcp.headers.is_empty() {
+        writeln!(output, "      headers: {}", mcp.headers.join(", "))?;
+    }
     Ok(())
 }
 
@@ -248,9 +526,12 @@ pub fn format_detailed(
 mod tests {
     use super::*;
     use crate::info::{
-        AgentInfo, CommandInfo, HookInfo, McpInfo, PluginInfo, SessionInfo, SkillInfo,
-        SkillLocation, Source,
+        AgentInfo, CommandInfo, HookInfo, McpInfo, McpScope, McpTransport, MemoryInfo, MemoryScope,
+        Origin, PermissionKind, PermissionRule, PluginInfo, PluginInstall, ResourceKind,
+        SessionInfo, SettingValue, SettingsInfo, SettingsScope, SkillInfo, SkillLocation,
+        SkillResource, Source,
     };
+    use std::collections::BTreeMap;
     use std::path::PathBuf;
 
     #[test]
@@ -258,12 +539,20 @@ mod tests {
         let info = ClaudeInfo {
             version: "0.1.0".to_string(),
             config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
             plugins: vec![PluginInfo {
                 name: "context7".to_string(),
                 version: Some("2.1.0".to_string()),
                 source: Source::Official,
                 path: PathBuf::from("/test/.claude/settings.json"),
+                marketplace: None,
+                enabled: true,
                 description: None,
+                author: None,
+                homepage: None,
+                repository: None,
+                keywords: vec![],
+                installs: vec![],
             }],
             skills: vec![],
             sessions: SessionInfo {
@@ -274,6 +563,9 @@ mod tests {
             hooks: vec![],
             agents: vec![],
             commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
         };
 
         let color_scheme = ColorScheme::default();
@@ -294,11 +586,133 @@ mod tests {
         assert!(!output.contains("Official plugin")); // DESCRIPTION should not appear
     }
 
+    #[test]
+    fn test_format_detailed_plugin_manifest_details() {
+        let info = ClaudeInfo {
+            version: "0.1.0".to_string(),
+            config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
+            plugins: vec![PluginInfo {
+                name: "toolkit".to_string(),
+                version: Some("1.0.0".to_string()),
+                source: Source::Official,
+                path: PathBuf::from("/test/plugins/toolkit"),
+                marketplace: None,
+                enabled: true,
+                description: Some("Handy tools".to_string()),
+                author: Some("Jane Doe".to_string()),
+                homepage: Some("https://example.com/toolkit".to_string()),
+                repository: None,
+                keywords: vec!["tools".to_string(), "productivity".to_string()],
+                installs: vec![],
+            }],
+            skills: vec![],
+            sessions: SessionInfo {
+                count: 0,
+                last_session: None,
+            },
+            mcp_servers: vec![],
+            hooks: vec![],
+            agents: vec![],
+            commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
+        };
+
+        let mut buffer = Vec::new();
+        format_detailed(
+            &info,
+            &ColorScheme::default(),
+            &ColorSettings::force(),
+            &mut buffer,
+        )
+        .unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+
+        assert!(output.contains("      Handy tools"));
+        assert!(output.contains("      author: Jane Doe"));
+        assert!(output.contains("      homepage: https://example.com/toolkit"));
+        assert!(output.contains("      keywords: tools, productivity"));
+        assert!(!output.contains("repository:"));
+    }
+
+    #[test]
+    fn test_format_detailed_plugin_installs() {
+        let info = ClaudeInfo {
+            version: "0.1.0".to_string(),
+            config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
+            plugins: vec![PluginInfo {
+                name: "formatter".to_string(),
+                version: Some("1.0.0".to_string()),
+                source: Source::ThirdParty,
+                path: PathBuf::from("/test/plugins/formatter"),
+                marketplace: Some("team".to_string()),
+                enabled: true,
+                description: None,
+                author: None,
+                homepage: None,
+                repository: None,
+                keywords: vec![],
+                installs: vec![
+                    PluginInstall {
+                        scope: Some("user".to_string()),
+                        version: Some("1.0.0".to_string()),
+                        install_path: None,
+                        project_path: None,
+                        installed_at: Some("2026-01-14T04:28:57.441Z".to_string()),
+                        last_updated: None,
+                        git_commit_sha: None,
+                        is_local: false,
+                    },
+                    PluginInstall {
+                        scope: Some("project".to_string()),
+                        version: None,
+                        install_path: None,
+                        project_path: Some(PathBuf::from("/work/app")),
+                        installed_at: Some("2026-02-01T10:00:00.000Z".to_string()),
+                        last_updated: None,
+                        git_commit_sha: None,
+                        is_local: true,
+                    },
+                ],
+            }],
+            skills: vec![],
+            sessions: SessionInfo {
+                count: 0,
+                last_session: None,
+            },
+            mcp_servers: vec![],
+            hooks: vec![],
+            agents: vec![],
+            commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
+        };
+
+        let mut buffer = Vec::new();
+        format_detailed(
+            &info,
+            &ColorScheme::default(),
+            &ColorSettings::force(),
+            &mut buffer,
+        )
+        .unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+
+        assert!(output.contains("      marketplace: team"));
+        assert!(output.contains("      user scope, version 1.0.0, installed 2026-01-14"));
+        assert!(output.contains("      project scope (/work/app), installed 2026-02-01"));
+    }
+
     #[test]
     fn test_format_detailed_skills_output() {
         let info = ClaudeInfo {
             version: "0.1.0".to_string(),
             config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
             plugins: vec![],
             skills: vec![SkillInfo {
                 name: "test-skill".to_string(),
@@ -307,6 +721,24 @@ mod tests {
                 path: PathBuf::from("/test/.claude/skills/test-skill"),
                 description: Some("A test skill".to_string()),
                 location_type: SkillLocation::Global,
+                shadowed_by: None,
+                uses: 0,
+                resources: vec![
+                    SkillResource {
+                        path: PathBuf::from("scripts/extract.py"),
+                        size: 2150,
+                        kind: ResourceKind::Script,
+                    },
+                    SkillResource {
+                        path: PathBuf::from("reference.md"),
+                        size: 300,
+                        kind: ResourceKind::Reference,
+                    },
+                ],
+                declared_name: Some("extractor".to_string()),
+                allowed_tools: vec!["Read".to_string(), "Bash(python:*)".to_string()],
+                license: None,
+                metadata: BTreeMap::new(),
             }],
             sessions: SessionInfo {
                 count: 0,
@@ -316,6 +748,9 @@ mod tests {
             hooks: vec![],
             agents: vec![],
             commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
         };
 
         let color_scheme = ColorScheme::default();
@@ -325,6 +760,13 @@ mod tests {
         format_detailed(&info, &color_scheme, &color_settings, &mut buffer).unwrap();
         let output = String::from_utf8(buffer).unwrap();
 
+        assert!(output.contains(
+            "      declared name: extractor (folder is test-skill)\n      allowed tools: Read, Bash(python:*)\n"
+        ));
+        assert!(output.contains(&format!(
+            "      {:<40} script       2.1 KB\n      {:<40} reference     300 B\n",
+            "scripts/extract.py", "reference.md"
+        )));
         assert!(output.contains("NAME"));
         assert!(output.contains("SOURCE")); // Skills now show SOURCE (location)
         assert!(output.contains("DESCRIPTION"));
@@ -334,11 +776,19 @@ mod tests {
         assert!(!output.contains("1.0.0")); // VERSION should not appear
     }
 
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(2150), "2.1 KB");
+        assert_eq!(format_size(3 * 1024 * 1024 + 400 * 1024), "3.4 MB");
+    }
+
     #[test]
     fn test_format_detailed_mcp_output() {
         let info = ClaudeInfo {
             version: "0.1.0".to_string(),
             config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
             plugins: vec![],
             skills: vec![],
             sessions: SessionInfo {
@@ -351,10 +801,22 @@ mod tests {
                 command: Some("npx".to_string()),
                 path: PathBuf::from("/test/.claude/mcp.json"),
                 description: None,
+                scope: McpScope::User,
+                project: None,
+                plugin: None,
+                transport: McpTransport::Stdio,
+                args: vec![],
+                env: vec![],
+                url: None,
+                headers: vec![],
+                shadowed_by: None,
             }],
             hooks: vec![],
             agents: vec![],
             commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
         };
 
         let color_scheme = ColorScheme::default();
@@ -370,6 +832,9 @@ mod tests {
         assert!(output.contains("test-mcp"));
         assert!(output.contains("connected"));
         assert!(output.contains("connected MCP server")); // Derived description
+        assert!(output.contains("SCOPE"));
+        assert!(output.contains("user"));
+        assert!(output.contains("stdio: npx"));
         assert!(!output.contains("PATH"));
     }
 
@@ -378,6 +843,7 @@ mod tests {
         let info = ClaudeInfo {
             version: "0.1.0".to_string(),
             config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
             plugins: vec![],
             skills: vec![],
             sessions: SessionInfo {
@@ -390,9 +856,18 @@ mod tests {
                 hook_type: "pre-commit".to_string(),
                 path: PathBuf::from("/test/.claude/hooks/pre-commit.md"),
                 description: None,
+                event: None,
+                matcher: None,
+                command: None,
+                timeout: None,
+                settings_file: None,
+                origin: Origin::User,
             }],
             agents: vec![],
             commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
         };
 
         let color_scheme = ColorScheme::default();
@@ -410,11 +885,60 @@ mod tests {
         assert!(!output.contains("PATH"));
     }
 
+    #[test]
+    fn test_format_detailed_settings_hook_details() {
+        let mut info = ClaudeInfo {
+            version: "0.1.0".to_string(),
+            config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
+            plugins: vec![],
+            skills: vec![],
+            sessions: SessionInfo {
+                count: 0,
+                last_session: None,
+            },
+            mcp_servers: vec![],
+            hooks: vec![],
+            agents: vec![],
+            commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
+        };
+        info.hooks.push(HookInfo {
+            name: "PreToolUse:Bash".to_string(),
+            hook_type: "command".to_string(),
+            path: PathBuf::from("/work/app/.claude/settings.json"),
+            description: None,
+            event: Some("PreToolUse".to_string()),
+            matcher: Some("Bash".to_string()),
+            command: Some("./check.sh".to_string()),
+            timeout: Some(30),
+            settings_file: Some(PathBuf::from("/work/app/.claude/settings.json")),
+            origin: Origin::Project,
+        });
+
+        let mut buffer = Vec::new();
+        format_detailed(
+            &info,
+            &ColorScheme::default(),
+            &ColorSettings::force(),
+            &mut buffer,
+        )
+        .unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+
+        assert!(output.contains("SOURCE"));
+        assert!(output.contains(" project    ./check.sh"));
+        assert!(output.contains("      timeout: 30s, settings: /work/app/.claude/settings.json"));
+    }
+
     #[test]
     fn test_format_detailed_agents_output() {
         let info = ClaudeInfo {
             version: "0.1.0".to_string(),
             config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
             plugins: vec![],
             skills: vec![],
             sessions: SessionInfo {
@@ -427,8 +951,16 @@ mod tests {
                 name: "database-agent".to_string(),
                 description: Some("Agent for database operations".to_string()),
                 path: PathBuf::from("/test/.claude/agents/database-agent.md"),
+                origin: Origin::User,
+                shadowed_by: None,
+                tools: vec![],
+                model: None,
+                color: None,
             }],
             commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
         };
 
         let color_scheme = ColorScheme::default();
@@ -442,6 +974,48 @@ mod tests {
         assert!(output.contains("DESCRIPTION"));
         assert!(output.contains("database-agent"));
         assert!(output.contains("Agent for database operations"));
+        assert!(output.contains("SOURCE"));
+        assert!(output.contains("user"));
+    }
+
+    #[test]
+    fn test_format_detailed_marks_shadowed_items() {
+        let info = ClaudeInfo {
+            version: "0.1.0".to_string(),
+            config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
+            plugins: vec![],
+            skills: vec![],
+            sessions: SessionInfo {
+                count: 0,
+                last_session: None,
+            },
+            mcp_servers: vec![],
+            hooks: vec![],
+            agents: vec![AgentInfo {
+                name: "reviewer".to_string(),
+                description: Some("User reviewer".to_string()),
+                path: PathBuf::from("/test/.claude/agents/reviewer.md"),
+                origin: Origin::User,
+                shadowed_by: Some("project".to_string()),
+                tools: vec![],
+                model: None,
+                color: None,
+            }],
+            commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
+        };
+
+        let color_scheme = ColorScheme::default();
+        let color_settings = ColorSettings::force();
+
+        let mut buffer = Vec::new();
+        format_detailed(&info, &color_scheme, &color_settings, &mut buffer).unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+
+        assert!(output.contains("[shadowed by project] User reviewer"));
     }
 
     #[test]
@@ -449,6 +1023,7 @@ mod tests {
         let info = ClaudeInfo {
             version: "0.1.0".to_string(),
             config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
             plugins: vec![],
             skills: vec![],
             sessions: SessionInfo {
@@ -464,7 +1039,15 @@ mod tests {
                 allowed_tools: None,
                 argument_hint: None,
                 path: PathBuf::from("/test/.claude/commands/analyze-code.md"),
+                origin: Origin::User,
+                shadowed_by: None,
+                uses: 12,
+                model: None,
+                namespace: None,
             }],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
         };
 
         let color_scheme = ColorScheme::default();
@@ -475,9 +1058,114 @@ mod tests {
         let output = String::from_utf8(buffer).unwrap();
 
         assert!(output.contains("NAME"));
+        assert!(output.contains("USES"));
         assert!(output.contains("DESCRIPTION"));
         assert!(output.contains("analyze-code"));
-        assert!(output.contains("Analyze code quality"));
+        assert!(output.contains(" user          12 Analyze code quality"));
+    }
+
+    #[test]
+    fn test_format_detailed_settings_output() {
+        let mut info = ClaudeInfo {
+            version: "0.1.0".to_string(),
+            config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
+            plugins: vec![],
+            skills: vec![],
+            sessions: SessionInfo {
+                count: 0,
+                last_session: None,
+            },
+            mcp_servers: vec![],
+            hooks: vec![],
+            agents: vec![],
+            commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
+        };
+        info.settings.values.push(SettingValue {
+            key: "model".to_string(),
+            value: Some("sonnet".to_string()),
+            scope: SettingsScope::User,
+            path: PathBuf::from("/test/.claude/settings.json"),
+            shadowed_by: Some("project".to_string()),
+        });
+        info.settings.permissions.push(PermissionRule {
+            kind: PermissionKind::Deny,
+            rule: "Read(./.env)".to_string(),
+            scope: SettingsScope::User,
+            path: PathBuf::from("/test/.claude/settings.json"),
+        });
+
+        let mut buffer = Vec::new();
+        format_detailed(
+            &info,
+            &ColorScheme::default(),
+            &ColorSettings::force(),
+            &mut buffer,
+        )
+        .unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+
+        assert!(output.contains("SETTINGS   1 keys, 1 permission rules"));
+        assert!(output.contains(" user       [shadowed by project] sonnet\n"));
+        assert!(output.contains("permissions.deny"));
+        assert!(output.contains(" user       Read(./.env)\n"));
+    }
+
+    #[test]
+    fn test_format_detailed_memory_output() {
+        let info = ClaudeInfo {
+            version: "0.1.0".to_string(),
+            config_dir: PathBuf::from("/test/.claude"),
+            project_dir: Some(PathBuf::from("/work/app")),
+            plugins: vec![],
+            skills: vec![],
+            sessions: SessionInfo {
+                count: 0,
+                last_session: None,
+            },
+            mcp_servers: vec![],
+            hooks: vec![],
+            agents: vec![],
+            commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![
+                MemoryInfo {
+                    path: PathBuf::from("/work/app/CLAUDE.md"),
+                    scope: MemoryScope::Project,
+                    size: 2150,
+                    lines: 40,
+                    imported_from: None,
+                    depth: 0,
+                },
+                MemoryInfo {
+                    path: PathBuf::from("/work/app/docs/style.md"),
+                    scope: MemoryScope::Project,
+                    size: 300,
+                    lines: 12,
+                    imported_from: Some(PathBuf::from("/work/app/CLAUDE.md")),
+                    depth: 1,
+                },
+            ],
+            warnings: vec![],
+        };
+
+        let mut buffer = Vec::new();
+        format_detailed(
+            &info,
+            &ColorScheme::default(),
+            &ColorSettings::force(),
+            &mut buffer,
+        )
+        .unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+
+        assert!(output.contains("MEMORY     2 files, 52 lines"));
+        assert!(output.contains(" project       40    2.1 KB\n"));
+        assert!(output.contains("  docs/style.md"));
+        assert!(output.contains(" project       12     300 B\n"));
     }
 
     #[test]
@@ -486,6 +1174,7 @@ mod tests {
         let info = ClaudeInfo {
             version: "0.1.0".to_string(),
             config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
             plugins: vec![],
             skills: vec![SkillInfo {
                 name: "test-skill".to_string(),
@@ -494,6 +1183,13 @@ mod tests {
                 path: PathBuf::from("/test/.claude/skills/test-skill"),
                 description: Some("This is a very long description that definitely exceeds fifty characters and should be truncated".to_string()),
                 location_type: SkillLocation::Global,
+                shadowed_by: None,
+                uses: 0,
+                resources: vec![],
+                declared_name: None,
+                allowed_tools: vec![],
+                license: None,
+                metadata: BTreeMap::new(),
             }],
             sessions: SessionInfo {
                 count: 0,
@@ -503,6 +1199,9 @@ mod tests {
             hooks: vec![],
             agents: vec![],
             commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
         };
 
         let color_scheme = ColorScheme::default();
@@ -523,6 +1222,7 @@ mod tests {
         let info = ClaudeInfo {
             version: "0.1.0".to_string(),
             config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
             plugins: vec![],
             skills: vec![],
             sessions: SessionInfo {
@@ -533,6 +1233,9 @@ mod tests {
             hooks: vec![],
             agents: vec![],
             commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
         };
 
         let color_scheme = ColorScheme::default();
@@ -18,9 +18,10 @@ impl Formatter for JsonFormatter {
 mod tests {
     use super::*;
     use crate::info::{
-        AgentInfo, CommandInfo, HookInfo, McpInfo, PluginInfo, SessionInfo, SkillInfo,
-        SkillLocation, Source,
+        AgentInfo, CommandInfo, HookInfo, McpInfo, McpScope, McpTransport, Origin, PluginInfo,
+        SessionInfo, SettingsInfo, SkillInfo, SkillLocation, Source, Timestamp,
     };
+    use std::collections::BTreeMap;
     use std::path::PathBuf;
 
     #[test]
@@ -28,12 +29,20 @@ mod tests {
         let info = ClaudeInfo {
             version: "0.1.0".to_string(),
             config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
             plugins: vec![PluginInfo {
                 name: "context7".to_string(),
                 version: Some("2.1.0".to_string()),
                 source: Source::Official,
                 path: PathBuf::from("/test/.claude/settings.json"),
+                marketplace: None,
+                enabled: true,
                 description: None,
+                author: None,
+                homepage: None,
+                repository: None,
+                keywords: vec![],
+                installs: vec![],
             }],
             skills: vec![],
             sessions: SessionInfo {
@@ -44,6 +53,9 @@ mod tests {
             hooks: vec![],
             agents: vec![],
             commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
         };
 
         let formatter = JsonFormatter;
@@ -65,12 +77,20 @@ mod tests {
         let info = ClaudeInfo {
             version: "0.1.0".to_string(),
             config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
             plugins: vec![PluginInfo {
                 name: "test-plugin".to_string(),
                 version: Some("1.0.0".to_string()),
                 source: Source::Official,
                 path: PathBuf::from("/test/plugins"),
+                marketplace: None,
+                enabled: true,
                 description: Some("A test plugin".to_string()),
+                author: None,
+                homepage: None,
+                repository: None,
+                keywords: vec![],
+                installs: vec![],
             }],
             skills: vec![SkillInfo {
                 name: "test-skill".to_string(),
@@ -79,10 +99,17 @@ mod tests {
                 path: PathBuf::from("/test/skills/test-skill"),
                 description: Some("A test skill".to_string()),
                 location_type: SkillLocation::Global,
+                shadowed_by: None,
+                uses: 0,
+                resources: vec![],
+                declared_name: None,
+                allowed_tools: vec![],
+                license: None,
+                metadata: BTreeMap::new(),
             }],
             sessions: SessionInfo {
                 count: 42,
-                last_session: Some("2025-01-29T10:00:00Z".to_string()),
+                last_session: Timestamp::parse("2025-01-29T10:00:00Z"),
             },
             mcp_servers: vec![McpInfo {
                 name: "test-mcp".to_string(),
@@ -90,17 +117,37 @@ mod tests {
                 command: Some("npx".to_string()),
                 path: PathBuf::from("/test/mcp.json"),
                 description: Some("A test MCP server".to_string()),
+                scope: McpScope::User,
+                project: None,
+                plugin: None,
+                transport: McpTransport::Stdio,
+                args: vec![],
+                env: vec![],
+                url: None,
+                headers: vec![],
+                shadowed_by: None,
             }],
             hooks: vec![HookInfo {
                 name: "pre-commit".to_string(),
                 hook_type: "pre-commit".to_string(),
                 path: PathBuf::from("/test/hooks/pre-commit.md"),
                 description: Some("A pre-commit hook".to_string()),
+                event: None,
+                matcher: None,
+                command: None,
+                timeout: None,
+                settings_file: None,
+                origin: Origin::User,
             }],
             agents: vec![AgentInfo {
                 name: "test-agent".to_string(),
                 description: Some("A test agent".to_string()),
                 path: PathBuf::from("/test/agents/test-agent.md"),
+                origin: Origin::User,
+                shadowed_by: None,
+                tools: vec![],
+                model: None,
+                color: None,
             }],
             commands: vec![CommandInfo {
                 name: "test-command".to_string(),
@@ -108,7 +155,15 @@ mod tests {
                 allowed_tools: Some("Bash, Read".to_string()),
                 argument_hint: Some("[test]".to_string()),
                 path: PathBuf::from("/test/commands/test-command.md"),
+                origin: Origin::User,
+                shadowed_by: None,
+                uses: 0,
+                model: None,
+                namespace: None,
             }],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
         };
 
         let formatter = JsonFormatter;
@@ -133,6 +188,8 @@ mod tests {
         assert_eq!(json["plugins"].as_array().unwrap().len(), 1);
         assert_eq!(json["skills"].as_array().unwrap().len(), 1);
         assert_eq!(json["mcp_servers"].as_array().unwrap().len(), 1);
+        assert_eq!(json["mcp_servers"][0]["transport"], "stdio");
+        assert_eq!(json["mcp_servers"][0]["scope"], "user");
         assert_eq!(json["hooks"].as_array().unwrap().len(), 1);
         assert_eq!(json["agents"].as_array().unwrap().len(), 1);
         assert_eq!(json["commands"].as_array().unwrap().len(), 1);
@@ -157,6 +214,7 @@ mod tests {
         let info = ClaudeInfo {
             version: "0.1.0".to_string(),
             config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
             plugins: vec![],
             skills: vec![],
             sessions: SessionInfo {
@@ -167,6 +225,9 @@ mod tests {
             hooks: vec![],
             agents: vec![],
             commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
         };
 
         let formatter = JsonFormatter;
@@ -194,6 +255,7 @@ mod tests {
         let info = ClaudeInfo {
             version: "0.1.0".to_string(),
             config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
             plugins: vec![],
             skills: vec![SkillInfo {
                 name: "plugin-skill".to_string(),
@@ -204,6 +266,13 @@ mod tests {
                 location_type: SkillLocation::Plugin {
                     plugin_name: Some("test-plugin".to_string()),
                 },
+                shadowed_by: None,
+                uses: 0,
+                resources: vec![],
+                declared_name: None,
+                allowed_tools: vec![],
+                license: None,
+                metadata: BTreeMap::new(),
             }],
             sessions: SessionInfo {
                 count: 0,
@@ -213,6 +282,9 @@ mod tests {
             hooks: vec![],
             agents: vec![],
             commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
         };
 
         let formatter = JsonFormatter;
@@ -234,12 +306,20 @@ mod tests {
         let info = ClaudeInfo {
             version: "0.1.0".to_string(),
             config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
             plugins: vec![PluginInfo {
                 name: "custom-plugin".to_string(),
                 version: Some("1.0.0".to_string()),
                 source: Source::ThirdParty,
                 path: PathBuf::from("/test/plugins"),
+                marketplace: None,
+                enabled: true,
                 description: None,
+                author: None,
+                homepage: None,
+                repository: None,
+                keywords: vec![],
+                installs: vec![],
             }],
             skills: vec![],
             sessions: SessionInfo {
@@ -250,6 +330,9 @@ mod tests {
             hooks: vec![],
             agents: vec![],
             commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
         };
 
         let formatter = JsonFormatter;
@@ -267,12 +350,20 @@ mod tests {
         let info = ClaudeInfo {
             version: "0.1.0".to_string(),
             config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
             plugins: vec![PluginInfo {
                 name: "test-plugin".to_string(),
                 version: Some("1.0.0".to_string()),
                 source: Source::Official,
                 path: PathBuf::from("/test"),
+                marketplace: None,
+                enabled: true,
                 description: None,
+                author: None,
+                homepage: None,
+                repository: None,
+                keywords: vec![],
+                installs: vec![],
             }],
             skills: vec![],
             sessions: SessionInfo {
@@ -283,6 +374,9 @@ mod tests {
             hooks: vec![],
             agents: vec![],
             commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
         };
 
         let formatter = JsonFormatter;
@@ -304,12 +398,20 @@ mod tests {
         let info = ClaudeInfo {
             version: "0.1.0".to_string(),
             config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
             plugins: vec![PluginInfo {
                 name: "test-plugin".to_string(),
                 version: None,
                 source: Source::Official,
                 path: PathBuf::from("/test"),
+                marketplace: None,
+                enabled: true,
                 description: None,
+                author: None,
+                homepage: None,
+                repository: None,
+                keywords: vec![],
+                installs: vec![],
             }],
             skills: vec![],
             sessions: SessionInfo {
@@ -320,6 +422,9 @@ mod tests {
             hooks: vec![],
             agents: vec![],
             commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
         };
 
         let formatter = JsonFormatter;
@@ -0,0 +1,138 @@
+//! Marketplace output formatter (for --marketplaces)
+//!
+//! Output format: each marketplace with its source, then the plugins it offers
+
+use crate::formatters::write_warnings;
+use crate::info::{Diagnostic, MarketplaceInfo};
+use crate::output::{
+    truncate_with_ellipsis, write_colored_padded_field, Alignment, ColorScheme, ColorSettings,
+    ComponentType,
+};
+use std::io::Write;
+
+const NAME_WIDTH: usize = 30;
+const STATUS_WIDTH: usize = 10;
+const DESC_WIDTH: usize = 50;
+
+pub fn format_marketplaces(
+    marketplaces: &[MarketplaceInfo],
+    warnings: &[Diagnostic],
+    color_scheme: &ColorScheme,
+    color_settings: &ColorSettings,
+    output: &mut dyn Write,
+) -> std::io::Result<()> {
+    if marketplaces.is_empty() {
+        writeln!(output, "No marketplaces registered")?;
+    } else {
+        writeln!(output, "MARKETPLACES {} registered", marketplaces.len())?;
+        writeln!(output)?;
+    }
+
+    for marketplace in marketplaces {
+        writeln!(output, "  {}", marketplace.name)?;
+        writeln!(output, "      source: {}", marketplace.source)?;
+        if let Some(ref last_updated) = marketplace.last_updated {
+            writeln!(output, "      updated: {}", last_updated)?;
+        }
+        writeln!(output, "      path: {}", marketplace.path.display())?;
+
+        if marketplace.plugins.is_empty() {
+            writeln!(output, "      no plugin catalog found")?;
+            writeln!(output)?;
+            continue;
+        }
+
+        let installed = marketplace.plugins.iter().filter(|p| p.installed).count();
+        writeln!(
+            output,
+            "      {} plugins, {} installed",
+            marketplace.plugins.len(),
+            installed
+        )?;
+        for plugin in &marketplace.plugins {
+            write!(output, "      ")?;
+            write_colored_padded_field(
+                output,
+                &plugin.name,
+                ComponentType::Plugin,
+                color_scheme,
+                color_settings,
+                NAME_WIDTH,
+                Alignment::Left,
+            )?;
+            let status = if plugin.installed { "installed" } else { "" };
+            let description = plugin.description.as_deref().unwrap_or_default();
+            writeln!(
+                output,
+                " {:<width$} {}",
+                status,
+                truncate_with_ellipsis(description, DESC_WIDTH, "..."),
+                width = STATUS_WIDTH
+            )?;
+        }
+        writeln!(output)?;
+    }
+
+    write_warnings(warnings, output)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::info::MarketplacePlugin;
+    use std::path::PathBuf;
+
+    fn render(marketplaces: &[MarketplaceInfo]) -> String {
+        let mut buffer = Vec::new();
+        format_marketplaces(
+            marketplaces,
+            &[],
+            &ColorScheme::default(),
+            &ColorSettings::force(),
+            &mut buffer,
+        )
+        .unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn test_format_marketplaces_output() {
+        let marketplaces = vec![MarketplaceInfo {
+            name: "claude-plugins-official".to_string(),
+            source: "github:anthropics/claude-plugins-official".to_string(),
+            path: PathBuf::from("/test/.claude/plugins/marketplaces/claude-plugins-official"),
+            last_updated: Some("2026-01-14T04:28:57.441Z".to_string()),
+            plugins: vec![
+                MarketplacePlugin {
+                    name: "context7".to_string(),
+                    version: None,
+                    description: Some("Up-to-date docs".to_string()),
+                    installed: true,
+                },
+                MarketplacePlugin {
+                    name: "github".to_string(),
+                    version: None,
+                    description: None,
+                    installed: false,
+                },
+            ],
+        }];
+
+        let output = render(&marketplaces);
+        assert!(output.contains("MARKETPLACES 1 registered"));
+        assert!(output.contains("  claude-plugins-official"));
+        assert!(output.contains("source: github:anthropics/claude-plugins-official"));
+        assert!(output.contains("updated: 2026-01-14T04:28:57.441Z"));
+        assert!(output.contains("2 plugins, 1 installed"));
+        assert!(output.contains("installed  Up-to-date docs"));
+        assert!(output.contains("github"));
+    }
+
+    #[test]
+    fn test_format_marketplaces_empty() {
+        let output = render(&[]);
+        assert_eq!(output, "No marketplaces registered\n");
+    }
+}
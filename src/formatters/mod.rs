@@ -3,10 +3,66 @@
 pub mod compact;
 pub mod detailed;
 pub mod json;
+pub mod marketplaces;
+pub mod sessions;
 
-use crate::info::ClaudeInfo;
+use crate::info::{ClaudeInfo, CommandInfo, Diagnostic, MemoryInfo, SettingsInfo};
+use std::collections::BTreeMap;
 use std::io::Write;
+use std::path::Path;
 
 pub trait Formatter {
     fn format(&self, info: &ClaudeInfo, output: &mut dyn Write) -> std::io::Result<()>;
 }
+
+/// Write the non-fatal parse warnings as a footer, if there are any
+pub fn write_warnings(warnings: &[Diagnostic], output: &mut dyn Write) -> std::io::Result<()> {
+    if warnings.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(output, "WARNINGS   {} found", warnings.len())?;
+    for warning in warnings {
+        writeln!(output, "  [{}] {}", warning.component, warning)?;
+    }
+
+    Ok(())
+}
+
+/// Commands grouped by namespace: top-level commands first, then each
+/// namespace in alphabetical order, keeping the original order within a group
+pub fn group_commands(commands: &[CommandInfo]) -> Vec<(Option<&str>, Vec<&CommandInfo>)> {
+    let mut groups: BTreeMap<Option<&str>, Vec<&CommandInfo>> = BTreeMap::new();
+    for command in commands {
+        groups
+            .entry(command.namespace.as_deref())
+            .or_default()
+            .push(command);
+    }
+    groups.into_iter().collect()
+}
+
+/// A memory file's path relative to the project when it is inside it,
+/// indented two spaces per import level
+pub fn memory_label(memory: &MemoryInfo, project_dir: Option<&Path>) -> String {
+    let path = project_dir
+        .and_then(|dir| memory.path.strip_prefix(dir).ok())
+        .unwrap_or(&memory.path);
+    format!("{}{}", "  ".repeat(memory.depth), path.display())
+}
+
+/// Header line for the memory section, e.g. `MEMORY     3 files, 120 lines`
+pub fn memory_summary(memory: &[MemoryInfo]) -> String {
+    let lines: usize = memory.iter().map(|m| m.lines).sum();
+    format!("MEMORY     {} files, {} lines", memory.len(), lines)
+}
+
+/// Header line for the settings section, e.g.
+/// `SETTINGS   4 keys, 3 permission rules`
+pub fn settings_summary(settings: &SettingsInfo) -> String {
+    format!(
+        "SETTINGS   {} keys, {} permission rules",
+        settings.values.len(),
+        settings.permissions.len()
+    )
+}
@@ -0,0 +1,172 @@
+//! Session analytics formatter (for the `sessions` subcommand)
+//!
+//! Output format: PROJECT, SESSIONS, MESSAGES, DURATION, FIRST ACTIVITY,
+//! LAST ACTIVITY
+
+use crate::formatters::write_warnings;
+use crate::info::{Diagnostic, ProjectSessions, Timestamp};
+use crate::output::truncate_with_ellipsis;
+use std::io::Write;
+
+const PROJECT_WIDTH: usize = 40;
+const COUNT_WIDTH: usize = 9;
+const DURATION_WIDTH: usize = 10;
+/// `YYYY-MM-DD HH:MM`
+const DATE_WIDTH: usize = 16;
+
+pub fn format_sessions(
+    projects: &[ProjectSessions],
+    warnings: &[Diagnostic],
+    output: &mut dyn Write,
+) -> std::io::Result<()> {
+    if projects.is_empty() {
+        writeln!(output, "No session transcripts found")?;
+        write_warnings(warnings, output)?;
+        return Ok(());
+    }
+
+    let now = Timestamp::now();
+    writeln!(
+        output,
+        "  {:<project$} {:>count$} {:>count$} {:>duration$}  {:<date$}  LAST ACTIVITY",
+        "PROJECT",
+        "SESSIONS",
+        "MESSAGES",
+        "DURATION",
+        "FIRST ACTIVITY",
+        project = PROJECT_WIDTH,
+        count = COUNT_WIDTH,
+        duration = DURATION_WIDTH,
+        date = DATE_WIDTH
+    )?;
+    writeln!(
+        output,
+        "  {} {} {} {}  {}  {}",
+        "-".repeat(PROJECT_WIDTH),
+        "-".repeat(COUNT_WIDTH),
+        "-".repeat(COUNT_WIDTH),
+        "-".repeat(DURATION_WIDTH),
+        "-".repeat(DATE_WIDTH),
+        "-".repeat(DATE_WIDTH)
+    )?;
+    for project in projects {
+        writeln!(
+            output,
+            "  {:<project$} {:>count$} {:>count$} {:>duration$}  {:<date$}  {}",
+            truncate_with_ellipsis(&project.project, PROJECT_WIDTH, "..."),
+            project.sessions,
+            project.messages,
+            format_duration(project.duration_secs),
+            project
+                .first_activity
+                .map(format_minutes)
+                .unwrap_or_else(|| "-".to_string()),
+            project
+                .last_activity
+                .map(|ts| format_timestamp(ts, now))
+                .unwrap_or_else(|| "-".to_string()),
+            project = PROJECT_WIDTH,
+            count = COUNT_WIDTH,
+            duration = DURATION_WIDTH,
+            date = DATE_WIDTH
+        )?;
+    }
+    writeln!(output)?;
+
+    let sessions: usize = projects.iter().map(|p| p.sessions).sum();
+    writeln!(
+        output,
+        "{} session(s) in {} project(s)",
+        sessions,
+        projects.len()
+    )?;
+
+    if !warnings.is_empty() {
+        writeln!(output)?;
+        write_warnings(warnings, output)?;
+    }
+
+    Ok(())
+}
+
+/// `3h 12m`, `5m 3s` or `42s`
+fn format_duration(secs: u64) -> String {
+    let (hours, minutes, seconds) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// `2026-01-14 04:28`
+fn format_minutes(ts: Timestamp) -> String {
+    ts.to_string()
+        .replacen('T', " ", 1)
+        .chars()
+        .take(DATE_WIDTH)
+        .collect()
+}
+
+/// `2026-01-14 04:28 (3 hours ago)`
+fn format_timestamp(ts: Timestamp, now: Timestamp) -> String {
+    format!("{} ({})", format_minutes(ts), ts.relative_to(now))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(42), "42s");
+        assert_eq!(format_duration(303), "5m 3s");
+        assert_eq!(format_duration(3 * 3600 + 12 * 60 + 5), "3h 12m");
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        let ts = Timestamp::parse("2026-01-15T09:20:59.500Z").unwrap();
+        let now = Timestamp::parse("2026-01-15T12:30:00Z").unwrap();
+        assert_eq!(format_timestamp(ts, now), "2026-01-15 09:20 (3 hours ago)");
+    }
+
+    #[test]
+    fn test_format_sessions_table() {
+        let projects = vec![ProjectSessions {
+            project: "/work/app".to_string(),
+            path: PathBuf::from("/test/.claude/projects/-work-app"),
+            sessions: 2,
+            messages: 5,
+            first_activity: Timestamp::parse("2026-01-14T10:00:00.000Z"),
+            last_activity: Timestamp::parse("2026-01-15T09:20:00.000Z"),
+            duration_secs: 3000,
+        }];
+
+        let mut buffer = Vec::new();
+        format_sessions(&projects, &[], &mut buffer).unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+
+        assert!(output.contains("PROJECT"));
+        assert!(output.contains("FIRST ACTIVITY    LAST ACTIVITY"));
+        assert!(output.contains("  2026-01-14 10:00  2026-01-15 09:20 ("));
+        assert!(output.contains("/work/app"));
+        assert!(output.contains("50m 0s"));
+        assert!(output.contains("2026-01-15 09:20 ("));
+        assert!(output.contains(" ago)"));
+        assert!(output.contains("2 session(s) in 1 project(s)"));
+    }
+
+    #[test]
+    fn test_format_sessions_empty() {
+        let mut buffer = Vec::new();
+        format_sessions(&[], &[], &mut buffer).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "No session transcripts found\n"
+        );
+    }
+}
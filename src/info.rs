@@ -1,6 +1,7 @@
 //! Data structures for Claude Code environment information
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 // ====================
@@ -19,6 +20,9 @@ pub trait DescriptionProvider {
 pub struct ClaudeInfo {
     pub version: String,
     pub config_dir: PathBuf,
+    /// Project root whose .claude directory was merged in (project-aware mode)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_dir: Option<PathBuf>,
     pub plugins: Vec<PluginInfo>,
     pub skills: Vec<SkillInfo>,
     pub sessions: SessionInfo,
@@ -26,6 +30,15 @@ pub struct ClaudeInfo {
     pub hooks: Vec<HookInfo>,
     pub agents: Vec<AgentInfo>,
     pub commands: Vec<CommandInfo>,
+    /// Keys and permission rules from the settings files
+    #[serde(default)]
+    pub settings: SettingsInfo,
+    /// CLAUDE.md memory files in load order
+    #[serde(default)]
+    pub memory: Vec<MemoryInfo>,
+    /// Problems found while parsing that did not stop the listing
+    #[serde(default)]
+    pub warnings: Vec<Diagnostic>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -45,6 +58,36 @@ pub enum Source {
     ThirdParty,
 }
 
+/// Represents where a component (agent, command, hook) was defined.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(tag = "type")]
+pub enum Origin {
+    /// User-level components from ~/.claude/
+    #[default]
+    User,
+    /// Project-level components from <repo>/.claude/
+    Project,
+    /// Components shipped inside an installed plugin
+    Plugin {
+        /// The name of the plugin this component belongs to
+        #[serde(skip_serializing_if = "Option::is_none")]
+        plugin_name: Option<String>,
+    },
+}
+
+impl std::fmt::Display for Origin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Origin::User => write!(f, "user"),
+            Origin::Project => write!(f, "project"),
+            Origin::Plugin {
+                plugin_name: Some(name),
+            } => write!(f, "plugin:{}", name),
+            Origin::Plugin { plugin_name: None } => write!(f, "plugin"),
+        }
+    }
+}
+
 /// Represents where a skill is located.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(tag = "type")]
@@ -52,6 +95,8 @@ pub enum SkillLocation {
     /// Global skills from ~/.claude/skills/
     #[default]
     Global,
+    /// Project skills from <repo>/.claude/skills/
+    Project,
     /// Skills bundled within a plugin from plugins/cache/*/*/skills/
     Plugin {
         /// The name of the plugin this skill belongs to
@@ -66,8 +111,56 @@ pub struct PluginInfo {
     pub version: Option<String>,
     pub source: Source,
     pub path: PathBuf,
+    /// Marketplace the plugin was installed from (the `@source` half of
+    /// its `name@source` key)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marketplace: Option<String>,
+    /// Whether the plugin is enabled once `enabledPlugins` from every
+    /// settings layer is applied; plugins not mentioned there are enabled
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Author name from the plugin manifest
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+    /// Every install record from installed_plugins.json; a plugin can be
+    /// installed at user scope and in several projects
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub installs: Vec<PluginInstall>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// One install record of a plugin in installed_plugins.json
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PluginInstall {
+    /// `user`, `project` or `local`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub install_path: Option<PathBuf>,
+    /// Project the plugin is installed for (project and local scope)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_path: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub installed_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_commit_sha: Option<String>,
+    #[serde(default)]
+    pub is_local: bool,
 }
 
 impl DescriptionProvider for PluginInfo {
@@ -82,6 +175,33 @@ impl DescriptionProvider for PluginInfo {
     }
 }
 
+/// A plugin marketplace registered in plugins/known_marketplaces.json
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketplaceInfo {
+    pub name: String,
+    /// Where the marketplace is fetched from, e.g. `github:owner/repo`
+    pub source: String,
+    /// Local clone of the marketplace repository
+    pub path: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<String>,
+    /// Plugins listed in the marketplace's `.claude-plugin/marketplace.json`
+    #[serde(default)]
+    pub plugins: Vec<MarketplacePlugin>,
+}
+
+/// A plugin offered by a marketplace
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketplacePlugin {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether this plugin is installed from this marketplace
+    pub installed: bool,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SkillInfo {
     pub name: String,
@@ -89,9 +209,103 @@ pub struct SkillInfo {
     pub source: Source,
     pub path: PathBuf,
     pub description: Option<String>,
+    /// `name` from SKILL.md frontmatter, which may differ from the folder
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub declared_name: Option<String>,
+    /// Tools the skill may use without asking, from `allowed-tools`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_tools: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// Free-form `metadata` mapping from the frontmatter
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, serde_json::Value>,
     /// Where this skill is located (global or from plugin)
     #[serde(default)]
     pub location_type: SkillLocation,
+    /// Files bundled with the skill besides its SKILL.md or skill.yaml
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resources: Vec<SkillResource>,
+    /// How often the skill was invoked as a slash command, from history.jsonl
+    #[serde(default)]
+    pub uses: usize,
+    /// Label of the higher-precedence item that hides this one, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shadowed_by: Option<String>,
+}
+
+/// A file shipped inside a skill folder
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillResource {
+    /// Path relative to the skill folder, e.g. `scripts/extract.py`
+    pub path: PathBuf,
+    /// Size in bytes
+    pub size: u64,
+    pub kind: ResourceKind,
+}
+
+/// What a bundled skill file is for, judged by its folder or extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResourceKind {
+    Script,
+    Reference,
+    Template,
+    Asset,
+    Other,
+}
+
+impl ResourceKind {
+    /// `scripts/`, `references/`, `templates/` and `assets/` folders decide
+    /// first; otherwise executables' and documents' extensions
+    pub fn classify(path: &std::path::Path) -> Self {
+        let folder = path
+            .components()
+            .next()
+            .filter(|_| path.components().count() > 1)
+            .and_then(|c| c.as_os_str().to_str())
+            .map(str::to_lowercase);
+        match folder.as_deref() {
+            Some("scripts" | "bin") => return ResourceKind::Script,
+            Some("references" | "reference" | "docs") => return ResourceKind::Reference,
+            Some("templates" | "template") => return ResourceKind::Template,
+            Some("assets") => return ResourceKind::Asset,
+            _ => {}
+        }
+
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_lowercase);
+        match extension.as_deref() {
+            Some("sh" | "bash" | "py" | "js" | "mjs" | "ts" | "rb" | "pl" | "ps1") => {
+                ResourceKind::Script
+            }
+            Some("md" | "txt" | "pdf" | "rst") => ResourceKind::Reference,
+            _ => ResourceKind::Other,
+        }
+    }
+}
+
+impl std::fmt::Display for ResourceKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            ResourceKind::Script => "script",
+            ResourceKind::Reference => "reference",
+            ResourceKind::Template => "template",
+            ResourceKind::Asset => "asset",
+            ResourceKind::Other => "other",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+impl SkillInfo {
+    /// The declared name when it doesn't match the folder the skill is
+    /// listed under
+    pub fn mismatched_name(&self) -> Option<&str> {
+        self.declared_name.as_deref().filter(|n| *n != self.name)
+    }
 }
 
 impl DescriptionProvider for SkillInfo {
@@ -103,7 +317,196 @@ impl DescriptionProvider for SkillInfo {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SessionInfo {
     pub count: usize,
-    pub last_session: Option<String>,
+    pub last_session: Option<Timestamp>,
+}
+
+/// A point in time as milliseconds since the Unix epoch. Serialized as an
+/// RFC 3339 string in UTC; deserializes from that, from other RFC 3339
+/// offsets, or from epoch seconds or milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(pub i64);
+
+impl Timestamp {
+    pub fn now() -> Self {
+        let millis = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+        Timestamp(millis)
+    }
+
+    /// Epoch numbers below 10^11 are taken as seconds (10^11 ms is 1973)
+    pub fn from_epoch(value: i64) -> Self {
+        if value.unsigned_abs() < 100_000_000_000 {
+            Timestamp(value * 1000)
+        } else {
+            Timestamp(value)
+        }
+    }
+
+    /// Parse an RFC 3339 timestamp or an epoch number written as a string
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) {
+            return value.parse().ok().map(Self::from_epoch);
+        }
+        crate::time::parse_timestamp(value).map(Timestamp)
+    }
+
+    /// Read a JSON `timestamp`-style value, either a number or a string
+    pub fn from_json(value: &serde_json::Value) -> Option<Self> {
+        match value {
+            serde_json::Value::Number(n) => n.as_i64().map(Self::from_epoch),
+            serde_json::Value::String(s) => Self::parse(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_millis(&self) -> i64 {
+        self.0
+    }
+
+    /// `3 hours ago`, `just now` or `in 2 days`, relative to `now`
+    pub fn relative_to(&self, now: Timestamp) -> String {
+        let delta = now.0.saturating_sub(self.0) / 1000;
+        let secs = delta.unsigned_abs();
+        if secs < 60 {
+            return "just now".to_string();
+        }
+        let (amount, unit) = match secs {
+            s if s < 3600 => (s / 60, "minute"),
+            s if s < 86_400 => (s / 3600, "hour"),
+            s if s < 30 * 86_400 => (s / 86_400, "day"),
+            s if s < 365 * 86_400 => (s / (30 * 86_400), "month"),
+            s => (s / (365 * 86_400), "year"),
+        };
+        let plural = if amount == 1 { "" } else { "s" };
+        if delta < 0 {
+            format!("in {} {}{}", amount, unit, plural)
+        } else {
+            format!("{} {}{} ago", amount, unit, plural)
+        }
+    }
+}
+
+impl std::fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", crate::time::utc_datetime(self.0))
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        Timestamp::from_json(&value)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid timestamp: {}", value)))
+    }
+}
+
+/// Session activity for one project, from the transcripts in
+/// `projects/<encoded-path>/*.jsonl`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectSessions {
+    /// Project directory (the sessions' `cwd`, or the decoded folder name)
+    pub project: String,
+    /// Folder holding the project's transcripts
+    pub path: PathBuf,
+    pub sessions: usize,
+    /// User and assistant messages across all sessions
+    pub messages: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_activity: Option<Timestamp>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_activity: Option<Timestamp>,
+    /// Sum of each session's first-to-last message span
+    pub duration_secs: u64,
+}
+
+/// Token counts reported in the `usage` block of assistant messages
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_creation_tokens: u64,
+    pub cache_read_tokens: u64,
+}
+
+impl TokenUsage {
+    pub fn total(&self) -> u64 {
+        self.input_tokens + self.output_tokens + self.cache_creation_tokens + self.cache_read_tokens
+    }
+
+    pub fn add(&mut self, other: &TokenUsage) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cache_creation_tokens += other.cache_creation_tokens;
+        self.cache_read_tokens += other.cache_read_tokens;
+    }
+}
+
+/// Token usage of one assistant message in a session transcript
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageRecord {
+    pub project: String,
+    pub model: String,
+    /// When the message was sent, if the transcript records it
+    pub timestamp: Option<Timestamp>,
+    pub usage: TokenUsage,
+}
+
+/// Configuration scope an MCP server was declared in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum McpScope {
+    /// Available in every project (~/.claude.json `mcpServers`)
+    #[default]
+    User,
+    /// Shared with the repository (`.mcp.json` at the project root)
+    Project,
+    /// Private to one project (~/.claude.json `projects.<path>.mcpServers`)
+    Local,
+    /// Shipped inside an installed plugin (`.mcp.json` at the plugin root)
+    Plugin,
+}
+
+impl std::fmt::Display for McpScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            McpScope::User => write!(f, "user"),
+            McpScope::Project => write!(f, "project"),
+            McpScope::Local => write!(f, "local"),
+            McpScope::Plugin => write!(f, "plugin"),
+        }
+    }
+}
+
+/// Transport used to talk to an MCP server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum McpTransport {
+    /// Local process speaking over stdin/stdout
+    #[default]
+    Stdio,
+    /// Remote server using Server-Sent Events
+    Sse,
+    /// Remote server using streamable HTTP
+    Http,
+}
+
+impl std::fmt::Display for McpTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            McpTransport::Stdio => write!(f, "stdio"),
+            McpTransport::Sse => write!(f, "sse"),
+            McpTransport::Http => write!(f, "http"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -114,6 +517,49 @@ pub struct McpInfo {
     pub path: PathBuf,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Scope the server is configured in
+    #[serde(default)]
+    pub scope: McpScope,
+    /// Project directory for project and local scoped servers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<PathBuf>,
+    /// Name of the plugin that ships a plugin scoped server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plugin: Option<String>,
+    /// Transport type (stdio, sse or http)
+    #[serde(default)]
+    pub transport: McpTransport,
+    /// Arguments passed to `command` for stdio servers
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// Names of environment variables set for the server (values are omitted)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env: Vec<String>,
+    /// Endpoint for sse/http servers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Names of HTTP headers sent to the server (values are omitted)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<String>,
+    /// Label of the higher-precedence item that hides this one, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shadowed_by: Option<String>,
+}
+
+impl McpInfo {
+    /// How the server is reached: the full command line for stdio servers,
+    /// or the URL for remote ones.
+    pub fn launch_summary(&self) -> Option<String> {
+        match self.transport {
+            McpTransport::Stdio => self.command.as_ref().map(|command| {
+                std::iter::once(command.as_str())
+                    .chain(self.args.iter().map(String::as_str))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            }),
+            McpTransport::Sse | McpTransport::Http => self.url.clone(),
+        }
+    }
 }
 
 impl DescriptionProvider for McpInfo {
@@ -132,6 +578,24 @@ pub struct HookInfo {
     pub path: PathBuf,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Lifecycle event the hook runs on (e.g. PreToolUse, Stop)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event: Option<String>,
+    /// Tool matcher pattern, if the event supports one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matcher: Option<String>,
+    /// Shell command (or prompt) executed by the hook
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Timeout in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+    /// settings.json file the hook was declared in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settings_file: Option<PathBuf>,
+    /// Whether the hook comes from user or project settings
+    #[serde(default)]
+    pub origin: Origin,
 }
 
 impl DescriptionProvider for HookInfo {
@@ -139,6 +603,9 @@ impl DescriptionProvider for HookInfo {
         if let Some(ref desc) = self.description {
             return Some(desc.clone());
         }
+        if let Some(ref command) = self.command {
+            return Some(command.clone());
+        }
         Some(format!("{} hook", self.hook_type))
     }
 }
@@ -147,7 +614,20 @@ impl DescriptionProvider for HookInfo {
 pub struct AgentInfo {
     pub name: String,
     pub description: Option<String>,
+    /// Tools the agent may use; empty when it inherits every tool
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
     pub path: PathBuf,
+    /// Whether the agent is defined at user or project level
+    #[serde(default)]
+    pub origin: Origin,
+    /// Label of the higher-precedence item that hides this one, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shadowed_by: Option<String>,
 }
 
 impl DescriptionProvider for AgentInfo {
@@ -158,11 +638,27 @@ impl DescriptionProvider for AgentInfo {
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CommandInfo {
+    /// Invocation name without the slash, e.g. `frontend:component`
     pub name: String,
+    /// Subdirectory path of a nested command, e.g. `frontend` for
+    /// `commands/frontend/component.md`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
     pub description: Option<String>,
     pub allowed_tools: Option<String>,
     pub argument_hint: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
     pub path: PathBuf,
+    /// Whether the command is defined at user or project level
+    #[serde(default)]
+    pub origin: Origin,
+    /// How often the command was run, from the prompts in history.jsonl
+    #[serde(default)]
+    pub uses: usize,
+    /// Label of the higher-precedence item that hides this one, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shadowed_by: Option<String>,
 }
 
 impl DescriptionProvider for CommandInfo {
@@ -171,10 +667,319 @@ impl DescriptionProvider for CommandInfo {
     }
 }
 
+/// Which settings file a setting comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SettingsScope {
+    /// `settings.json` in the config directory
+    User,
+    /// `.claude/settings.json` in the project
+    Project,
+    /// `.claude/settings.local.json` in the project
+    Local,
+    /// `managed-settings.json` installed by an administrator
+    Managed,
+}
+
+impl std::fmt::Display for SettingsScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SettingsScope::User => write!(f, "user"),
+            SettingsScope::Project => write!(f, "project"),
+            SettingsScope::Local => write!(f, "local"),
+            SettingsScope::Managed => write!(f, "managed"),
+        }
+    }
+}
+
+/// Settings from every settings file, in the order the files are loaded
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SettingsInfo {
+    /// Single-valued keys, where a higher scope overrides a lower one
+    #[serde(default)]
+    pub values: Vec<SettingValue>,
+    /// Permission rules, which add up across scopes
+    #[serde(default)]
+    pub permissions: Vec<PermissionRule>,
+}
+
+impl SettingsInfo {
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty() && self.permissions.is_empty()
+    }
+}
+
+/// One settings key and the file that sets it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SettingValue {
+    /// e.g. `model`, `statusLine` or `env.DEBUG`
+    pub key: String,
+    /// The value as text; `None` for `env` keys, whose values may be secrets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    pub scope: SettingsScope,
+    pub path: PathBuf,
+    /// Label of the higher-precedence item that hides this one, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shadowed_by: Option<String>,
+}
+
+/// Which list of `permissions` a rule is in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PermissionKind {
+    Allow,
+    Deny,
+    Ask,
+}
+
+impl std::fmt::Display for PermissionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PermissionKind::Allow => write!(f, "allow"),
+            PermissionKind::Deny => write!(f, "deny"),
+            PermissionKind::Ask => write!(f, "ask"),
+        }
+    }
+}
+
+/// A permission rule such as `Bash(npm run test:*)`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PermissionRule {
+    pub kind: PermissionKind,
+    pub rule: String,
+    pub scope: SettingsScope,
+    pub path: PathBuf,
+}
+
+/// Where a CLAUDE.md memory file sits in the load hierarchy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryScope {
+    /// `CLAUDE.md` in the config directory, loaded in every project
+    User,
+    /// `CLAUDE.md` or `.claude/CLAUDE.md` in the project or a parent directory
+    Project,
+    /// `CLAUDE.local.md` in the project or a parent directory
+    Local,
+    /// Below the project, loaded once Claude reads files in that directory
+    Nested,
+}
+
+impl std::fmt::Display for MemoryScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MemoryScope::User => write!(f, "user"),
+            MemoryScope::Project => write!(f, "project"),
+            MemoryScope::Local => write!(f, "local"),
+            MemoryScope::Nested => write!(f, "nested"),
+        }
+    }
+}
+
+/// A memory file, listed in the order Claude Code loads it. Files pulled in
+/// with `@path` follow the file that imports them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemoryInfo {
+    pub path: PathBuf,
+    /// Scope of the file, or of the file that imports it
+    pub scope: MemoryScope,
+    /// Size in bytes
+    pub size: u64,
+    pub lines: usize,
+    /// The memory file whose `@path` import loads this one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub imported_from: Option<PathBuf>,
+    /// Import hops from a directly loaded file (0 for the file itself)
+    #[serde(default)]
+    pub depth: usize,
+}
+
+/// Kind of component a diagnostic refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComponentKind {
+    Plugin,
+    Skill,
+    Session,
+    Mcp,
+    Hook,
+    Agent,
+    Command,
+    Memory,
+    Settings,
+}
+
+impl std::fmt::Display for ComponentKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComponentKind::Plugin => write!(f, "plugin"),
+            ComponentKind::Skill => write!(f, "skill"),
+            ComponentKind::Session => write!(f, "session"),
+            ComponentKind::Mcp => write!(f, "mcp"),
+            ComponentKind::Hook => write!(f, "hook"),
+            ComponentKind::Agent => write!(f, "agent"),
+            ComponentKind::Command => write!(f, "command"),
+            ComponentKind::Memory => write!(f, "memory"),
+            ComponentKind::Settings => write!(f, "settings"),
+        }
+    }
+}
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A problem found in a component file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub component: ComponentKind,
+    pub path: PathBuf,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn new(
+        severity: Severity,
+        component: ComponentKind,
+        path: &std::path::Path,
+        error: crate::error::Error,
+    ) -> Self {
+        let message = match error {
+            // These carry no path of their own
+            crate::error::Error::MissingField(_) | crate::error::Error::DirectoryReadError(_) => {
+                format!("{} in {}", error, path.display())
+            }
+            _ => error.to_string(),
+        };
+        Diagnostic {
+            severity,
+            component,
+            path: path.to_path_buf(),
+            message,
+        }
+    }
+
+    pub fn error(
+        component: ComponentKind,
+        path: &std::path::Path,
+        error: crate::error::Error,
+    ) -> Self {
+        Self::new(Severity::Error, component, path, error)
+    }
+
+    pub fn warning(
+        component: ComponentKind,
+        path: &std::path::Path,
+        error: crate::error::Error,
+    ) -> Self {
+        Self::new(Severity::Warning, component, path, error)
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_resource_kind_classify() {
+        let kind = |path: &str| ResourceKind::classify(std::path::Path::new(path));
+        assert_eq!(kind("scripts/extract.py"), ResourceKind::Script);
+        assert_eq!(kind("scripts/README.md"), ResourceKind::Script);
+        assert_eq!(kind("references/api.md"), ResourceKind::Reference);
+        assert_eq!(kind("templates/report.html"), ResourceKind::Template);
+        assert_eq!(kind("assets/logo.png"), ResourceKind::Asset);
+        assert_eq!(kind("helper.sh"), ResourceKind::Script);
+        assert_eq!(kind("FORMS.md"), ResourceKind::Reference);
+        assert_eq!(kind("data.csv"), ResourceKind::Other);
+        // A file named like a folder is judged by its extension
+        assert_eq!(kind("assets"), ResourceKind::Other);
+    }
+
+    #[test]
+    fn test_timestamp_parse_and_display() {
+        let iso = Timestamp::parse("2025-12-24T09:13:44.402Z").unwrap();
+        assert_eq!(Timestamp::parse("1766567624402"), Some(iso));
+        assert_eq!(
+            Timestamp::parse("1766567624"),
+            Some(Timestamp(1_766_567_624_000))
+        );
+        assert_eq!(Timestamp::parse("2025-12-24T10:13:44.402+01:00"), Some(iso));
+        assert_eq!(iso.to_string(), "2025-12-24T09:13:44.402Z");
+        assert_eq!(
+            Timestamp::parse("2025-01-29T10:00:00Z")
+                .unwrap()
+                .to_string(),
+            "2025-01-29T10:00:00Z"
+        );
+        assert_eq!(Timestamp::parse("last tuesday"), None);
+    }
+
+    #[test]
+    fn test_timestamp_serde() {
+        let ts = Timestamp(1_766_567_624_402);
+        let json = serde_json::to_string(&ts).unwrap();
+        assert_eq!(json, "\"2025-12-24T09:13:44.402Z\"");
+        assert_eq!(serde_json::from_str::<Timestamp>(&json).unwrap(), ts);
+        assert_eq!(
+            serde_json::from_str::<Timestamp>("1766567624402").unwrap(),
+            ts
+        );
+        assert!(serde_json::from_str::<Timestamp>("\"soon\"").is_err());
+    }
+
+    #[test]
+    fn test_timestamp_relative_to() {
+        let now = Timestamp::parse("2026-01-15T12:00:00Z").unwrap();
+        let ago = |iso: &str| Timestamp::parse(iso).unwrap().relative_to(now);
+        assert_eq!(ago("2026-01-15T11:59:30Z"), "just now");
+        assert_eq!(ago("2026-01-15T11:59:00Z"), "1 minute ago");
+        assert_eq!(ago("2026-01-15T09:00:00Z"), "3 hours ago");
+        assert_eq!(ago("2026-01-13T12:00:00Z"), "2 days ago");
+        assert_eq!(ago("2025-11-15T12:00:00Z"), "2 months ago");
+        assert_eq!(ago("2024-01-15T12:00:00Z"), "2 years ago");
+        assert_eq!(ago("2026-01-15T14:00:00Z"), "in 2 hours");
+    }
+
+    #[test]
+    fn test_timestamp_extremes_do_not_overflow() {
+        assert_eq!(Timestamp::from_epoch(i64::MIN), Timestamp(i64::MIN));
+        assert_eq!(Timestamp::from_epoch(i64::MAX), Timestamp(i64::MAX));
+        assert_eq!(
+            Timestamp::from_json(&serde_json::json!("99999999999999999-01-01T00:00:00Z")),
+            None
+        );
+
+        let now = Timestamp::parse("2026-01-15T12:00:00Z").unwrap();
+        assert!(Timestamp(i64::MIN).relative_to(now).ends_with("years ago"));
+        assert!(Timestamp(i64::MAX)
+            .relative_to(Timestamp(i64::MIN))
+            .starts_with("in "));
+        assert!(Timestamp(i64::MIN)
+            .relative_to(Timestamp(i64::MAX))
+            .ends_with("years ago"));
+    }
+
     #[test]
     fn test_plugin_info_description_provider_with_description() {
         let plugin = PluginInfo {
@@ -182,7 +987,14 @@ mod tests {
             version: Some("1.0.0".to_string()),
             source: Source::Official,
             path: PathBuf::from("/test"),
+            marketplace: None,
+            enabled: true,
             description: Some("A test plugin".to_string()),
+            author: None,
+            homepage: None,
+            repository: None,
+            keywords: vec![],
+            installs: vec![],
         };
 
         assert_eq!(plugin.get_description(), Some("A test plugin".to_string()));
@@ -195,7 +1007,14 @@ mod tests {
             version: Some("1.0.0".to_string()),
             source: Source::Official,
             path: PathBuf::from("/test"),
+            marketplace: None,
+            enabled: true,
             description: None,
+            author: None,
+            homepage: None,
+            repository: None,
+            keywords: vec![],
+            installs: vec![],
         };
 
         assert_eq!(
@@ -211,7 +1030,14 @@ mod tests {
             version: Some("1.0.0".to_string()),
             source: Source::ThirdParty,
             path: PathBuf::from("/test"),
+            marketplace: None,
+            enabled: true,
             description: None,
+            author: None,
+            homepage: None,
+            repository: None,
+            keywords: vec![],
+            installs: vec![],
         };
 
         assert_eq!(
@@ -229,6 +1055,13 @@ mod tests {
             path: PathBuf::from("/test"),
             description: Some("A test skill".to_string()),
             location_type: SkillLocation::Global,
+            shadowed_by: None,
+            uses: 0,
+            resources: vec![],
+            declared_name: None,
+            allowed_tools: vec![],
+            license: None,
+            metadata: BTreeMap::new(),
         };
 
         assert_eq!(skill.get_description(), Some("A test skill".to_string()));
@@ -243,6 +1076,13 @@ mod tests {
             path: PathBuf::from("/test"),
             description: None,
             location_type: SkillLocation::Global,
+            shadowed_by: None,
+            uses: 0,
+            resources: vec![],
+            declared_name: None,
+            allowed_tools: vec![],
+            license: None,
+            metadata: BTreeMap::new(),
         };
 
         assert_eq!(skill.get_description(), None);
@@ -256,6 +1096,15 @@ mod tests {
             command: Some("npx".to_string()),
             path: PathBuf::from("/test"),
             description: Some("A test MCP server".to_string()),
+            scope: McpScope::User,
+            project: None,
+            plugin: None,
+            transport: McpTransport::Stdio,
+            args: vec![],
+            env: vec![],
+            url: None,
+            headers: vec![],
+            shadowed_by: None,
         };
 
         assert_eq!(mcp.get_description(), Some("A test MCP server".to_string()));
@@ -269,6 +1118,15 @@ mod tests {
             command: Some("npx".to_string()),
             path: PathBuf::from("/test"),
             description: None,
+            scope: McpScope::User,
+            project: None,
+            plugin: None,
+            transport: McpTransport::Stdio,
+            args: vec![],
+            env: vec![],
+            url: None,
+            headers: vec![],
+            shadowed_by: None,
         };
 
         assert_eq!(
@@ -277,6 +1135,76 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_mcp_launch_summary_stdio() {
+        let mcp = McpInfo {
+            name: "github".to_string(),
+            status: "configured".to_string(),
+            command: Some("npx".to_string()),
+            path: PathBuf::from("/test"),
+            description: None,
+            scope: McpScope::User,
+            project: None,
+            plugin: None,
+            transport: McpTransport::Stdio,
+            args: vec!["-y".to_string(), "server-github".to_string()],
+            env: vec!["GITHUB_TOKEN".to_string()],
+            url: None,
+            headers: vec![],
+            shadowed_by: None,
+        };
+
+        assert_eq!(
+            mcp.launch_summary(),
+            Some("npx -y server-github".to_string())
+        );
+    }
+
+    #[test]
+    fn test_mcp_launch_summary_http() {
+        let mcp = McpInfo {
+            name: "sentry".to_string(),
+            status: "configured".to_string(),
+            command: None,
+            path: PathBuf::from("/test"),
+            description: None,
+            scope: McpScope::Project,
+            project: None,
+            plugin: None,
+            transport: McpTransport::Http,
+            args: vec![],
+            env: vec![],
+            url: Some("https://mcp.sentry.dev/mcp".to_string()),
+            headers: vec!["Authorization".to_string()],
+            shadowed_by: None,
+        };
+
+        assert_eq!(
+            mcp.launch_summary(),
+            Some("https://mcp.sentry.dev/mcp".to_string())
+        );
+        assert_eq!(
+            serde_json::to_value(&mcp).unwrap()["transport"],
+            serde_json::json!("http")
+        );
+    }
+
+    #[test]
+    fn test_mcp_scope_serialization() {
+        assert_eq!(serde_json::to_string(&McpScope::User).unwrap(), r#""user""#);
+        assert_eq!(
+            serde_json::to_string(&McpScope::Project).unwrap(),
+            r#""project""#
+        );
+        assert_eq!(
+            serde_json::to_string(&McpScope::Local).unwrap(),
+            r#""local""#
+        );
+        assert_eq!(McpScope::default(), McpScope::User);
+        assert_eq!(McpScope::Local.to_string(), "local");
+        assert_eq!(McpScope::Plugin.to_string(), "plugin");
+    }
+
     #[test]
     fn test_hook_info_description_provider_with_description() {
         let hook = HookInfo {
@@ -284,6 +1212,12 @@ mod tests {
             hook_type: "pre-commit".to_string(),
             path: PathBuf::from("/test"),
             description: Some("A pre-commit hook".to_string()),
+            event: None,
+            matcher: None,
+            command: None,
+            timeout: None,
+            settings_file: None,
+            origin: Origin::User,
         };
 
         assert_eq!(
@@ -299,6 +1233,12 @@ mod tests {
             hook_type: "pre-commit".to_string(),
             path: PathBuf::from("/test"),
             description: None,
+            event: None,
+            matcher: None,
+            command: None,
+            timeout: None,
+            settings_file: None,
+            origin: Origin::User,
         };
 
         assert_eq!(hook.get_description(), Some("pre-commit hook".to_string()));
@@ -310,6 +1250,11 @@ mod tests {
             name: "test-agent".to_string(),
             description: Some("A test agent".to_string()),
             path: PathBuf::from("/test"),
+            origin: Origin::User,
+            shadowed_by: None,
+            tools: vec![],
+            model: None,
+            color: None,
         };
 
         assert_eq!(agent.get_description(), Some("A test agent".to_string()));
@@ -321,6 +1266,11 @@ mod tests {
             name: "test-agent".to_string(),
             description: None,
             path: PathBuf::from("/test"),
+            origin: Origin::User,
+            shadowed_by: None,
+            tools: vec![],
+            model: None,
+            color: None,
         };
 
         assert_eq!(agent.get_description(), None);
@@ -334,6 +1284,11 @@ mod tests {
             allowed_tools: None,
             argument_hint: None,
             path: PathBuf::from("/test"),
+            origin: Origin::User,
+            shadowed_by: None,
+            uses: 0,
+            model: None,
+            namespace: None,
         };
 
         assert_eq!(
@@ -350,11 +1305,33 @@ mod tests {
             allowed_tools: None,
             argument_hint: None,
             path: PathBuf::from("/test"),
+            origin: Origin::User,
+            shadowed_by: None,
+            uses: 0,
+            model: None,
+            namespace: None,
         };
 
         assert_eq!(command.get_description(), None);
     }
 
+    #[test]
+    fn test_origin_default_and_display() {
+        assert_eq!(Origin::default(), Origin::User);
+        assert_eq!(Origin::User.to_string(), "user");
+        assert_eq!(Origin::Project.to_string(), "project");
+
+        let json = serde_json::to_string(&Origin::Project).unwrap();
+        assert_eq!(json, r#"{"type":"Project"}"#);
+
+        let plugin = Origin::Plugin {
+            plugin_name: Some("toolkit".to_string()),
+        };
+        assert_eq!(plugin.to_string(), "plugin:toolkit");
+        let json = serde_json::to_string(&plugin).unwrap();
+        assert_eq!(json, r#"{"type":"Plugin","plugin_name":"toolkit"}"#);
+    }
+
     #[test]
     fn test_skill_location_default() {
         let location = SkillLocation::default();
@@ -411,7 +1388,14 @@ mod tests {
             version: Some("1.0.0".to_string()),
             source: Source::Official,
             path: PathBuf::from("/test"),
+            marketplace: None,
+            enabled: true,
             description: Some("A test plugin".to_string()),
+            author: None,
+            homepage: None,
+            repository: None,
+            keywords: vec![],
+            installs: vec![],
         };
 
         let json = serde_json::to_string(&plugin).unwrap();
@@ -432,4 +1416,39 @@ mod tests {
         let json = serde_json::to_string(&plugin).unwrap();
         assert!(json.contains(r#""type":"Plugin""#));
     }
+
+    #[test]
+    fn test_diagnostic_message_includes_path_for_missing_field() {
+        let diagnostic = Diagnostic::warning(
+            ComponentKind::Agent,
+            std::path::Path::new("/test/agents/a.md"),
+            crate::error::Error::MissingField("description".to_string()),
+        );
+
+        assert_eq!(diagnostic.severity, Severity::Warning);
+        assert_eq!(
+            diagnostic.to_string(),
+            "Missing required field: description in /test/agents/a.md"
+        );
+
+        let json = serde_json::to_value(&diagnostic).unwrap();
+        assert_eq!(json["component"], "agent");
+        assert_eq!(json["severity"], "warning");
+    }
+
+    #[test]
+    fn test_diagnostic_message_includes_path_for_directory_read_error() {
+        let diagnostic = Diagnostic::warning(
+            ComponentKind::Command,
+            std::path::Path::new("/test/commands/private"),
+            crate::error::Error::DirectoryReadError(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                "permission denied",
+            )),
+        );
+        assert_eq!(
+            diagnostic.to_string(),
+            "Failed to read directory: permission denied in /test/commands/private"
+        );
+    }
 }
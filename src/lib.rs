@@ -1,8 +1,14 @@
 //! claude-list - Parse and display Claude Code .claude directory information
 
 pub mod cli;
+pub mod diff;
+pub mod doctor;
 pub mod error;
 pub mod formatters;
 pub mod info;
+pub mod outdated;
 pub mod output;
 pub mod parsers;
+pub mod resolve;
+pub mod time;
+pub mod usage;
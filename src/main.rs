@@ -1,14 +1,30 @@
 use anyhow::{Context, Result};
 use clap::Parser;
 use std::env;
-use std::path::PathBuf;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::process;
 
-use claude_list::cli::{Args, OutputMode};
+use claude_list::cli::{Args, Command, OutputMode};
+use claude_list::diff::{diff_inventories, load_inventory, write_diff};
+use claude_list::doctor::{has_errors, run_doctor, write_report};
 use claude_list::formatters::compact::format_compact;
 use claude_list::formatters::detailed::format_detailed;
+use claude_list::formatters::marketplaces::format_marketplaces;
+use claude_list::formatters::sessions::format_sessions;
+use claude_list::formatters::write_warnings;
+use claude_list::outdated::{check_outdated, has_outdated, write_outdated};
 use claude_list::output::{ColorScheme, ColorSettings};
-use claude_list::parsers::{filter_components, parse_all, FilterFlags, SearchFilter};
+use claude_list::parsers::marketplaces::parse_marketplaces_with_warnings;
+use claude_list::parsers::sessions::{parse_project_sessions, parse_usage_records};
+use claude_list::parsers::{
+    filter_components, find_project_root, merge_managed_settings, parse_all_with_project,
+    same_path, FilterFlags, SearchFilter,
+};
+use claude_list::resolve::{effective_only, resolve_effective};
+use claude_list::time::parse_date;
+use claude_list::usage::{build_usage_report, write_usage, UsageWindow};
 
 fn main() {
     if let Err(e) = run() {
@@ -20,7 +36,23 @@ fn main() {
 fn run() -> Result<()> {
     let args = Args::parse();
 
+    // diff works on its own inputs, not on the config directory
+    if let Some(Command::Diff { old, new }) = &args.command {
+        let old_info = load_inventory(old)
+            .with_context(|| format!("Failed to load inventory {}", old.display()))?;
+        let new_info = load_inventory(new)
+            .with_context(|| format!("Failed to load inventory {}", new.display()))?;
+        let diff = diff_inventories(&old_info, &new_info);
+        if args.json {
+            println!("{}", serde_json::to_string_pretty(&diff)?);
+        } else {
+            write_diff(&diff, &mut std::io::stdout())?;
+        }
+        return Ok(());
+    }
+
     // Determine config directory
+    let default_config = args.config.is_none();
     let config_dir = match args.config {
         Some(path) => path,
         None => {
@@ -35,11 +67,102 @@ fn run() -> Result<()> {
         anyhow::bail!("Directory not found: {}", config_dir.display());
     }
 
-    // Parse all components
-    let info = parse_all(config_dir)?;
+    // Resolve project root for project-aware mode
+    let project_dir = match args.project {
+        Some(Some(dir)) => Some(dir),
+        Some(None) => {
+            let cwd = env::current_dir().context("Failed to read current directory")?;
+            Some(find_project_root(&cwd, &config_dir))
+        }
+        None => None,
+    };
+    let project_dir = match project_dir {
+        Some(dir) => {
+            if !dir.exists() {
+                anyhow::bail!("Project directory not found: {}", dir.display());
+            }
+            Some(dir.canonicalize().unwrap_or(dir))
+        }
+        None => None,
+    };
 
-    // Create search filter
-    let search_filter = args.search.as_ref().map(|q| SearchFilter::new(q));
+    if let Some(Command::Doctor) = &args.command {
+        let diagnostics = run_doctor(&config_dir, project_dir.as_deref())?;
+        let mut stdout = std::io::stdout();
+        if args.json {
+            writeln!(stdout, "{}", serde_json::to_string_pretty(&diagnostics)?)?;
+        } else {
+            write_report(&diagnostics, &mut stdout)?;
+        }
+        stdout.flush()?;
+        if has_errors(&diagnostics) {
+            process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Sessions) = &args.command {
+        let mut warnings = Vec::new();
+        let mut projects = parse_project_sessions(&config_dir, &mut warnings);
+        // With --project, only that project's sessions
+        if let Some(ref project_dir) = project_dir {
+            projects.retain(|p| same_path(Path::new(&p.project), project_dir));
+        }
+        if args.json {
+            let json = serde_json::json!({
+                "projects": projects,
+                "warnings": warnings,
+            });
+            println!("{}", serde_json::to_string_pretty(&json)?);
+        } else {
+            format_sessions(&projects, &warnings, &mut std::io::stdout())?;
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Usage { since, until }) = &args.command {
+        let mut warnings = Vec::new();
+        let mut records = parse_usage_records(&config_dir, &mut warnings);
+        if let Some(ref project_dir) = project_dir {
+            records.retain(|r| same_path(Path::new(&r.project), project_dir));
+        }
+        // --until is inclusive, so the window ends when the next day starts
+        let window = UsageWindow {
+            since: since.as_deref().and_then(parse_date),
+            until: until
+                .as_deref()
+                .and_then(parse_date)
+                .map(|day| day + 86_400_000),
+        };
+        let mut report = build_usage_report(&records, window);
+        report.warnings = warnings;
+        if args.json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            let mut stdout = std::io::stdout();
+            write_usage(&report, &mut stdout)?;
+            if !report.warnings.is_empty() {
+                writeln!(stdout)?;
+                write_warnings(&report.warnings, &mut stdout)?;
+            }
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Outdated) = &args.command {
+        let updates = check_outdated(&config_dir)?;
+        let mut stdout = std::io::stdout();
+        if args.json {
+            writeln!(stdout, "{}", serde_json::to_string_pretty(&updates)?)?;
+        } else {
+            write_outdated(&updates, &mut stdout)?;
+        }
+        stdout.flush()?;
+        if has_outdated(&updates) {
+            process::exit(1);
+        }
+        return Ok(());
+    }
 
     // Create color settings (respect --no-color flag)
     let mut color_settings = ColorSettings::from_env();
@@ -51,6 +174,50 @@ fn run() -> Result<()> {
     }
     let color_scheme = ColorScheme::default();
 
+    if args.marketplaces {
+        let mut warnings = Vec::new();
+        let marketplaces = parse_marketplaces_with_warnings(&config_dir, &mut warnings)?;
+        if args.json {
+            let json = serde_json::json!({
+                "marketplaces": marketplaces,
+                "warnings": warnings,
+            });
+            println!("{}", serde_json::to_string_pretty(&json)?);
+        } else {
+            format_marketplaces(
+                &marketplaces,
+                &warnings,
+                &color_scheme,
+                &color_settings,
+                &mut std::io::stdout(),
+            )?;
+        }
+        return Ok(());
+    }
+
+    // Parse all components and apply precedence rules
+    let mut info = parse_all_with_project(config_dir, project_dir)?;
+    if default_config {
+        merge_managed_settings(&mut info);
+    }
+    resolve_effective(&mut info);
+
+    if let Some(Command::Snapshot { output }) = &args.command {
+        let json = serde_json::to_string_pretty(&info)?;
+        match output {
+            Some(path) => fs::write(path, json + "\n")
+                .with_context(|| format!("Failed to write snapshot {}", path.display()))?,
+            None => println!("{}", json),
+        }
+        return Ok(());
+    }
+    if args.effective {
+        info = effective_only(info);
+    }
+
+    // Create search filter
+    let search_filter = args.search.as_ref().map(|q| SearchFilter::new(q));
+
     // Filter based on flags
     let filters = FilterFlags {
         plugins: args.plugins,
@@ -60,10 +227,26 @@ fn run() -> Result<()> {
         hooks: args.hooks,
         agents: args.agents,
         commands: args.commands,
+        memory: args.memory,
+        settings: args.settings,
+        enabled: args.enabled,
+        disabled: args.disabled,
+        unused: args.unused,
         search: search_filter,
     };
     let info = filter_components(info, filters);
 
+    // --strict turns parse warnings into a failure
+    if args.strict && !info.warnings.is_empty() {
+        for warning in &info.warnings {
+            eprintln!("warning: [{}] {}", warning.component, warning);
+        }
+        anyhow::bail!(
+            "{} warning(s) found while parsing (--strict)",
+            info.warnings.len()
+        );
+    }
+
     // Output based on mode
     if args.json {
         let json = serde_json::to_string_pretty(&info)?;
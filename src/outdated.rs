@@ -0,0 +1,361 @@
+//! Offline comparison of installed plugins against the locally cloned
+//! marketplace catalogs, for the `outdated` subcommand
+
+use crate::error::Result;
+use crate::info::MarketplaceInfo;
+use crate::parsers::marketplaces::parse_marketplaces;
+use crate::parsers::plugins::parse_plugins;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::io::Write;
+use std::path::Path;
+
+const NAME_WIDTH: usize = 30;
+const VERSION_WIDTH: usize = 14;
+
+/// How an installed plugin compares to its marketplace catalog
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateStatus {
+    Current,
+    Outdated,
+    /// No catalog entry, or versions that cannot be compared (e.g. git SHAs)
+    Unknown,
+}
+
+impl std::fmt::Display for UpdateStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UpdateStatus::Current => write!(f, "current"),
+            UpdateStatus::Outdated => write!(f, "outdated"),
+            UpdateStatus::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PluginUpdate {
+    pub name: String,
+    pub marketplace: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub installed: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub available: Option<String>,
+    pub status: UpdateStatus,
+}
+
+/// Compare every plugin installed from a marketplace with the version that
+/// marketplace's local catalog offers
+pub fn check_outdated(config_dir: &Path) -> Result<Vec<PluginUpdate>> {
+    let marketplaces = parse_marketplaces(config_dir)?;
+
+    let mut updates: Vec<PluginUpdate> = parse_plugins(config_dir)?
+        .into_iter()
+        .filter_map(|plugin| {
+            let marketplace = plugin.marketplace?;
+            let available = catalog_version(&marketplaces, &marketplace, &plugin.name);
+            let status = match (&plugin.version, &available) {
+                (Some(installed), Some(available)) => compare_versions(installed, available),
+                _ => UpdateStatus::Unknown,
+            };
+
+            Some(PluginUpdate {
+                name: plugin.name,
+                marketplace,
+                installed: plugin.version,
+                available,
+                status,
+            })
+        })
+        .collect();
+
+    updates.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(updates)
+}
+
+fn catalog_version(
+    marketplaces: &[MarketplaceInfo],
+    marketplace: &str,
+    name: &str,
+) -> Option<String> {
+    marketplaces
+        .iter()
+        .find(|m| m.name == marketplace)?
+        .plugins
+        .iter()
+        .find(|p| p.name == name)?
+        .version
+        .clone()
+}
+
+/// Compare dotted numeric versions (`1.2.0`, `v2.0`, `2.0.0-beta.1`) with
+/// semver precedence; anything else is only known to be current when the
+/// strings match
+fn compare_versions(installed: &str, available: &str) -> UpdateStatus {
+    if installed == available {
+        return UpdateStatus::Current;
+    }
+
+    match (Version::parse(installed), Version::parse(available)) {
+        (Some(installed), Some(available)) => {
+            if installed.cmp(&available) == Ordering::Less {
+                UpdateStatus::Outdated
+            } else {
+                UpdateStatus::Current
+            }
+        }
+        _ => UpdateStatus::Unknown,
+    }
+}
+
+/// A `1.2.3`, `v1.2` or `1.2.3-beta.1` version. Build metadata (`+sha`) is
+/// ignored.
+#[derive(Debug)]
+struct Version {
+    release: Vec<u64>,
+    /// Dot-separated pre-release identifiers, empty for a release
+    prerelease: Vec<String>,
+}
+
+impl Version {
+    fn parse(version: &str) -> Option<Self> {
+        let version = version.strip_prefix('v').unwrap_or(version);
+        let version = version.split('+').next()?;
+        let (release, prerelease) = version.split_once('-').unwrap_or((version, ""));
+        Some(Version {
+            release: release
+                .split('.')
+                .map(|part| part.parse().ok())
+                .collect::<Option<_>>()?,
+            prerelease: prerelease
+                .split('.')
+                .filter(|id| !id.is_empty())
+                .map(String::from)
+                .collect(),
+        })
+    }
+}
+
+impl Ord for Version {
+    /// Missing release components count as 0, and a pre-release sorts below
+    /// its release (`2.0.0-beta.1 < 2.0.0`)
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.release.len().max(other.release.len());
+        let component = |v: &[u64], i: usize| v.get(i).copied().unwrap_or(0);
+        let release = (0..len)
+            .map(|i| component(&self.release, i).cmp(&component(&other.release, i)))
+            .find(|o| *o != Ordering::Equal)
+            .unwrap_or(Ordering::Equal);
+
+        release.then_with(
+            || match (self.prerelease.is_empty(), other.prerelease.is_empty()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => compare_prerelease(&self.prerelease, &other.prerelease),
+            },
+        )
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Numeric identifiers compare as numbers and sort below alphanumeric ones;
+/// a shorter list that is a prefix of the other sorts first
+fn compare_prerelease(a: &[String], b: &[String]) -> Ordering {
+    for (a, b) in a.iter().zip(b) {
+        let ordering = match (a.parse::<u64>(), b.parse::<u64>()) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => a.cmp(b),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+/// Print installed and catalog versions per plugin with a summary line
+pub fn write_outdated(updates: &[PluginUpdate], output: &mut dyn Write) -> std::io::Result<()> {
+    if updates.is_empty() {
+        writeln!(output, "No plugins installed from a marketplace")?;
+        return Ok(());
+    }
+
+    writeln!(
+        output,
+        "  {:<name$} {:<version$} {:<version$} STATUS",
+        "NAME",
+        "INSTALLED",
+        "AVAILABLE",
+        name = NAME_WIDTH,
+        version = VERSION_WIDTH
+    )?;
+    writeln!(
+        output,
+        "  {} {} {} {}",
+        "-".repeat(NAME_WIDTH),
+        "-".repeat(VERSION_WIDTH),
+        "-".repeat(VERSION_WIDTH),
+        "-".repeat(8)
+    )?;
+    for update in updates {
+        writeln!(
+            output,
+            "  {:<name$} {:<version$} {:<version$} {}",
+            update.name,
+            update.installed.as_deref().unwrap_or("-"),
+            update.available.as_deref().unwrap_or("-"),
+            update.status,
+            name = NAME_WIDTH,
+            version = VERSION_WIDTH
+        )?;
+    }
+    writeln!(output)?;
+
+    let count = |status| updates.iter().filter(|u| u.status == status).count();
+    writeln!(
+        output,
+        "{} outdated, {} current, {} unknown",
+        count(UpdateStatus::Outdated),
+        count(UpdateStatus::Current),
+        count(UpdateStatus::Unknown)
+    )?;
+
+    Ok(())
+}
+
+/// Whether any plugin lags behind its catalog
+pub fn has_outdated(updates: &[PluginUpdate]) -> bool {
+    updates.iter().any(|u| u.status == UpdateStatus::Outdated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.0.0", "1.0.0"), UpdateStatus::Current);
+        assert_eq!(compare_versions("1.0.0", "1.2.0"), UpdateStatus::Outdated);
+        assert_eq!(compare_versions("1.10.0", "1.9.0"), UpdateStatus::Current);
+        assert_eq!(compare_versions("v1.2", "1.2.1"), UpdateStatus::Outdated);
+        assert_eq!(compare_versions("1.2", "1.2.0"), UpdateStatus::Current);
+        assert_eq!(
+            compare_versions("2.0.0-beta.1", "2.0.0"),
+            UpdateStatus::Outdated
+        );
+        assert_eq!(
+            compare_versions("2.0.0", "2.0.0-beta.1"),
+            UpdateStatus::Current
+        );
+        assert_eq!(
+            compare_versions("2.0.0-beta.2", "2.0.0-beta.11"),
+            UpdateStatus::Outdated
+        );
+        assert_eq!(
+            compare_versions("2.0.0-alpha", "2.0.0-alpha.1"),
+            UpdateStatus::Outdated
+        );
+        assert_eq!(
+            compare_versions("2.0.0-rc.1", "2.0.0-beta"),
+            UpdateStatus::Current
+        );
+        assert_eq!(
+            compare_versions("1.0.0+abc", "1.0.0+def"),
+            UpdateStatus::Current
+        );
+        assert_eq!(
+            compare_versions("d49ad3558669", "1.0.0"),
+            UpdateStatus::Unknown
+        );
+    }
+
+    #[test]
+    fn test_check_outdated() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+        write(
+            &path.join("plugins/known_marketplaces.json"),
+            r#"{"team": {"source": {"source": "github", "repo": "team/plugins"}}}"#,
+        );
+        write(
+            &path.join("plugins/marketplaces/team/.claude-plugin/marketplace.json"),
+            r#"{"plugins": [
+                {"name": "formatter", "version": "1.2.0"},
+                {"name": "linter", "version": "0.3.0"}
+            ]}"#,
+        );
+        write(
+            &path.join("plugins/installed_plugins.json"),
+            r#"{"version": 2, "plugins": {
+                "formatter@team": [{"version": "1.0.0"}],
+                "linter@team": [{"version": "0.3.0"}],
+                "context7@elsewhere": [{"version": "2.0.0"}]
+            }}"#,
+        );
+
+        let updates = check_outdated(path).unwrap();
+        assert_eq!(updates.len(), 3);
+
+        assert_eq!(updates[0].name, "context7");
+        assert_eq!(updates[0].status, UpdateStatus::Unknown);
+        assert_eq!(updates[0].available, None);
+
+        assert_eq!(updates[1].name, "formatter");
+        assert_eq!(updates[1].installed, Some("1.0.0".to_string()));
+        assert_eq!(updates[1].available, Some("1.2.0".to_string()));
+        assert_eq!(updates[1].status, UpdateStatus::Outdated);
+
+        assert_eq!(updates[2].status, UpdateStatus::Current);
+        assert!(has_outdated(&updates));
+    }
+
+    #[test]
+    fn test_write_outdated() {
+        let updates = vec![PluginUpdate {
+            name: "formatter".to_string(),
+            marketplace: "team".to_string(),
+            installed: Some("1.0.0".to_string()),
+            available: Some("1.2.0".to_string()),
+            status: UpdateStatus::Outdated,
+        }];
+
+        let mut buffer = Vec::new();
+        write_outdated(&updates, &mut buffer).unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(output.contains("formatter"));
+        assert!(output.contains("1.0.0"));
+        assert!(output.contains("1.2.0"));
+        assert!(output.contains("outdated"));
+        assert!(output.contains("1 outdated, 0 current, 0 unknown"));
+
+        let mut buffer = Vec::new();
+        write_outdated(&[], &mut buffer).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "No plugins installed from a marketplace\n"
+        );
+    }
+}
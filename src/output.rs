@@ -16,7 +16,11 @@ pub enum ComponentType {
     Hook,
     Agent,
     Command,
+    Memory,
+    Settings,
     Version,
+    /// Disabled components, shown dimmed
+    Disabled,
 }
 
 /// Color scheme mapping component types to ANSI colors.
@@ -28,7 +32,10 @@ pub struct ColorScheme {
     pub hooks: Option<Style>,
     pub agents: Option<Style>,
     pub commands: Option<Style>,
+    pub memory: Option<Style>,
+    pub settings: Option<Style>,
     pub version: Option<Style>,
+    pub disabled: Option<Style>,
 }
 
 impl Default for ColorScheme {
@@ -40,7 +47,10 @@ impl Default for ColorScheme {
             hooks: Some(AnsiColor::Magenta.on_default()),
             agents: Some(AnsiColor::Red.on_default()),
             commands: Some(AnsiColor::BrightYellow.on_default()),
+            memory: Some(AnsiColor::Cyan.on_default()),
+            settings: Some(AnsiColor::BrightBlue.on_default()),
             version: Some(AnsiColor::BrightBlack.on_default()),
+            disabled: Some(Style::new().dimmed()),
         }
     }
 }
@@ -55,7 +65,10 @@ impl ColorScheme {
             ComponentType::Hook => self.hooks,
             ComponentType::Agent => self.agents,
             ComponentType::Command => self.commands,
+            ComponentType::Memory => self.memory,
+            ComponentType::Settings => self.settings,
             ComponentType::Version => self.version,
+            ComponentType::Disabled => self.disabled,
         }
     }
 }
@@ -202,6 +215,8 @@ pub fn parse_component_type(s: &str) -> Option<ComponentType> {
         "hook" | "hooks" => Some(ComponentType::Hook),
         "agent" | "agents" => Some(ComponentType::Agent),
         "command" | "commands" => Some(ComponentType::Command),
+        "memory" => Some(ComponentType::Memory),
+        "setting" | "settings" => Some(ComponentType::Settings),
         _ => None,
     }
 }
@@ -287,6 +302,7 @@ mod tests {
         assert!(scheme.agents.is_some());
         assert!(scheme.commands.is_some());
         assert!(scheme.version.is_some());
+        assert!(scheme.disabled.is_some());
     }
 
     #[test]
@@ -1,11 +1,58 @@
 //! Parse agents from agents/ directory
 
-use crate::error::Result;
-use crate::info::AgentInfo;
+use crate::error::{Error, Result};
+use crate::info::{AgentInfo, ComponentKind, Diagnostic, Origin};
+use crate::parsers::frontmatter::{self, empty_if_null, StringList};
+use crate::parsers::plugins::plugin_roots;
+use serde::Deserialize;
 use std::fs;
 use std::path::Path;
 
+/// Fields of an agent's YAML frontmatter
+#[derive(Debug, Default, Deserialize)]
+struct AgentFrontmatter {
+    name: Option<String>,
+    #[serde(default, deserialize_with = "empty_if_null")]
+    description: Option<String>,
+    tools: Option<StringList>,
+    model: Option<String>,
+    color: Option<String>,
+}
+
 pub fn parse_agents(base_path: &Path) -> Result<Vec<AgentInfo>> {
+    let mut warnings = Vec::new();
+    let mut agents = parse_agents_with_origin(base_path, Origin::User, &mut warnings)?;
+    agents.extend(parse_plugin_agents(base_path, &mut warnings)?);
+    Ok(agents)
+}
+
+/// Parse the agents/ directory shipped by every installed plugin, tagging
+/// each entry with its plugin
+pub fn parse_plugin_agents(
+    base_path: &Path,
+    warnings: &mut Vec<Diagnostic>,
+) -> Result<Vec<AgentInfo>> {
+    let mut agents = Vec::new();
+    for plugin in plugin_roots(base_path) {
+        agents.extend(parse_agents_with_origin(
+            &plugin.install_path,
+            Origin::Plugin {
+                plugin_name: Some(plugin.plugin_name),
+            },
+            warnings,
+        )?);
+    }
+    Ok(agents)
+}
+
+/// Parse agents/ inside a user or project `.claude` directory, tagging each
+/// entry with the given origin. Unreadable files are skipped; they and
+/// unterminated frontmatter are recorded in `warnings`
+pub fn parse_agents_with_origin(
+    base_path: &Path,
+    origin: Origin,
+    warnings: &mut Vec<Diagnostic>,
+) -> Result<Vec<AgentInfo>> {
     let agents_dir = base_path.join("agents");
 
     if !agents_dir.exists() || !agents_dir.is_dir() {
@@ -14,45 +61,63 @@ pub fn parse_agents(base_path: &Path) -> Result<Vec<AgentInfo>> {
 
     let mut agents = Vec::new();
 
-    for entry in fs::read_dir(&agents_dir)? {
-        let entry = entry?;
+    let entries = match fs::read_dir(&agents_dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            warnings.push(Diagnostic::warning(
+                ComponentKind::Agent,
+                &agents_dir,
+                Error::DirectoryReadError(e),
+            ));
+            return Ok(agents);
+        }
+    };
+
+    for entry in entries.filter_map(|e| e.ok()) {
         let agent_path = entry.path();
 
         if !agent_path.is_file() || agent_path.extension().map(|e| e.to_str()) != Some(Some("md")) {
             continue;
         }
 
-        let content = fs::read_to_string(&agent_path)?;
+        let content = match fs::read_to_string(&agent_path) {
+            Ok(c) => c,
+            Err(e) => {
+                warnings.push(Diagnostic::warning(
+                    ComponentKind::Agent,
+                    &agent_path,
+                    Error::FileReadError {
+                        path: agent_path.clone(),
+                        source: e,
+                    },
+                ));
+                continue;
+            }
+        };
 
-        // Parse frontmatter for name and description
-        let mut name = agent_path
-            .file_stem()
-            .and_then(|n| n.to_str())
-            .map(String::from)
+        let fields: AgentFrontmatter =
+            frontmatter::read(&agent_path, &content, ComponentKind::Agent, warnings);
+
+        let name = fields
+            .name
+            .filter(|n| !n.is_empty())
+            .or_else(|| {
+                agent_path
+                    .file_stem()
+                    .and_then(|n| n.to_str())
+                    .map(String::from)
+            })
             .unwrap_or_default();
 
-        let mut description: Option<String> = None;
-
-        if content.starts_with("---") {
-            let frontmatter = content
-                .trim_start_matches("---")
-                .split("---")
-                .next()
-                .unwrap_or("");
-
-            for line in frontmatter.lines() {
-                if line.trim_start().starts_with("name:") {
-                    name = line.split(":").nth(1).unwrap_or("").trim().to_string();
-                } else if line.trim_start().starts_with("description:") {
-                    description = Some(line.split(":").nth(1).unwrap_or("").trim().to_string());
-                }
-            }
-        }
-
         agents.push(AgentInfo {
             name,
-            description,
+            description: fields.description,
+            tools: fields.tools.map(StringList::items).unwrap_or_default(),
+            model: fields.model,
+            color: fields.color,
             path: agent_path,
+            origin: origin.clone(),
+            shadowed_by: None,
         });
     }
 
@@ -124,6 +189,80 @@ mod tests {
         assert_eq!(agents[0].name, "test");
     }
 
+    #[test]
+    fn test_unreadable_agent_skipped_with_warning() {
+        let dir = TempDir::new().unwrap();
+        let agents_dir = dir.path().join("agents");
+        std::fs::create_dir_all(&agents_dir).unwrap();
+        std::fs::write(agents_dir.join("reviewer.md"), "# Reviewer\n").unwrap();
+        std::fs::write(agents_dir.join("binary.md"), [0xff, 0xfe, 0x00]).unwrap();
+
+        let mut warnings = Vec::new();
+        let agents = parse_agents_with_origin(dir.path(), Origin::User, &mut warnings).unwrap();
+        assert_eq!(agents.len(), 1);
+        assert_eq!(agents[0].name, "reviewer");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].component, ComponentKind::Agent);
+        assert_eq!(warnings[0].path, agents_dir.join("binary.md"));
+    }
+
+    #[test]
+    fn test_agents_full_frontmatter() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+
+        let agents_dir = path.join("agents");
+        std::fs::create_dir_all(&agents_dir).unwrap();
+
+        let content = r##"---
+name: reviewer
+description: >
+  Reviews diffs. Use when: a PR is ready,
+  or before merging
+tools: Read, Grep, Bash(git diff:*, git log:*)
+model: sonnet
+color: "#ff8800"
+---
+"##;
+        File::create(agents_dir.join("reviewer.md"))
+            .unwrap()
+            .write_all(content.as_bytes())
+            .unwrap();
+
+        let agents = parse_agents(path).unwrap();
+        let agent = &agents[0];
+        assert_eq!(
+            agent.description,
+            Some("Reviews diffs. Use when: a PR is ready, or before merging".to_string())
+        );
+        assert_eq!(agent.tools, ["Read", "Grep", "Bash(git diff:*, git log:*)"]);
+        assert_eq!(agent.model, Some("sonnet".to_string()));
+        assert_eq!(agent.color, Some("#ff8800".to_string()));
+    }
+
+    #[test]
+    fn test_agents_unquoted_colon_in_description() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+
+        let agents_dir = path.join("agents");
+        std::fs::create_dir_all(&agents_dir).unwrap();
+
+        // Not valid YAML, but common in hand-written agents
+        let content = "---\nname: helper\ndescription: Use this agent when: tests fail\ntools:\n  - Read\n  - Edit\n---\n";
+        File::create(agents_dir.join("helper.md"))
+            .unwrap()
+            .write_all(content.as_bytes())
+            .unwrap();
+
+        let agents = parse_agents(path).unwrap();
+        assert_eq!(agents[0].name, "helper");
+        assert_eq!(
+            agents[0].description,
+            Some("Use this agent when: tests fail".to_string())
+        );
+    }
+
     #[test]
     fn test_agents_description_extraction() {
         let dir = TempDir::new().unwrap();
@@ -1,68 +1,162 @@
 //! Parse commands from commands/ directory
 
-use crate::error::Result;
-use crate::info::CommandInfo;
+use crate::error::{Error, Result};
+use crate::info::{CommandInfo, ComponentKind, Diagnostic, Origin};
+use crate::parsers::frontmatter::{self, empty_if_null, StringList};
+use crate::parsers::plugins::plugin_roots;
+use serde::Deserialize;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// Fields of a command's YAML frontmatter
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct CommandFrontmatter {
+    #[serde(default, deserialize_with = "empty_if_null")]
+    description: Option<String>,
+    allowed_tools: Option<StringList>,
+    argument_hint: Option<String>,
+    model: Option<String>,
+}
 
 pub fn parse_commands(base_path: &Path) -> Result<Vec<CommandInfo>> {
+    let mut warnings = Vec::new();
+    let mut commands = parse_commands_with_origin(base_path, Origin::User, &mut warnings)?;
+    commands.extend(parse_plugin_commands(base_path, &mut warnings)?);
+    Ok(commands)
+}
+
+/// Parse the commands/ directory shipped by every installed plugin, tagging
+/// each entry with its plugin
+pub fn parse_plugin_commands(
+    base_path: &Path,
+    warnings: &mut Vec<Diagnostic>,
+) -> Result<Vec<CommandInfo>> {
+    let mut commands = Vec::new();
+    for plugin in plugin_roots(base_path) {
+        commands.extend(parse_commands_with_origin(
+            &plugin.install_path,
+            Origin::Plugin {
+                plugin_name: Some(plugin.plugin_name),
+            },
+            warnings,
+        )?);
+    }
+    Ok(commands)
+}
+
+/// Parse commands/ inside a user or project `.claude` directory, tagging each
+/// entry with the given origin and recording unterminated frontmatter in
+/// `warnings`
+pub fn parse_commands_with_origin(
+    base_path: &Path,
+    origin: Origin,
+    warnings: &mut Vec<Diagnostic>,
+) -> Result<Vec<CommandInfo>> {
     let commands_dir = base_path.join("commands");
 
     if !commands_dir.exists() || !commands_dir.is_dir() {
         return Ok(vec![]);
     }
 
-    let mut commands = Vec::new();
-
-    for entry in fs::read_dir(&commands_dir)? {
-        let entry = entry?;
-        let command_path = entry.path();
+    let mut files = Vec::new();
+    command_files(&commands_dir, &mut files, warnings);
 
-        if !command_path.is_file()
-            || command_path.extension().map(|e| e.to_str()) != Some(Some("md"))
-        {
-            continue;
-        }
+    let mut commands = Vec::new();
 
-        let name = command_path
-            .file_stem()
-            .and_then(|n| n.to_str())
-            .map(String::from)
-            .unwrap_or_default();
-
-        let content = fs::read_to_string(&command_path)?;
-
-        let mut description = None;
-        let mut allowed_tools = None;
-        let mut argument_hint = None;
-
-        if content.starts_with("---") {
-            if let Some(frontmatter) = content.trim_start_matches("---").split("---").next() {
-                for line in frontmatter.lines() {
-                    let trimmed = line.trim();
-                    if trimmed.starts_with("description:") {
-                        description = trimmed.split(":").nth(1).map(|s| s.trim().to_string());
-                    } else if trimmed.starts_with("allowed-tools:") {
-                        allowed_tools = trimmed.split(":").nth(1).map(|s| s.trim().to_string());
-                    } else if trimmed.starts_with("argument-hint:") {
-                        argument_hint = trimmed.split(":").nth(1).map(|s| s.trim().to_string());
-                    }
-                }
+    for command_path in files {
+        // commands/frontend/component.md is run as /frontend:component
+        let relative = command_path
+            .strip_prefix(&commands_dir)
+            .unwrap_or(&command_path);
+        let mut segments: Vec<String> = relative
+            .with_extension("")
+            .iter()
+            .map(|s| s.to_string_lossy().into_owned())
+            .collect();
+        let stem = segments.pop().unwrap_or_default();
+        let namespace = (!segments.is_empty()).then(|| segments.join(":"));
+        let name = match &namespace {
+            Some(namespace) => format!("{}:{}", namespace, stem),
+            None => stem,
+        };
+
+        let content = match fs::read_to_string(&command_path) {
+            Ok(c) => c,
+            Err(e) => {
+                warnings.push(Diagnostic::warning(
+                    ComponentKind::Command,
+                    &command_path,
+                    Error::FileReadError {
+                        path: command_path.clone(),
+                        source: e,
+                    },
+                ));
+                continue;
             }
-        }
+        };
+
+        let fields: CommandFrontmatter =
+            frontmatter::read(&command_path, &content, ComponentKind::Command, warnings);
 
         commands.push(CommandInfo {
             name,
-            description,
-            allowed_tools,
-            argument_hint,
+            namespace,
+            description: fields.description,
+            allowed_tools: fields.allowed_tools.map(StringList::joined),
+            argument_hint: fields.argument_hint,
+            model: fields.model,
             path: command_path,
+            origin: origin.clone(),
+            shadowed_by: None,
+            uses: 0,
         });
     }
 
     Ok(commands)
 }
 
+/// Markdown files under `dir` and its subdirectories, sorted by path.
+/// Symlinked directories are not followed. Directories and entries that
+/// cannot be read are skipped and recorded in `warnings`.
+pub fn command_files(dir: &Path, files: &mut Vec<PathBuf>, warnings: &mut Vec<Diagnostic>) {
+    let unreadable = |path: &Path, e| Diagnostic::warning(ComponentKind::Command, path, e);
+
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            warnings.push(unreadable(dir, Error::DirectoryReadError(e)));
+            return;
+        }
+    };
+    let mut entries: Vec<fs::DirEntry> = entries
+        .filter_map(|entry| match entry {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                warnings.push(unreadable(dir, Error::DirectoryReadError(e)));
+                None
+            }
+        })
+        .collect();
+    entries.sort_by_key(|e| e.path());
+
+    for entry in entries {
+        let path = entry.path();
+        let file_type = match entry.file_type() {
+            Ok(file_type) => file_type,
+            Err(e) => {
+                warnings.push(unreadable(&path, Error::DirectoryReadError(e)));
+                continue;
+            }
+        };
+        if file_type.is_dir() {
+            command_files(&path, files, warnings);
+        } else if path.is_file() && path.extension().map(|e| e.to_str()) == Some(Some("md")) {
+            files.push(path);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -236,6 +330,115 @@ description: Test command
         assert_eq!(commands[0].name, "valid-command");
     }
 
+    #[test]
+    fn test_commands_yaml_frontmatter() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+
+        let commands_dir = path.join("commands");
+        std::fs::create_dir_all(&commands_dir).unwrap();
+
+        let command_md = r#"---
+description: "Deploy: build, tag and push"
+allowed-tools:
+  - Bash(git tag:*)
+  - Bash(make:*)
+argument-hint: "[env]"
+model: claude-haiku-4-5
+---
+# Deploy
+"#;
+        File::create(commands_dir.join("deploy.md"))
+            .unwrap()
+            .write_all(command_md.as_bytes())
+            .unwrap();
+
+        let commands = parse_commands(path).unwrap();
+        let cmd = &commands[0];
+        assert_eq!(
+            cmd.description,
+            Some("Deploy: build, tag and push".to_string())
+        );
+        assert_eq!(
+            cmd.allowed_tools,
+            Some("Bash(git tag:*), Bash(make:*)".to_string())
+        );
+        assert_eq!(cmd.argument_hint, Some("[env]".to_string()));
+        assert_eq!(cmd.model, Some("claude-haiku-4-5".to_string()));
+    }
+
+    #[test]
+    fn test_commands_unquoted_colon_in_description() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+
+        let commands_dir = path.join("commands");
+        std::fs::create_dir_all(&commands_dir).unwrap();
+
+        let command_md = "---\ndescription: Note: runs the full suite\n---\n";
+        File::create(commands_dir.join("test-all.md"))
+            .unwrap()
+            .write_all(command_md.as_bytes())
+            .unwrap();
+
+        let commands = parse_commands(path).unwrap();
+        assert_eq!(
+            commands[0].description,
+            Some("Note: runs the full suite".to_string())
+        );
+    }
+
+    #[test]
+    fn test_nested_commands_are_namespaced() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+
+        let commands_dir = path.join("commands");
+        std::fs::create_dir_all(commands_dir.join("frontend/forms")).unwrap();
+        std::fs::write(commands_dir.join("review.md"), "# Review\n").unwrap();
+        std::fs::write(
+            commands_dir.join("frontend/component.md"),
+            "---\ndescription: New component\n---\n",
+        )
+        .unwrap();
+        std::fs::write(commands_dir.join("frontend/forms/field.md"), "# Field\n").unwrap();
+        std::fs::write(commands_dir.join("frontend/notes.txt"), "ignored").unwrap();
+
+        let commands = parse_commands(path).unwrap();
+        let names: Vec<(&str, Option<&str>)> = commands
+            .iter()
+            .map(|c| (c.name.as_str(), c.namespace.as_deref()))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("frontend:component", Some("frontend")),
+                ("frontend:forms:field", Some("frontend:forms")),
+                ("review", None),
+            ]
+        );
+        assert_eq!(commands[0].description, Some("New component".to_string()));
+    }
+
+    #[test]
+    fn test_unreadable_command_skipped_with_warning() {
+        let dir = TempDir::new().unwrap();
+        let commands_dir = dir.path().join("commands");
+        std::fs::create_dir_all(commands_dir.join("tools")).unwrap();
+        std::fs::write(commands_dir.join("review.md"), "# Review\n").unwrap();
+        std::fs::write(commands_dir.join("tools/binary.md"), [0xff, 0xfe, 0x00]).unwrap();
+
+        let mut warnings = Vec::new();
+        let commands = parse_commands_with_origin(dir.path(), Origin::User, &mut warnings).unwrap();
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].name, "review");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].path, commands_dir.join("tools/binary.md"));
+        assert!(warnings[0]
+            .message
+            .starts_with(&format!("Failed to read {}:", warnings[0].path.display())));
+    }
+
     #[test]
     fn test_commands_empty_description() {
         let dir = TempDir::new().unwrap();
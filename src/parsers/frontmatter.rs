@@ -0,0 +1,336 @@
+//! YAML frontmatter of markdown components (skills, agents, commands and
+//! hooks)
+//!
+//! A frontmatter block opens with a `---` line at the very top of the file
+//! and ends at the next line that is exactly `---`. Anything after that is
+//! the markdown body, `---` rules included.
+
+use crate::error::Error;
+use crate::info::{ComponentKind, Diagnostic};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_yaml::Value as YamlValue;
+use std::path::Path;
+
+/// Parsed frontmatter of a markdown file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frontmatter {
+    /// The YAML between the fences (`Null` when the block is empty)
+    pub yaml: YamlValue,
+    /// Byte offset of the markdown body, just past the closing fence
+    pub body_offset: usize,
+}
+
+/// The raw text between the fences
+struct Block<'a> {
+    text: &'a str,
+    body_offset: usize,
+}
+
+/// Locate the frontmatter block. Returns `None` when the file does not start
+/// with a `---` line and an error when the closing fence is missing.
+fn block<'a>(path: &Path, content: &'a str) -> Option<Result<Block<'a>, Error>> {
+    let mut lines = content.split_inclusive('\n');
+    let opening = lines.next()?;
+    if opening.trim_end() != "---" {
+        return None;
+    }
+
+    let start = opening.len();
+    let mut offset = start;
+    for line in lines {
+        if line.trim_end() == "---" {
+            return Some(Ok(Block {
+                text: &content[start..offset],
+                body_offset: offset + line.len(),
+            }));
+        }
+        offset += line.len();
+    }
+
+    Some(Err(malformed(path, 1, 1, "no closing `---`")))
+}
+
+fn malformed(path: &Path, line: usize, column: usize, reason: &str) -> Error {
+    Error::MalformedFrontmatter {
+        path: path.to_path_buf(),
+        line,
+        column,
+        reason: reason.to_string(),
+    }
+}
+
+/// Strictly parse the frontmatter of a markdown file. Returns `None` when
+/// there is none and an error, pointing at the offending line and column,
+/// when it is unterminated or not valid YAML.
+pub fn parse(path: &Path, content: &str) -> Option<Result<Frontmatter, Error>> {
+    block(path, content).map(|block| {
+        let block = block?;
+        match serde_yaml::from_str::<YamlValue>(block.text) {
+            Ok(yaml) => Ok(Frontmatter {
+                yaml,
+                body_offset: block.body_offset,
+            }),
+            Err(e) => Err(yaml_error(path, e)),
+        }
+    })
+}
+
+/// A YAML error in a frontmatter block, located in the markdown file
+fn yaml_error(path: &Path, e: serde_yaml::Error) -> Error {
+    // The block starts on the line after the opening fence
+    let (line, column) = e
+        .location()
+        .map_or((2, 1), |loc| (loc.line() + 1, loc.column()));
+    let message = e.to_string();
+    let reason = message
+        .split(" at line ")
+        .next()
+        .unwrap_or(&message)
+        .to_string();
+    malformed(path, line, column, &reason)
+}
+
+/// Deserialize the frontmatter of a markdown file into `T`, or
+/// `T::default()` when there is none. An unterminated block is recorded in
+/// `warnings` and read to the end of the file. Frontmatter that is not valid
+/// YAML, such as an unquoted description containing `: `, is recorded too and
+/// read line by line instead, taking each top-level `key: value` as a string
+/// and the `- item` lines under an empty key as a list.
+pub fn read<T: DeserializeOwned + Default>(
+    path: &Path,
+    content: &str,
+    component: ComponentKind,
+    warnings: &mut Vec<Diagnostic>,
+) -> T {
+    let text = match block(path, content) {
+        None => return T::default(),
+        Some(Ok(block)) => block.text,
+        Some(Err(e)) => {
+            warnings.push(Diagnostic::warning(component, path, e));
+            content.split_once('\n').map_or("", |(_, rest)| rest)
+        }
+    };
+
+    if let Ok(value) = serde_yaml::from_str::<Option<T>>(text) {
+        return value.unwrap_or_default();
+    }
+    if let Err(e) = serde_yaml::from_str::<YamlValue>(text) {
+        warnings.push(Diagnostic::warning(component, path, yaml_error(path, e)));
+    }
+    serde_yaml::from_value(lines(text)).unwrap_or_default()
+}
+
+/// Top-level `key: value` lines of a frontmatter block, split on the first
+/// colon, as a YAML mapping of strings. `- item` lines following a key with
+/// no value make that key a list.
+fn lines(text: &str) -> YamlValue {
+    let mut mapping = serde_yaml::Mapping::new();
+    let mut last_key: Option<String> = None;
+    for line in text.lines() {
+        if let Some(item) = line.trim_start().strip_prefix('-') {
+            let value = last_key.as_deref().and_then(|key| mapping.get_mut(key));
+            if let Some(value) = value {
+                if value.as_str() == Some("") {
+                    *value = YamlValue::Sequence(Vec::new());
+                }
+                if let YamlValue::Sequence(items) = value {
+                    items.push(item.trim().into());
+                }
+            }
+            continue;
+        }
+        if line.starts_with([' ', '\t', '#']) {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            mapping.insert(key.trim().into(), value.trim().into());
+            last_key = Some(key.trim().to_string());
+        }
+    }
+    YamlValue::Mapping(mapping)
+}
+
+/// Read a frontmatter field that is present but empty (`description:`) as
+/// an empty string rather than as missing. Surrounding whitespace, such as
+/// the trailing newline of a folded `>` block, is trimmed.
+pub fn empty_if_null<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<String>::deserialize(deserializer)?.unwrap_or_default();
+    Ok(Some(value.trim().to_string()))
+}
+
+/// A frontmatter field written either as a YAML list or as a comma-separated
+/// string, e.g. `tools: [Read, Grep]` or `tools: Read, Grep`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum StringList {
+    List(Vec<String>),
+    String(String),
+}
+
+impl StringList {
+    /// Each entry, splitting strings on commas outside parentheses so that
+    /// `Bash(git add:*, git commit:*)` stays one entry
+    pub fn items(self) -> Vec<String> {
+        let value = match self {
+            StringList::List(items) => return items,
+            StringList::String(value) => value,
+        };
+
+        let mut items = Vec::new();
+        let mut current = String::new();
+        let mut depth = 0usize;
+        for c in value.chars() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => {
+                    items.push(std::mem::take(&mut current));
+                    continue;
+                }
+                _ => {}
+            }
+            current.push(c);
+        }
+        items.push(current);
+
+        items
+            .into_iter()
+            .map(|item| item.trim().to_string())
+            .filter(|item| !item.is_empty())
+            .collect()
+    }
+
+    /// The entries as written in a single comma-separated string
+    pub fn joined(self) -> String {
+        match self {
+            StringList::List(items) => items.join(", "),
+            StringList::String(value) => value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(error: Error) -> (usize, usize) {
+        match error {
+            Error::MalformedFrontmatter { line, column, .. } => (line, column),
+            other => panic!("unexpected error: {}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_frontmatter_and_body_offset() {
+        let content = "---\nname: a\ndescription: x --- y\n---\n# Body\n\n---\nmore\n";
+        let frontmatter = parse(Path::new("a.md"), content).unwrap().unwrap();
+        assert_eq!(frontmatter.yaml["description"].as_str(), Some("x --- y"));
+        assert_eq!(&content[frontmatter.body_offset..], "# Body\n\n---\nmore\n");
+    }
+
+    #[test]
+    fn test_parse_without_frontmatter() {
+        assert!(parse(Path::new("a.md"), "# Title\n---\n").is_none());
+        assert!(parse(Path::new("a.md"), "----\nname: a\n----\n").is_none());
+        assert!(parse(Path::new("a.md"), "").is_none());
+    }
+
+    #[test]
+    fn test_parse_crlf_and_empty_block() {
+        let frontmatter = parse(Path::new("a.md"), "---\r\nname: a\r\n---\r\nbody")
+            .unwrap()
+            .unwrap();
+        assert_eq!(frontmatter.yaml["name"].as_str(), Some("a"));
+
+        let frontmatter = parse(Path::new("a.md"), "---\n---\nbody").unwrap().unwrap();
+        assert!(frontmatter.yaml.is_null());
+        assert_eq!(frontmatter.body_offset, 8);
+    }
+
+    #[test]
+    fn test_parse_unterminated() {
+        let error = parse(Path::new("a.md"), "---\nname: a\n")
+            .unwrap()
+            .unwrap_err();
+        assert!(error.to_string().contains("no closing `---`"));
+        assert_eq!(location(error), (1, 1));
+    }
+
+    #[test]
+    fn test_parse_invalid_yaml_location() {
+        let content = "---\nname: a\ndescription: Use when: tests fail\n---\n";
+        let error = parse(Path::new("a.md"), content).unwrap().unwrap_err();
+        let message = error.to_string();
+        assert!(message.starts_with("Malformed frontmatter in a.md:3:"));
+        assert!(!message.contains(" at line "));
+        assert_eq!(location(error).0, 3);
+    }
+
+    #[test]
+    fn test_read_unterminated_is_a_warning() {
+        #[derive(Debug, Default, Deserialize)]
+        struct Fields {
+            description: Option<String>,
+        }
+
+        let path = Path::new("agents/broken.md");
+        let mut warnings = Vec::new();
+        let fields: Fields = read(
+            path,
+            "---\ndescription: Still read\n",
+            ComponentKind::Agent,
+            &mut warnings,
+        );
+        assert_eq!(fields.description, Some("Still read".to_string()));
+        assert_eq!(warnings.len(), 1);
+
+        let fields: Fields = read(
+            path,
+            "# No frontmatter",
+            ComponentKind::Agent,
+            &mut warnings,
+        );
+        assert!(fields.description.is_none());
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_read_invalid_yaml_warns_and_keeps_lists() {
+        #[derive(Debug, Default, Deserialize)]
+        struct Fields {
+            description: Option<String>,
+            tools: Option<StringList>,
+        }
+
+        let mut warnings = Vec::new();
+        let fields: Fields = read(
+            Path::new("agents/loose.md"),
+            "---\ndescription: Use when: tests fail\ntools:\n  - Read\n  - Grep\n---\n",
+            ComponentKind::Agent,
+            &mut warnings,
+        );
+        assert_eq!(fields.description, Some("Use when: tests fail".to_string()));
+        assert_eq!(
+            fields.tools.map(StringList::items).unwrap(),
+            ["Read", "Grep"]
+        );
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].component, ComponentKind::Agent);
+        assert!(warnings[0].message.contains("agents/loose.md:2:"));
+    }
+
+    #[test]
+    fn test_string_list_items() {
+        let list = StringList::String("Read, Bash(git add:*, git commit:*),, Edit".to_string());
+        assert_eq!(
+            list.items(),
+            ["Read", "Bash(git add:*, git commit:*)", "Edit"]
+        );
+        let list = StringList::List(vec!["Read".to_string(), "Edit".to_string()]);
+        assert_eq!(list.joined(), "Read, Edit");
+    }
+}
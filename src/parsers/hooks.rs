@@ -1,21 +1,188 @@
-//! Parse hooks from hooks/ directory
-
-use crate::error::Result;
-use crate::info::HookInfo;
+//! Parse hooks from settings.json and the legacy hooks/ directory
+
+use crate::error::{Error, Result};
+use crate::info::{ComponentKind, Diagnostic, HookInfo, Origin};
+use crate::parsers::plugins::plugin_roots;
+use crate::parsers::{frontmatter, invalid_json};
+use serde::Deserialize;
+use serde_json::Value;
 use std::fs;
 use std::path::Path;
 
+/// Settings files that may declare hooks, in the order Claude Code loads them
+const SETTINGS_FILES: [&str; 2] = ["settings.json", "settings.local.json"];
+
 pub fn parse_hooks(base_path: &Path) -> Result<Vec<HookInfo>> {
-    let hooks_dir = base_path.join("hooks");
+    let mut warnings = Vec::new();
+    let mut hooks = parse_hooks_with_origin(base_path, Origin::User, &mut warnings)?;
+    hooks.extend(parse_plugin_hooks(base_path, &mut warnings));
+    Ok(hooks)
+}
 
-    if !hooks_dir.exists() || !hooks_dir.is_dir() {
-        return Ok(vec![]);
+/// Parse `hooks/hooks.json` shipped by every installed plugin, tagging each
+/// entry with its plugin
+pub fn parse_plugin_hooks(base_path: &Path, warnings: &mut Vec<Diagnostic>) -> Vec<HookInfo> {
+    let mut hooks = Vec::new();
+    for plugin in plugin_roots(base_path) {
+        parse_settings_hooks(
+            &plugin.install_path.join("hooks").join("hooks.json"),
+            Origin::Plugin {
+                plugin_name: Some(plugin.plugin_name),
+            },
+            &mut hooks,
+            warnings,
+        );
     }
+    hooks
+}
 
+/// Parse hooks of a user or project `.claude` directory, tagging each entry
+/// with the given origin and recording unreadable files in `warnings`
+pub fn parse_hooks_with_origin(
+    base_path: &Path,
+    origin: Origin,
+    warnings: &mut Vec<Diagnostic>,
+) -> Result<Vec<HookInfo>> {
     let mut hooks = Vec::new();
 
-    for entry in fs::read_dir(&hooks_dir)? {
-        let entry = entry?;
+    // Hooks configured in settings.json (current format). The settings
+    // parser reports malformed files, except the user's settings.local.json,
+    // which it does not read
+    for file in SETTINGS_FILES {
+        let mut reported_elsewhere = Vec::new();
+        let file_warnings = if origin == Origin::User && file == "settings.local.json" {
+            &mut *warnings
+        } else {
+            &mut reported_elsewhere
+        };
+        parse_settings_hooks(
+            &base_path.join(file),
+            origin.clone(),
+            &mut hooks,
+            file_warnings,
+        );
+    }
+
+    // Fallback: markdown hooks in hooks/ directory (legacy format)
+    parse_hooks_dir(&base_path.join("hooks"), origin, &mut hooks, warnings)?;
+
+    Ok(hooks)
+}
+
+/// Parse the `"hooks"` object of a settings file (or a plugin's hooks.json).
+///
+/// The object is keyed by event name, each holding a list of matcher groups:
+/// `{"PreToolUse": [{"matcher": "Bash", "hooks": [{"type": "command", "command": "..."}]}]}`
+pub fn parse_settings_hooks(
+    settings_path: &Path,
+    origin: Origin,
+    hooks: &mut Vec<HookInfo>,
+    warnings: &mut Vec<Diagnostic>,
+) {
+    let content = match fs::read_to_string(settings_path) {
+        Ok(c) => c,
+        Err(_) => return,
+    };
+
+    // Gracefully handle malformed JSON
+    let json: Value = match serde_json::from_str(&content) {
+        Ok(j) => j,
+        Err(e) => {
+            warnings.push(invalid_json(ComponentKind::Hook, settings_path, e));
+            return;
+        }
+    };
+
+    let events = match json.get("hooks").and_then(|v| v.as_object()) {
+        Some(events) => events,
+        None => return,
+    };
+
+    for (event, groups) in events {
+        let groups = match groups.as_array() {
+            Some(groups) => groups,
+            None => continue,
+        };
+
+        for group in groups {
+            let matcher = group
+                .get("matcher")
+                .and_then(|v| v.as_str())
+                .filter(|m| !m.is_empty())
+                .map(String::from);
+
+            let name = match matcher {
+                Some(ref m) => format!("{}:{}", event, m),
+                None => event.clone(),
+            };
+
+            let handlers = match group.get("hooks").and_then(|v| v.as_array()) {
+                Some(handlers) => handlers,
+                None => continue,
+            };
+
+            for handler in handlers {
+                let hook_type = handler
+                    .get("type")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("command")
+                    .to_string();
+                let command = handler
+                    .get("command")
+                    .or_else(|| handler.get("prompt"))
+                    .and_then(|v| v.as_str())
+                    .map(String::from);
+                let timeout = handler.get("timeout").and_then(|v| v.as_u64());
+
+                hooks.push(HookInfo {
+                    name: name.clone(),
+                    hook_type,
+                    path: settings_path.to_path_buf(),
+                    description: None,
+                    event: Some(event.clone()),
+                    matcher: matcher.clone(),
+                    command,
+                    timeout,
+                    settings_file: Some(settings_path.to_path_buf()),
+                    origin: origin.clone(),
+                });
+            }
+        }
+    }
+}
+
+/// Fields of a markdown hook's YAML frontmatter
+#[derive(Debug, Default, Deserialize)]
+struct HookFrontmatter {
+    #[serde(default, deserialize_with = "frontmatter::empty_if_null")]
+    hook: Option<String>,
+}
+
+/// Parse markdown hooks with a `hook:` frontmatter line from hooks/ directory.
+/// Unreadable files are skipped and recorded in `warnings`.
+fn parse_hooks_dir(
+    hooks_dir: &Path,
+    origin: Origin,
+    hooks: &mut Vec<HookInfo>,
+    warnings: &mut Vec<Diagnostic>,
+) -> Result<()> {
+    if !hooks_dir.exists() || !hooks_dir.is_dir() {
+        return Ok(());
+    }
+
+    let entries = match fs::read_dir(hooks_dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            warnings.push(Diagnostic::warning(
+                ComponentKind::Hook,
+                hooks_dir,
+                Error::DirectoryReadError(e),
+            ));
+            return Ok(());
+        }
+    };
+
+    for entry in entries.filter_map(|e| e.ok()) {
         let hook_path = entry.path();
 
         if !hook_path.is_file() || hook_path.extension().map(|e| e.to_str()) != Some(Some("md")) {
@@ -28,33 +195,40 @@ pub fn parse_hooks(base_path: &Path) -> Result<Vec<HookInfo>> {
             .map(String::from)
             .unwrap_or_default();
 
-        let content = fs::read_to_string(&hook_path)?;
-
-        // Parse frontmatter
-        let hook_type = if content.starts_with("---") {
-            content
-                .trim_start_matches("---")
-                .split("---")
-                .next()
-                .and_then(|s| {
-                    s.lines()
-                        .find(|l| l.trim_start().starts_with("hook:"))
-                        .map(|l| l.split(":").nth(1).unwrap_or("").trim().to_string())
-                })
-                .unwrap_or_else(|| "unknown".to_string())
-        } else {
-            "unknown".to_string()
+        let content = match fs::read_to_string(&hook_path) {
+            Ok(c) => c,
+            Err(e) => {
+                warnings.push(Diagnostic::warning(
+                    ComponentKind::Hook,
+                    &hook_path,
+                    Error::FileReadError {
+                        path: hook_path.clone(),
+                        source: e,
+                    },
+                ));
+                continue;
+            }
         };
 
+        let fields: HookFrontmatter =
+            frontmatter::read(&hook_path, &content, ComponentKind::Hook, warnings);
+        let hook_type = fields.hook.unwrap_or_else(|| "unknown".to_string());
+
         hooks.push(HookInfo {
             name,
             hook_type,
             path: hook_path,
             description: None,
+            event: None,
+            matcher: None,
+            command: None,
+            timeout: None,
+            settings_file: None,
+            origin: origin.clone(),
         });
     }
 
-    Ok(hooks)
+    Ok(())
 }
 
 #[cfg(test)]
@@ -124,6 +298,23 @@ mod tests {
         assert_eq!(hooks[0].hook_type, "pre-commit");
     }
 
+    #[test]
+    fn test_unreadable_hook_skipped_with_warning() {
+        let dir = TempDir::new().unwrap();
+        let hooks_dir = dir.path().join("hooks");
+        std::fs::create_dir_all(&hooks_dir).unwrap();
+        std::fs::write(hooks_dir.join("lint.md"), "---\nhook: pre-commit\n---\n").unwrap();
+        std::fs::write(hooks_dir.join("binary.md"), [0xff, 0xfe, 0x00]).unwrap();
+
+        let mut warnings = Vec::new();
+        let hooks = parse_hooks_with_origin(dir.path(), Origin::User, &mut warnings).unwrap();
+        assert_eq!(hooks.len(), 1);
+        assert_eq!(hooks[0].name, "lint");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].component, ComponentKind::Hook);
+        assert_eq!(warnings[0].path, hooks_dir.join("binary.md"));
+    }
+
     #[test]
     fn test_hooks_empty_hook_type() {
         let dir = TempDir::new().unwrap();
@@ -166,6 +357,134 @@ hook:
         assert_eq!(hooks[0].name, "readme");
     }
 
+    #[test]
+    fn test_parse_hooks_from_settings() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+
+        let settings = r#"{
+            "hooks": {
+                "PreToolUse": [
+                    {
+                        "matcher": "Bash",
+                        "hooks": [
+                            {"type": "command", "command": "~/.claude/check-bash.sh", "timeout": 30}
+                        ]
+                    }
+                ],
+                "Stop": [
+                    {
+                        "hooks": [
+                            {"type": "command", "command": "notify-send done"},
+                            {"type": "prompt", "prompt": "Did you run the tests?"}
+                        ]
+                    }
+                ]
+            }
+        }"#;
+
+        File::create(path.join("settings.json"))
+            .unwrap()
+            .write_all(settings.as_bytes())
+            .unwrap();
+
+        let hooks = parse_hooks(path).unwrap();
+        assert_eq!(hooks.len(), 3);
+
+        let bash = hooks.iter().find(|h| h.name == "PreToolUse:Bash").unwrap();
+        assert_eq!(bash.event, Some("PreToolUse".to_string()));
+        assert_eq!(bash.matcher, Some("Bash".to_string()));
+        assert_eq!(bash.hook_type, "command");
+        assert_eq!(bash.command, Some("~/.claude/check-bash.sh".to_string()));
+        assert_eq!(bash.timeout, Some(30));
+        assert_eq!(bash.settings_file, Some(path.join("settings.json")));
+
+        let stop: Vec<_> = hooks.iter().filter(|h| h.name == "Stop").collect();
+        assert_eq!(stop.len(), 2);
+        assert!(stop.iter().all(|h| h.matcher.is_none()));
+        assert!(stop
+            .iter()
+            .any(|h| h.hook_type == "prompt"
+                && h.command == Some("Did you run the tests?".to_string())));
+    }
+
+    #[test]
+    fn test_parse_hooks_from_local_settings() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+
+        File::create(path.join("settings.json"))
+            .unwrap()
+            .write_all(br#"{"hooks": {"SessionStart": [{"hooks": [{"type": "command", "command": "a"}]}]}}"#)
+            .unwrap();
+        File::create(path.join("settings.local.json"))
+            .unwrap()
+            .write_all(br#"{"hooks": {"UserPromptSubmit": [{"matcher": "", "hooks": [{"type": "command", "command": "b"}]}]}}"#)
+            .unwrap();
+
+        let hooks = parse_hooks(path).unwrap();
+        assert_eq!(hooks.len(), 2);
+        assert_eq!(hooks[0].name, "SessionStart");
+        // Empty matcher is treated as no matcher
+        assert_eq!(hooks[1].name, "UserPromptSubmit");
+        assert!(hooks[1].matcher.is_none());
+        assert_eq!(
+            hooks[1].settings_file,
+            Some(path.join("settings.local.json"))
+        );
+    }
+
+    #[test]
+    fn test_parse_hooks_settings_without_hooks() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+
+        File::create(path.join("settings.json"))
+            .unwrap()
+            .write_all(br#"{"model": "opus"}"#)
+            .unwrap();
+
+        let hooks = parse_hooks(path).unwrap();
+        assert!(hooks.is_empty());
+    }
+
+    #[test]
+    fn test_parse_hooks_malformed_settings() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+
+        File::create(path.join("settings.json"))
+            .unwrap()
+            .write_all(b"{ invalid json }")
+            .unwrap();
+        create_hooks_dir(path, "legacy-hook", "pre-commit");
+
+        // Malformed settings are skipped, legacy hooks still parsed
+        let hooks = parse_hooks(path).unwrap();
+        assert_eq!(hooks.len(), 1);
+        assert_eq!(hooks[0].name, "legacy-hook");
+    }
+
+    #[test]
+    fn test_malformed_user_local_settings_reported() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+        std::fs::write(path.join("settings.json"), "{ invalid json }").unwrap();
+        std::fs::write(path.join("settings.local.json"), "{ invalid json }").unwrap();
+
+        // settings.json is left to the settings parser
+        let mut warnings = Vec::new();
+        parse_hooks_with_origin(path, Origin::User, &mut warnings).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].component, ComponentKind::Hook);
+        assert_eq!(warnings[0].path, path.join("settings.local.json"));
+
+        // A project's settings.local.json is read by the settings parser
+        let mut warnings = Vec::new();
+        parse_hooks_with_origin(path, Origin::Project, &mut warnings).unwrap();
+        assert!(warnings.is_empty());
+    }
+
     fn create_hooks_dir(base: &Path, name: &str, hook_type: &str) {
         let hook_dir = base.join("hooks");
         std::fs::create_dir_all(&hook_dir).unwrap();
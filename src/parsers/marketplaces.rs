@@ -0,0 +1,330 @@
+//! Parse registered plugin marketplaces from plugins/known_marketplaces.json
+
+use crate::error::Result;
+use crate::info::{ComponentKind, Diagnostic, MarketplaceInfo, MarketplacePlugin, PluginInfo};
+use crate::parsers::invalid_json;
+use crate::parsers::plugins::parse_plugins;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub fn parse_marketplaces(base_path: &Path) -> Result<Vec<MarketplaceInfo>> {
+    parse_marketplaces_with_warnings(base_path, &mut Vec::new())
+}
+
+/// Parse marketplaces and their plugin catalogs, recording files that had to
+/// be skipped in `warnings`
+pub fn parse_marketplaces_with_warnings(
+    base_path: &Path,
+    warnings: &mut Vec<Diagnostic>,
+) -> Result<Vec<MarketplaceInfo>> {
+    let known_path = base_path.join("plugins").join("known_marketplaces.json");
+
+    let content = match fs::read_to_string(&known_path) {
+        Ok(c) => c,
+        Err(_) => return Ok(vec![]),
+    };
+
+    // Gracefully handle malformed JSON
+    let json: Value = match serde_json::from_str(&content) {
+        Ok(j) => j,
+        Err(e) => {
+            warnings.push(invalid_json(ComponentKind::Plugin, &known_path, e));
+            return Ok(vec![]);
+        }
+    };
+
+    let installed = parse_plugins(base_path)?;
+    let mut marketplaces = Vec::new();
+
+    if let Some(known) = json.as_object() {
+        for (name, entry) in known {
+            // Marketplaces are cloned to plugins/marketplaces/<name> unless
+            // the entry says otherwise
+            let path = entry
+                .get("installLocation")
+                .and_then(|v| v.as_str())
+                .map(PathBuf::from)
+                .unwrap_or_else(|| base_path.join("plugins").join("marketplaces").join(name));
+
+            let plugins = parse_catalog(&path, name, &installed, warnings);
+
+            marketplaces.push(MarketplaceInfo {
+                name: name.clone(),
+                source: source_label(entry.get("source")),
+                path,
+                last_updated: entry
+                    .get("lastUpdated")
+                    .and_then(|v| v.as_str())
+                    .map(String::from),
+                plugins,
+            });
+        }
+    }
+
+    marketplaces.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(marketplaces)
+}
+
+/// Describe a marketplace source: `{"source": "github", "repo": "owner/repo"}`
+/// becomes `github:owner/repo`, git and URL sources show their URL and local
+/// directories their path
+fn source_label(source: Option<&Value>) -> String {
+    let source = match source {
+        Some(Value::String(s)) => return s.clone(),
+        Some(source) => source,
+        None => return "unknown".to_string(),
+    };
+
+    let field = |key: &str| source.get(key).and_then(|v| v.as_str());
+    match field("source") {
+        Some("github") => match field("repo") {
+            Some(repo) => format!("github:{}", repo),
+            None => "github".to_string(),
+        },
+        kind => field("url")
+            .or_else(|| field("path"))
+            .or(kind)
+            .unwrap_or("unknown")
+            .to_string(),
+    }
+}
+
+/// Read the plugins a marketplace offers from its
+/// `.claude-plugin/marketplace.json`, flagging the ones installed from it
+fn parse_catalog(
+    path: &Path,
+    marketplace: &str,
+    installed: &[PluginInfo],
+    warnings: &mut Vec<Diagnostic>,
+) -> Vec<MarketplacePlugin> {
+    let catalog_path = path.join(".claude-plugin").join("marketplace.json");
+
+    // The marketplace may not have been cloned yet
+    let content = match fs::read_to_string(&catalog_path) {
+        Ok(c) => c,
+        Err(_) => return vec![],
+    };
+
+    let json: Value = match serde_json::from_str(&content) {
+        Ok(j) => j,
+        Err(e) => {
+            warnings.push(invalid_json(ComponentKind::Plugin, &catalog_path, e));
+            return vec![];
+        }
+    };
+
+    json.get("plugins")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|p| {
+                    let name = p.get("name")?.as_str()?.to_string();
+                    let is_installed = installed
+                        .iter()
+                        .any(|i| i.name == name && i.marketplace.as_deref() == Some(marketplace));
+
+                    Some(MarketplacePlugin {
+                        version: p
+                            .get("version")
+                            .and_then(|v| v.as_str())
+                            .map(String::from)
+                            .or_else(|| local_source_version(path, p.get("source"))),
+                        description: p
+                            .get("description")
+                            .and_then(|v| v.as_str())
+                            .map(String::from),
+                        installed: is_installed,
+                        name,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Version from the manifest of a plugin kept inside the marketplace clone
+/// (`"source": "./plugins/name"`), for catalogs that do not list versions
+fn local_source_version(path: &Path, source: Option<&Value>) -> Option<String> {
+    let manifest_path = path
+        .join(source?.as_str()?)
+        .join(".claude-plugin")
+        .join("plugin.json");
+    let content = fs::read_to_string(manifest_path).ok()?;
+    let manifest: Value = serde_json::from_str(&content).ok()?;
+    manifest.get("version")?.as_str().map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        File::create(path)
+            .unwrap()
+            .write_all(content.as_bytes())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_parse_marketplaces_with_catalog() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+
+        write(
+            &path.join("plugins/known_marketplaces.json"),
+            r#"{
+                "claude-plugins-official": {
+                    "source": {"source": "github", "repo": "anthropics/claude-plugins-official"},
+                    "lastUpdated": "2026-01-14T04:28:57.441Z"
+                }
+            }"#,
+        );
+        write(
+            &path.join(
+                "plugins/marketplaces/claude-plugins-official/.claude-plugin/marketplace.json",
+            ),
+            r#"{
+                "name": "claude-plugins-official",
+                "plugins": [
+                    {"name": "context7", "description": "Up-to-date docs", "version": "1.0.0"},
+                    {"name": "github", "description": "GitHub integration"}
+                ]
+            }"#,
+        );
+        write(
+            &path.join("plugins/installed_plugins.json"),
+            r#"{"version": 2, "plugins": {"context7@claude-plugins-official": [{"version": "1.0.0"}]}}"#,
+        );
+
+        let marketplaces = parse_marketplaces(path).unwrap();
+        assert_eq!(marketplaces.len(), 1);
+
+        let marketplace = &marketplaces[0];
+        assert_eq!(marketplace.name, "claude-plugins-official");
+        assert_eq!(
+            marketplace.source,
+            "github:anthropics/claude-plugins-official"
+        );
+        assert_eq!(
+            marketplace.last_updated,
+            Some("2026-01-14T04:28:57.441Z".to_string())
+        );
+        assert!(marketplace
+            .path
+            .ends_with("plugins/marketplaces/claude-plugins-official"));
+
+        assert_eq!(marketplace.plugins.len(), 2);
+        assert_eq!(marketplace.plugins[0].name, "context7");
+        assert_eq!(marketplace.plugins[0].version, Some("1.0.0".to_string()));
+        assert!(marketplace.plugins[0].installed);
+        assert_eq!(marketplace.plugins[1].name, "github");
+        assert!(!marketplace.plugins[1].installed);
+    }
+
+    #[test]
+    fn test_installed_flag_requires_same_marketplace() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+        let clone = path.join("clones/team");
+
+        write(
+            &path.join("plugins/known_marketplaces.json"),
+            &format!(
+                r#"{{"team": {{"source": {{"source": "git", "url": "https://example.com/team.git"}}, "installLocation": "{}"}}}}"#,
+                clone.to_string_lossy().replace('\\', "/")
+            ),
+        );
+        write(
+            &clone.join(".claude-plugin/marketplace.json"),
+            r#"{"plugins": [{"name": "context7"}]}"#,
+        );
+        write(
+            &path.join("plugins/installed_plugins.json"),
+            r#"{"version": 2, "plugins": {"context7@other": [{"version": "1.0.0"}]}}"#,
+        );
+
+        let marketplaces = parse_marketplaces(path).unwrap();
+        assert_eq!(marketplaces[0].source, "https://example.com/team.git");
+        assert_eq!(marketplaces[0].path, clone);
+        assert!(!marketplaces[0].plugins[0].installed);
+    }
+
+    #[test]
+    fn test_catalog_version_from_local_plugin_manifest() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+        let clone = path.join("plugins/marketplaces/team");
+
+        write(
+            &path.join("plugins/known_marketplaces.json"),
+            r#"{"team": {"source": {"source": "github", "repo": "team/plugins"}}}"#,
+        );
+        write(
+            &clone.join(".claude-plugin/marketplace.json"),
+            r#"{"plugins": [{"name": "formatter", "source": "./plugins/formatter"}]}"#,
+        );
+        write(
+            &clone.join("plugins/formatter/.claude-plugin/plugin.json"),
+            r#"{"name": "formatter", "version": "2.3.0"}"#,
+        );
+
+        let marketplaces = parse_marketplaces(path).unwrap();
+        assert_eq!(
+            marketplaces[0].plugins[0].version,
+            Some("2.3.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_missing_known_marketplaces() {
+        let dir = TempDir::new().unwrap();
+        assert!(parse_marketplaces(dir.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_uncloned_marketplace_has_empty_catalog() {
+        let dir = TempDir::new().unwrap();
+        write(
+            &dir.path().join("plugins/known_marketplaces.json"),
+            r#"{"local": {"source": {"source": "directory", "path": "/srv/plugins"}}}"#,
+        );
+
+        let marketplaces = parse_marketplaces(dir.path()).unwrap();
+        assert_eq!(marketplaces.len(), 1);
+        assert_eq!(marketplaces[0].source, "/srv/plugins");
+        assert!(marketplaces[0].plugins.is_empty());
+    }
+
+    #[test]
+    fn test_malformed_files_record_warnings() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+        write(
+            &path.join("plugins/known_marketplaces.json"),
+            r#"{"broken": {"source": "github"}}"#,
+        );
+        write(
+            &path.join("plugins/marketplaces/broken/.claude-plugin/marketplace.json"),
+            "{ invalid }",
+        );
+
+        let mut warnings = Vec::new();
+        let marketplaces = parse_marketplaces_with_warnings(path, &mut warnings).unwrap();
+        assert_eq!(marketplaces.len(), 1);
+        assert!(marketplaces[0].plugins.is_empty());
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("invalid JSON"));
+
+        write(&path.join("plugins/known_marketplaces.json"), "{ invalid }");
+        let mut warnings = Vec::new();
+        assert!(parse_marketplaces_with_warnings(path, &mut warnings)
+            .unwrap()
+            .is_empty());
+        assert_eq!(warnings.len(), 1);
+    }
+}
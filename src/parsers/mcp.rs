@@ -1,63 +1,253 @@
-//! Parse MCP servers from mcp-servers/ directory
+//! Parse MCP servers from ~/.claude.json, project .mcp.json files and the
+//! legacy mcp-servers/ directory or mcp.json
 
 use crate::error::Result;
-use crate::info::McpInfo;
+use crate::info::{ComponentKind, Diagnostic, McpInfo, McpScope, McpTransport};
+use crate::parsers::invalid_json;
+use crate::parsers::plugins::plugin_roots;
 use serde_json::Value;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 pub fn parse_mcp(base_path: &Path) -> Result<Vec<McpInfo>> {
-    // Try new format: mcp-servers/ directory (takes priority)
-    let mcp_servers_dir = base_path.join("mcp-servers");
+    parse_mcp_with_warnings(base_path, &mut Vec::new())
+}
 
-    if mcp_servers_dir.exists() && mcp_servers_dir.is_dir() {
-        return parse_mcp_from_directory(&mcp_servers_dir);
+/// Parse MCP servers, recording config files that could not be read in
+/// `warnings`
+pub fn parse_mcp_with_warnings(
+    base_path: &Path,
+    warnings: &mut Vec<Diagnostic>,
+) -> Result<Vec<McpInfo>> {
+    let mut servers = parse_legacy_mcp(base_path, warnings)?;
+
+    if let Some(claude_json) = claude_json_path(base_path) {
+        parse_claude_json(&claude_json, &mut servers, warnings);
     }
 
-    // Fallback to old format: mcp.json
-    let mcp_path = base_path.join("mcp.json");
+    parse_plugin_mcp(base_path, &mut servers, warnings);
+
+    Ok(servers)
+}
 
-    if !mcp_path.exists() {
-        return Ok(vec![]);
+/// Locate `.claude.json` for a config directory. A custom config directory
+/// (`CLAUDE_CONFIG_DIR`) keeps it inside; a `.claude` directory has it
+/// alongside, as `~/.claude.json` sits next to `~/.claude`. Any other
+/// directory's parent is not searched, so `--config DIR` never picks up an
+/// unrelated `DIR/../.claude.json`.
+pub fn claude_json_path(base_path: &Path) -> Option<PathBuf> {
+    let inside = base_path.join(".claude.json");
+    if inside.is_file() {
+        return Some(inside);
     }
+    if base_path.file_name()? != ".claude" {
+        return None;
+    }
+    base_path.parent().map(|home| home.join(".claude.json"))
+}
 
-    let content = match fs::read_to_string(&mcp_path) {
-        Ok(c) => c,
-        Err(_) => return Ok(vec![]),
-    };
-    let json: Value = match serde_json::from_str(&content) {
-        Ok(j) => j,
-        Err(_) => return Ok(vec![]),
+/// Parse user and local scoped servers from `~/.claude.json`, plus the
+/// project scoped `.mcp.json` of every project it knows about.
+fn parse_claude_json(
+    claude_json: &Path,
+    servers: &mut Vec<McpInfo>,
+    warnings: &mut Vec<Diagnostic>,
+) {
+    let json = match read_json(claude_json, warnings) {
+        Some(j) => j,
+        None => return,
     };
 
+    // User scope: top-level mcpServers
+    servers.extend(servers_from_config(
+        &json,
+        claude_json,
+        McpScope::User,
+        None,
+        "configured",
+    ));
+
+    // Local scope: projects.<path>.mcpServers
+    if let Some(projects) = json.get("projects").and_then(|v| v.as_object()) {
+        for (project_path, project) in projects {
+            let project_path = PathBuf::from(project_path);
+            servers.extend(servers_from_config(
+                project,
+                claude_json,
+                McpScope::Local,
+                Some(&project_path),
+                "configured",
+            ));
+        }
+
+        // Project scope: .mcp.json checked into each known project
+        for project_path in projects.keys() {
+            parse_project_mcp(Path::new(project_path), servers, warnings);
+        }
+    }
+}
+
+/// Parse project scoped servers from `<project>/.mcp.json`.
+pub fn parse_project_mcp(
+    project_path: &Path,
+    servers: &mut Vec<McpInfo>,
+    warnings: &mut Vec<Diagnostic>,
+) {
+    let mcp_json = project_path.join(".mcp.json");
+    if let Some(json) = read_json(&mcp_json, warnings) {
+        servers.extend(servers_from_config(
+            &json,
+            &mcp_json,
+            McpScope::Project,
+            Some(project_path),
+            "configured",
+        ));
+    }
+}
+
+/// Parse plugin scoped servers from `.mcp.json` at each installed plugin's
+/// root, or the `mcpServers` object of its `.claude-plugin/plugin.json`
+fn parse_plugin_mcp(base_path: &Path, servers: &mut Vec<McpInfo>, warnings: &mut Vec<Diagnostic>) {
+    for plugin in plugin_roots(base_path) {
+        let mcp_json = plugin.install_path.join(".mcp.json");
+        let manifest = plugin
+            .install_path
+            .join(".claude-plugin")
+            .join("plugin.json");
+        let config_path = if mcp_json.exists() {
+            mcp_json
+        } else {
+            manifest
+        };
+
+        if let Some(json) = read_json(&config_path, warnings) {
+            let mut plugin_servers =
+                servers_from_config(&json, &config_path, McpScope::Plugin, None, "configured");
+            for server in &mut plugin_servers {
+                server.plugin = Some(plugin.plugin_name.clone());
+            }
+            servers.extend(plugin_servers);
+        }
+    }
+}
+
+/// Read a JSON file, returning None if missing or malformed. Malformed
+/// files are recorded in `warnings`.
+fn read_json(path: &Path, warnings: &mut Vec<Diagnostic>) -> Option<Value> {
+    let content = fs::read_to_string(path).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(json) => Some(json),
+        Err(e) => {
+            warnings.push(invalid_json(ComponentKind::Mcp, path, e));
+            None
+        }
+    }
+}
+
+/// Build server entries from the `mcpServers` object of a config value
+fn servers_from_config(
+    config: &Value,
+    path: &Path,
+    scope: McpScope,
+    project: Option<&Path>,
+    status: &str,
+) -> Vec<McpInfo> {
     let mut servers = Vec::new();
 
-    if let Some(mcp_servers) = json.get("mcpServers").and_then(|v| v.as_object()) {
+    if let Some(mcp_servers) = config.get("mcpServers").and_then(|v| v.as_object()) {
         for (name, server) in mcp_servers {
             let command = server
                 .get("command")
                 .and_then(|v| v.as_str())
                 .map(String::from);
-            let args = server
-                .get("args")
-                .and_then(|v| v.as_str())
-                .map(String::from);
+            let url = server.get("url").and_then(|v| v.as_str()).map(String::from);
 
             servers.push(McpInfo {
                 name: name.clone(),
-                status: "connected".to_string(),
-                command: command.or(args),
-                path: mcp_path.clone(),
+                status: status.to_string(),
+                command,
+                path: path.to_path_buf(),
                 description: None,
+                scope,
+                project: project.map(Path::to_path_buf),
+                plugin: None,
+                transport: parse_transport(server, url.is_some()),
+                args: string_array(server.get("args")),
+                env: object_keys(server.get("env")),
+                url,
+                headers: object_keys(server.get("headers")),
+                shadowed_by: None,
             });
         }
     }
 
-    Ok(servers)
+    servers
+}
+
+/// Determine the transport from the `type` field, defaulting to http for
+/// entries that only declare a `url` and stdio otherwise
+fn parse_transport(server: &Value, has_url: bool) -> McpTransport {
+    match server.get("type").and_then(|v| v.as_str()) {
+        Some("sse") => McpTransport::Sse,
+        Some("http") | Some("streamable-http") => McpTransport::Http,
+        Some(_) => McpTransport::Stdio,
+        None if has_url => McpTransport::Http,
+        None => McpTransport::Stdio,
+    }
+}
+
+/// Collect a JSON array of strings (e.g. `args`)
+fn string_array(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|a| a.as_str())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Collect the keys of a JSON object (e.g. `env`, `headers`), leaving out
+/// their values since they frequently hold secrets
+fn object_keys(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_object())
+        .map(|obj| obj.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Parse legacy locations inside the config directory: mcp-servers/
+/// (takes priority) or mcp.json
+fn parse_legacy_mcp(base_path: &Path, warnings: &mut Vec<Diagnostic>) -> Result<Vec<McpInfo>> {
+    // Try new format: mcp-servers/ directory (takes priority)
+    let mcp_servers_dir = base_path.join("mcp-servers");
+
+    if mcp_servers_dir.exists() && mcp_servers_dir.is_dir() {
+        return parse_mcp_from_directory(&mcp_servers_dir, warnings);
+    }
+
+    // Fallback to old format: mcp.json
+    let mcp_path = base_path.join("mcp.json");
+
+    match read_json(&mcp_path, warnings) {
+        Some(json) => Ok(servers_from_config(
+            &json,
+            &mcp_path,
+            McpScope::User,
+            None,
+            "connected",
+        )),
+        None => Ok(vec![]),
+    }
 }
 
 /// Parse MCP servers from mcp-servers/ directory
-fn parse_mcp_from_directory(mcp_servers_dir: &Path) -> Result<Vec<McpInfo>> {
+fn parse_mcp_from_directory(
+    mcp_servers_dir: &Path,
+    warnings: &mut Vec<Diagnostic>,
+) -> Result<Vec<McpInfo>> {
     let mut servers = Vec::new();
 
     for entry in fs::read_dir(mcp_servers_dir)? {
@@ -81,15 +271,12 @@ fn parse_mcp_from_directory(mcp_servers_dir: &Path) -> Result<Vec<McpInfo>> {
         let command = if smithery_path.exists() {
             Some("smithery.yaml".to_string())
         } else if package_path.exists() {
-            if let Ok(content) = fs::read_to_string(&package_path) {
-                if let Ok(json) = serde_json::from_str::<Value>(&content) {
-                    json.get("name").and_then(|v| v.as_str()).map(String::from)
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
+            read_json(&package_path, warnings).map(|json| {
+                json.get("name")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("package.json")
+                    .to_string()
+            })
         } else {
             None
         };
@@ -100,6 +287,15 @@ fn parse_mcp_from_directory(mcp_servers_dir: &Path) -> Result<Vec<McpInfo>> {
             command,
             path: server_path,
             description: None,
+            scope: McpScope::User,
+            project: None,
+            plugin: None,
+            transport: McpTransport::Stdio,
+            args: vec![],
+            env: vec![],
+            url: None,
+            headers: vec![],
+            shadowed_by: None,
         });
     }
 
@@ -135,7 +331,65 @@ mod tests {
         let servers = parse_mcp(path).unwrap();
         assert_eq!(servers.len(), 1);
         assert_eq!(servers[0].name, "MiniMax");
-        assert!(servers[0].command.is_some());
+        assert_eq!(servers[0].command, Some("npx".to_string()));
+        // args is an array and must be kept in full
+        assert_eq!(servers[0].args, vec!["-y", "@anthropic-ai/minimax-mcp"]);
+        assert_eq!(servers[0].transport, McpTransport::Stdio);
+    }
+
+    #[test]
+    fn test_parse_mcp_transport_details() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+
+        let mcp_config = r#"{
+            "mcpServers": {
+                "local": {
+                    "type": "stdio",
+                    "command": "python",
+                    "args": ["-m", "server", "--port", "0"],
+                    "env": {"API_KEY": "secret", "LOG_LEVEL": "debug"}
+                },
+                "events": {
+                    "type": "sse",
+                    "url": "https://example.com/sse",
+                    "headers": {"Authorization": "Bearer secret"}
+                },
+                "remote": {
+                    "type": "http",
+                    "url": "https://example.com/mcp"
+                },
+                "implicit-http": {
+                    "url": "https://example.com/other"
+                }
+            }
+        }"#;
+
+        File::create(path.join("mcp.json"))
+            .unwrap()
+            .write_all(mcp_config.as_bytes())
+            .unwrap();
+
+        let servers = parse_mcp(path).unwrap();
+        assert_eq!(servers.len(), 4);
+
+        let local = servers.iter().find(|s| s.name == "local").unwrap();
+        assert_eq!(local.transport, McpTransport::Stdio);
+        assert_eq!(local.args, vec!["-m", "server", "--port", "0"]);
+        assert_eq!(local.env, vec!["API_KEY", "LOG_LEVEL"]);
+        assert!(local.url.is_none());
+
+        let events = servers.iter().find(|s| s.name == "events").unwrap();
+        assert_eq!(events.transport, McpTransport::Sse);
+        assert_eq!(events.url, Some("https://example.com/sse".to_string()));
+        assert_eq!(events.headers, vec!["Authorization"]);
+        assert!(events.command.is_none());
+
+        let remote = servers.iter().find(|s| s.name == "remote").unwrap();
+        assert_eq!(remote.transport, McpTransport::Http);
+
+        let implicit = servers.iter().find(|s| s.name == "implicit-http").unwrap();
+        assert_eq!(implicit.transport, McpTransport::Http);
     }
 
     #[test]
@@ -254,6 +508,121 @@ startCommand:
         assert!(servers.is_empty());
     }
 
+    #[test]
+    fn test_parse_mcp_from_claude_json() {
+        let dir = TempDir::new().unwrap();
+        let base = dir.path().join(".claude");
+        std::fs::create_dir_all(&base).unwrap();
+
+        let project = dir.path().join("my-repo");
+        std::fs::create_dir_all(&project).unwrap();
+
+        let claude_json = format!(
+            r#"{{
+            "mcpServers": {{
+                "github": {{"command": "npx", "args": ["-y", "@modelcontextprotocol/server-github"]}}
+            }},
+            "projects": {{
+                "{}": {{
+                    "mcpServers": {{
+                        "postgres": {{"command": "pg-mcp"}}
+                    }}
+                }}
+            }}
+        }}"#,
+            project.to_string_lossy().replace('\\', "/")
+        );
+        File::create(dir.path().join(".claude.json"))
+            .unwrap()
+            .write_all(claude_json.as_bytes())
+            .unwrap();
+
+        File::create(project.join(".mcp.json"))
+            .unwrap()
+            .write_all(br#"{"mcpServers": {"sentry": {"type": "http", "url": "https://mcp.sentry.dev/mcp"}}}"#)
+            .unwrap();
+
+        let servers = parse_mcp(&base).unwrap();
+        assert_eq!(servers.len(), 3);
+
+        let github = servers.iter().find(|s| s.name == "github").unwrap();
+        assert_eq!(github.scope, McpScope::User);
+        assert!(github.project.is_none());
+        assert_eq!(github.path, dir.path().join(".claude.json"));
+
+        let postgres = servers.iter().find(|s| s.name == "postgres").unwrap();
+        assert_eq!(postgres.scope, McpScope::Local);
+        assert_eq!(postgres.project, Some(project.clone()));
+
+        let sentry = servers.iter().find(|s| s.name == "sentry").unwrap();
+        assert_eq!(sentry.scope, McpScope::Project);
+        assert_eq!(sentry.project, Some(project.clone()));
+        assert_eq!(sentry.path, project.join(".mcp.json"));
+    }
+
+    #[test]
+    fn test_parse_mcp_combines_legacy_and_claude_json() {
+        let dir = TempDir::new().unwrap();
+        let base = dir.path().join(".claude");
+        std::fs::create_dir_all(base.join("mcp-servers/local-server")).unwrap();
+
+        File::create(dir.path().join(".claude.json"))
+            .unwrap()
+            .write_all(br#"{"mcpServers": {"remote": {"command": "uvx"}}}"#)
+            .unwrap();
+
+        let servers = parse_mcp(&base).unwrap();
+        assert_eq!(servers.len(), 2);
+        assert!(servers.iter().any(|s| s.name == "local-server"));
+        assert!(servers
+            .iter()
+            .any(|s| s.name == "remote" && s.status == "configured"));
+    }
+
+    #[test]
+    fn test_claude_json_path_for_custom_config_dir() {
+        let dir = TempDir::new().unwrap();
+        let config = dir.path().join("claude-config");
+        std::fs::create_dir_all(&config).unwrap();
+        std::fs::write(dir.path().join(".claude.json"), "{}").unwrap();
+
+        // The parent's .claude.json belongs to someone else
+        assert_eq!(claude_json_path(&config), None);
+
+        std::fs::write(config.join(".claude.json"), "{}").unwrap();
+        assert_eq!(claude_json_path(&config), Some(config.join(".claude.json")));
+        assert_eq!(
+            claude_json_path(&dir.path().join(".claude")),
+            Some(dir.path().join(".claude.json"))
+        );
+    }
+
+    #[test]
+    fn test_parse_project_mcp_missing() {
+        let dir = TempDir::new().unwrap();
+        let mut servers = Vec::new();
+        parse_project_mcp(dir.path(), &mut servers, &mut Vec::new());
+        assert!(servers.is_empty());
+    }
+
+    #[test]
+    fn test_claude_json_malformed() {
+        let dir = TempDir::new().unwrap();
+        let base = dir.path().join(".claude");
+        std::fs::create_dir_all(&base).unwrap();
+
+        File::create(dir.path().join(".claude.json"))
+            .unwrap()
+            .write_all(b"{ invalid json }")
+            .unwrap();
+
+        let mut warnings = Vec::new();
+        let servers = parse_mcp_with_warnings(&base, &mut warnings).unwrap();
+        assert!(servers.is_empty());
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].path, dir.path().join(".claude.json"));
+    }
+
     #[test]
     fn test_mcp_directory_without_config() {
         // mcp-servers directory exists but no mcp.json
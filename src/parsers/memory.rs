@@ -0,0 +1,367 @@
+//! Discover CLAUDE.md memory files and the files they import with `@path`
+
+use crate::error::Error;
+use crate::info::{ComponentKind, Diagnostic, MemoryInfo, MemoryScope};
+use crate::parsers::{is_project_root, SKIPPED_DIRS};
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Import hops Claude Code follows from a directly loaded memory file
+const MAX_IMPORT_DEPTH: usize = 5;
+
+/// Memory files looked for in each directory, in load order
+const MEMORY_FILES: [(&str, MemoryScope); 3] = [
+    ("CLAUDE.md", MemoryScope::Project),
+    (".claude/CLAUDE.md", MemoryScope::Project),
+    ("CLAUDE.local.md", MemoryScope::Local),
+];
+
+/// Directory levels below the project searched for nested memory files
+const MAX_NESTED_DEPTH: usize = 5;
+
+pub fn parse_memory(base_path: &Path, project_dir: Option<&Path>) -> Vec<MemoryInfo> {
+    parse_memory_with_warnings(base_path, project_dir, &mut Vec::new())
+}
+
+/// List the memory files Claude Code loads for `project_dir`, in order: the
+/// user's CLAUDE.md, then those of the project and each parent directory
+/// from the filesystem root down, then those in subdirectories, which load
+/// on demand. Each file is followed by the files it imports. Without a
+/// project only the user's memory is listed. Subdirectories are only searched
+/// when `project_dir` is a real project root (see `find_project_root`) or has
+/// a memory file of its own, so a fallback to the home directory does not
+/// walk all of it.
+pub fn parse_memory_with_warnings(
+    base_path: &Path,
+    project_dir: Option<&Path>,
+    warnings: &mut Vec<Diagnostic>,
+) -> Vec<MemoryInfo> {
+    let mut loader = Loader {
+        memory: Vec::new(),
+        visited: HashSet::new(),
+        warnings,
+    };
+    loader.load(&base_path.join("CLAUDE.md"), MemoryScope::User, None, 0);
+
+    if let Some(project_dir) = project_dir {
+        let mut dirs: Vec<&Path> = project_dir.ancestors().collect();
+        dirs.reverse();
+        for dir in dirs {
+            for (file, scope) in MEMORY_FILES {
+                loader.load(&dir.join(file), scope, None, 0);
+            }
+        }
+
+        let mut nested = Vec::new();
+        let has_memory = MEMORY_FILES
+            .iter()
+            .any(|(file, _)| project_dir.join(file).is_file());
+        if has_memory || is_project_root(project_dir, base_path) {
+            nested_dirs(project_dir, 1, &mut nested);
+        }
+        for dir in nested {
+            for (file, _) in MEMORY_FILES {
+                loader.load(&dir.join(file), MemoryScope::Nested, None, 0);
+            }
+        }
+    }
+
+    loader.memory
+}
+
+/// Collects memory files depth-first, loading each real file once
+struct Loader<'a> {
+    memory: Vec<MemoryInfo>,
+    visited: HashSet<PathBuf>,
+    warnings: &'a mut Vec<Diagnostic>,
+}
+
+impl Loader<'_> {
+    fn load(
+        &mut self,
+        path: &Path,
+        scope: MemoryScope,
+        imported_from: Option<&Path>,
+        depth: usize,
+    ) {
+        if !path.is_file() {
+            return;
+        }
+        let real = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        if !self.visited.insert(real) {
+            return;
+        }
+
+        let bytes = match fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                self.warnings.push(Diagnostic::warning(
+                    ComponentKind::Memory,
+                    path,
+                    Error::FileReadError {
+                        path: path.to_path_buf(),
+                        source: e,
+                    },
+                ));
+                return;
+            }
+        };
+        let content = String::from_utf8_lossy(&bytes);
+
+        self.memory.push(MemoryInfo {
+            path: path.to_path_buf(),
+            scope,
+            size: bytes.len() as u64,
+            lines: content.lines().count(),
+            imported_from: imported_from.map(Path::to_path_buf),
+            depth,
+        });
+
+        if depth >= MAX_IMPORT_DEPTH {
+            return;
+        }
+        let dir = path.parent().unwrap_or(Path::new(""));
+        for import in imports(&content) {
+            self.load(&resolve_import(dir, &import), scope, Some(path), depth + 1);
+        }
+    }
+}
+
+/// The `@path` imports in a memory file, skipping code spans and fenced
+/// code blocks. An `@` only starts an import at the start of a line or after
+/// whitespace, so email addresses are not imports.
+pub fn imports(content: &str) -> Vec<String> {
+    let mut imports = Vec::new();
+    let mut fence: Option<&str> = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+            match fence {
+                Some(open) if open == marker => fence = None,
+                Some(_) => {}
+                None => fence = Some(marker),
+            }
+            continue;
+        }
+        if fence.is_some() {
+            continue;
+        }
+
+        // Text between backticks is inline code
+        for text in line.split('`').step_by(2) {
+            let mut previous = ' ';
+            for (i, c) in text.char_indices() {
+                if c == '@' && previous.is_whitespace() {
+                    let path: String = text[i + 1..]
+                        .chars()
+                        .take_while(|c| !c.is_whitespace())
+                        .collect();
+                    if !path.is_empty() {
+                        imports.push(path);
+                    }
+                }
+                previous = c;
+            }
+        }
+    }
+
+    imports
+}
+
+/// Resolve an import relative to the directory of the file that makes it;
+/// `~/` is the home directory
+fn resolve_import(dir: &Path, import: &str) -> PathBuf {
+    if let Some(rest) = import.strip_prefix("~/") {
+        if let Ok(home) = env::var("HOME") {
+            return PathBuf::from(home).join(rest);
+        }
+    }
+    dir.join(import)
+}
+
+/// The subdirectories of `dir` up to `MAX_NESTED_DEPTH` levels down,
+/// depth-first by name, without hidden, dependency or build folders and
+/// without following symlinks
+fn nested_dirs(dir: &Path, depth: usize, dirs: &mut Vec<PathBuf>) {
+    if depth > MAX_NESTED_DEPTH {
+        return;
+    }
+    let mut children: Vec<PathBuf> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+            .filter(|e| {
+                let name = e.file_name();
+                let name = name.to_string_lossy();
+                !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_ref())
+            })
+            .map(|e| e.path())
+            .collect(),
+        Err(_) => return,
+    };
+    children.sort();
+
+    for child in children {
+        dirs.push(child.clone());
+        nested_dirs(&child, depth + 1, dirs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::create_dir_all;
+    use tempfile::TempDir;
+
+    fn write(path: &Path, content: &str) {
+        create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    /// Paths relative to `root`, with their scope and import depth
+    fn listing(memory: &[MemoryInfo], root: &Path) -> Vec<(String, MemoryScope, usize)> {
+        memory
+            .iter()
+            .map(|m| {
+                let path = m.path.strip_prefix(root).unwrap_or(&m.path);
+                (path.to_string_lossy().replace('\\', "/"), m.scope, m.depth)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_user_memory_only_without_project() {
+        let dir = TempDir::new().unwrap();
+        let base = dir.path().join(".claude");
+        write(&base.join("CLAUDE.md"), "# Me\nPrefer tabs\n");
+        write(&dir.path().join("app/CLAUDE.md"), "# App\n");
+
+        let memory = parse_memory(&base, None);
+        assert_eq!(memory.len(), 1);
+        assert_eq!(memory[0].scope, MemoryScope::User);
+        assert_eq!(memory[0].size, 17);
+        assert_eq!(memory[0].lines, 2);
+    }
+
+    #[test]
+    fn test_memory_hierarchy_load_order() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let base = root.join(".claude");
+        let project = root.join("work/app");
+        write(&base.join("CLAUDE.md"), "user\n");
+        write(&root.join("work/CLAUDE.md"), "parent\n");
+        write(&project.join("CLAUDE.local.md"), "local\n");
+        write(&project.join(".claude/CLAUDE.md"), "dot claude\n");
+        write(&project.join("CLAUDE.md"), "project\n");
+        write(&project.join("web/CLAUDE.md"), "nested\n");
+        write(&project.join("node_modules/pkg/CLAUDE.md"), "skipped\n");
+        write(&project.join(".git/CLAUDE.md"), "skipped\n");
+
+        let memory = parse_memory(&base, Some(&project));
+        assert_eq!(
+            listing(&memory, root),
+            [
+                (".claude/CLAUDE.md".to_string(), MemoryScope::User, 0),
+                ("work/CLAUDE.md".to_string(), MemoryScope::Project, 0),
+                ("work/app/CLAUDE.md".to_string(), MemoryScope::Project, 0),
+                (
+                    "work/app/.claude/CLAUDE.md".to_string(),
+                    MemoryScope::Project,
+                    0
+                ),
+                (
+                    "work/app/CLAUDE.local.md".to_string(),
+                    MemoryScope::Local,
+                    0
+                ),
+                ("work/app/web/CLAUDE.md".to_string(), MemoryScope::Nested, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_memory_needs_project_root_and_depth_limit() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let base = root.join(".claude");
+        let project = root.join("home");
+        write(&project.join("notes/CLAUDE.md"), "nested\n");
+
+        // No project marker or memory file: only the directory itself and
+        // its parents
+        assert!(parse_memory(&base, Some(&project)).is_empty());
+
+        create_dir_all(project.join(".git")).unwrap();
+        write(&project.join("a/b/c/d/e/CLAUDE.md"), "deep\n");
+        write(&project.join("a/b/c/d/e/f/CLAUDE.md"), "too deep\n");
+        let memory = parse_memory(&base, Some(&project));
+        assert_eq!(
+            listing(&memory, root),
+            [
+                (
+                    "home/a/b/c/d/e/CLAUDE.md".to_string(),
+                    MemoryScope::Nested,
+                    0
+                ),
+                ("home/notes/CLAUDE.md".to_string(), MemoryScope::Nested, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_memory_imports_resolved_once() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let base = root.join(".claude");
+        let project = root.join("app");
+        write(
+            &project.join("CLAUDE.md"),
+            "See @docs/style.md and @docs/missing.md\nMail me@example.com\n",
+        );
+        write(
+            &project.join("docs/style.md"),
+            "Also @../CLAUDE.md and @rules.md\n",
+        );
+        write(&project.join("docs/rules.md"), "rules\n");
+
+        let memory = parse_memory(&base, Some(&project));
+        assert_eq!(
+            listing(&memory, root),
+            [
+                ("app/CLAUDE.md".to_string(), MemoryScope::Project, 0),
+                ("app/docs/style.md".to_string(), MemoryScope::Project, 1),
+                ("app/docs/rules.md".to_string(), MemoryScope::Project, 2),
+            ]
+        );
+        assert_eq!(memory[1].imported_from, Some(project.join("CLAUDE.md")));
+        assert_eq!(memory[2].imported_from, Some(project.join("docs/style.md")));
+    }
+
+    #[test]
+    fn test_memory_import_depth_limit() {
+        let dir = TempDir::new().unwrap();
+        let base = dir.path().join(".claude");
+        write(&base.join("CLAUDE.md"), "@m1.md\n");
+        for i in 1..=6 {
+            write(
+                &base.join(format!("m{}.md", i)),
+                &format!("@m{}.md\n", i + 1),
+            );
+        }
+
+        let memory = parse_memory(&base, None);
+        assert_eq!(memory.len(), MAX_IMPORT_DEPTH + 1);
+        assert_eq!(memory.last().unwrap().depth, MAX_IMPORT_DEPTH);
+    }
+
+    #[test]
+    fn test_imports_skip_code() {
+        let content =
+            "@a.md first\n`@b.md` and @c.md\n```\n@d.md\n```\n~~~\n```\n@e.md\n~~~\nuser@f.md\n";
+        assert_eq!(imports(content), ["a.md", "c.md"]);
+    }
+}
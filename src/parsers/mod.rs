@@ -2,17 +2,35 @@
 
 pub mod agents;
 pub mod commands;
+pub mod frontmatter;
 pub mod hooks;
+pub mod marketplaces;
 pub mod mcp;
+pub mod memory;
 pub mod plugins;
 pub mod sessions;
+pub mod settings;
 pub mod skills;
 
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::info::{
-    AgentInfo, ClaudeInfo, CommandInfo, HookInfo, McpInfo, PluginInfo, SessionInfo, SkillInfo,
+    AgentInfo, ClaudeInfo, CommandInfo, ComponentKind, Diagnostic, HookInfo, McpInfo, McpScope,
+    MemoryInfo, Origin, PluginInfo, SessionInfo, SettingsInfo, SettingsScope, SkillInfo,
+    SkillLocation,
 };
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Dependency and build folders not searched for nested memory files or
+/// skill resources
+pub const SKIPPED_DIRS: [&str; 6] = [
+    "node_modules",
+    "target",
+    "vendor",
+    "venv",
+    "dist",
+    "__pycache__",
+];
 
 /// Search filter for component name matching.
 #[derive(Debug, Clone, Default)]
@@ -62,41 +80,239 @@ pub struct FilterFlags {
     pub hooks: bool,
     pub agents: bool,
     pub commands: bool,
+    pub memory: bool,
+    pub settings: bool,
+    /// Only enabled plugins (implies plugins)
+    pub enabled: bool,
+    /// Only disabled plugins (implies plugins)
+    pub disabled: bool,
+    /// Only commands and skills never run (implies commands and skills)
+    pub unused: bool,
     pub search: Option<SearchFilter>,
 }
 
 /// Parse all components from .claude directory
 pub fn parse_all(base_path: PathBuf) -> Result<ClaudeInfo> {
-    Ok(ClaudeInfo {
+    parse_all_with_project(base_path, None)
+}
+
+/// Parse all components from .claude directory, merging in the components of
+/// a project root (its `.claude/` directory and `.mcp.json`) when given
+pub fn parse_all_with_project(
+    base_path: PathBuf,
+    project_dir: Option<PathBuf>,
+) -> Result<ClaudeInfo> {
+    let mut warnings = Vec::new();
+    let (sessions, invocations) =
+        sessions::parse_sessions_with_invocations(&base_path, &mut warnings)?;
+    let mut info = ClaudeInfo {
         version: env!("CARGO_PKG_VERSION").to_string(),
         config_dir: base_path.clone(),
-        plugins: plugins::parse_plugins(&base_path)?,
-        skills: skills::parse_skills(&base_path)?,
-        sessions: sessions::parse_sessions(&base_path)?,
-        mcp_servers: mcp::parse_mcp(&base_path)?,
-        hooks: hooks::parse_hooks(&base_path)?,
-        agents: agents::parse_agents(&base_path)?,
-        commands: commands::parse_commands(&base_path)?,
-    })
+        project_dir: project_dir.clone(),
+        plugins: plugins::parse_plugins_with_warnings(&base_path, &mut warnings)?,
+        skills: skills::parse_skills_with_warnings(&base_path, &mut warnings)?,
+        sessions,
+        mcp_servers: mcp::parse_mcp_with_warnings(&base_path, &mut warnings)?,
+        hooks: hooks::parse_hooks_with_origin(&base_path, Origin::User, &mut warnings)?,
+        agents: agents::parse_agents_with_origin(&base_path, Origin::User, &mut warnings)?,
+        commands: commands::parse_commands_with_origin(&base_path, Origin::User, &mut warnings)?,
+        settings: SettingsInfo::default(),
+        memory: memory::parse_memory_with_warnings(
+            &base_path,
+            project_dir.as_deref(),
+            &mut warnings,
+        ),
+        warnings: vec![],
+    };
+
+    settings::parse_settings_file(
+        &base_path.join("settings.json"),
+        SettingsScope::User,
+        &mut info.settings,
+        &mut warnings,
+    );
+
+    // Components shipped by installed plugins
+    info.hooks
+        .extend(hooks::parse_plugin_hooks(&base_path, &mut warnings));
+    info.agents
+        .extend(agents::parse_plugin_agents(&base_path, &mut warnings)?);
+    info.commands
+        .extend(commands::parse_plugin_commands(&base_path, &mut warnings)?);
+
+    if let Some(project_dir) = project_dir {
+        merge_project(&mut info, &project_dir, &mut warnings)?;
+    }
+
+    count_uses(&mut info, &invocations);
+    info.warnings = warnings;
+    Ok(info)
+}
+
+/// Add the administrator's managed settings, which override every other
+/// level. They apply machine-wide, so only merge them when inspecting the
+/// user's own configuration directory.
+pub fn merge_managed_settings(info: &mut ClaudeInfo) {
+    settings::parse_settings_file(
+        &settings::managed_settings_path(),
+        SettingsScope::Managed,
+        &mut info.settings,
+        &mut info.warnings,
+    );
+}
+
+/// Set how often each command and skill was run, from the slash command
+/// invocations in history.jsonl. Plugin components can be run both as
+/// `/name` and as `/plugin:name`.
+fn count_uses(info: &mut ClaudeInfo, invocations: &HashMap<String, usize>) {
+    let uses = |name: &str, plugin_name: Option<&String>| {
+        invocations.get(name).copied().unwrap_or(0)
+            + plugin_name
+                .and_then(|plugin| invocations.get(&format!("{}:{}", plugin, name)))
+                .copied()
+                .unwrap_or(0)
+    };
+
+    for command in &mut info.commands {
+        let plugin_name = match &command.origin {
+            Origin::Plugin { plugin_name } => plugin_name.as_ref(),
+            _ => None,
+        };
+        command.uses = uses(&command.name, plugin_name);
+    }
+    for skill in &mut info.skills {
+        let plugin_name = match &skill.location_type {
+            SkillLocation::Plugin { plugin_name } => plugin_name.as_ref(),
+            _ => None,
+        };
+        skill.uses = uses(&skill.name, plugin_name);
+    }
+}
+
+/// Add project-level components to user-level ones, tagging them with
+/// their project origin
+fn merge_project(
+    info: &mut ClaudeInfo,
+    project_dir: &Path,
+    warnings: &mut Vec<Diagnostic>,
+) -> Result<()> {
+    let project_claude_dir = project_dir.join(".claude");
+
+    // When the project is the home directory, its .claude is the user config
+    if !same_path(&project_claude_dir, &info.config_dir) {
+        info.skills
+            .extend(skills::parse_project_skills(&project_claude_dir, warnings));
+        info.agents.extend(agents::parse_agents_with_origin(
+            &project_claude_dir,
+            Origin::Project,
+            warnings,
+        )?);
+        info.commands.extend(commands::parse_commands_with_origin(
+            &project_claude_dir,
+            Origin::Project,
+            warnings,
+        )?);
+        info.hooks.extend(hooks::parse_hooks_with_origin(
+            &project_claude_dir,
+            Origin::Project,
+            warnings,
+        )?);
+        // Project settings can enable or disable user-installed plugins
+        for (file, scope) in [
+            ("settings.json", SettingsScope::Project),
+            ("settings.local.json", SettingsScope::Local),
+        ] {
+            let path = project_claude_dir.join(file);
+            plugins::apply_enabled_plugins(&mut info.plugins, &path);
+            settings::parse_settings_file(&path, scope, &mut info.settings, warnings);
+        }
+    }
+
+    // Only project and local MCP servers of this project apply
+    info.mcp_servers.retain(|server| {
+        matches!(server.scope, McpScope::User | McpScope::Plugin)
+            || server
+                .project
+                .as_deref()
+                .is_some_and(|p| same_path(p, project_dir))
+    });
+    // The .mcp.json of a project listed in ~/.claude.json was already read,
+    // whether it gave servers or a warning
+    let mcp_json = project_dir.join(".mcp.json");
+    let already_read = info
+        .mcp_servers
+        .iter()
+        .map(|server| &server.path)
+        .chain(warnings.iter().map(|warning| &warning.path))
+        .any(|path| same_path(path, &mcp_json));
+    if !already_read {
+        mcp::parse_project_mcp(project_dir, &mut info.mcp_servers, warnings);
+    }
+
+    Ok(())
+}
+
+/// Compare two paths, resolving symlinks when both exist
+pub fn same_path(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Find the project root for `start` by walking up to the nearest directory
+/// that has a `.claude/` directory (other than the user config directory),
+/// a `.mcp.json` file or a `.git` entry. Falls back to `start` itself.
+pub fn find_project_root(start: &Path, config_dir: &Path) -> PathBuf {
+    start
+        .ancestors()
+        .find(|dir| is_project_root(dir, config_dir))
+        .unwrap_or(start)
+        .to_path_buf()
+}
+
+/// Whether `dir` has one of the markers `find_project_root` looks for
+pub fn is_project_root(dir: &Path, config_dir: &Path) -> bool {
+    let claude_dir = dir.join(".claude");
+    (claude_dir.is_dir() && !same_path(&claude_dir, config_dir))
+        || dir.join(".mcp.json").is_file()
+        || dir.join(".git").exists()
+}
+
+/// Warning for a JSON file that could not be parsed and was skipped
+pub fn invalid_json(component: ComponentKind, path: &Path, error: serde_json::Error) -> Diagnostic {
+    Diagnostic::warning(
+        component,
+        path,
+        Error::InvalidComponent(path.to_path_buf(), format!("invalid JSON: {}", error)),
+    )
 }
 
 /// Filter components based on CLI flags
 pub fn filter_components(info: ClaudeInfo, filters: FilterFlags) -> ClaudeInfo {
+    // --enabled/--disabled select plugins by state
+    let plugins = filters.plugins || filters.enabled || filters.disabled;
+    // --unused selects commands and skills by usage
+    let skills = filters.skills || filters.unused;
+    let commands = filters.commands || filters.unused;
+
     // If no filter flags, show all
-    let show_all = !(filters.plugins
-        || filters.skills
+    let show_all = !(plugins
+        || skills
         || filters.sessions
         || filters.mcp
         || filters.hooks
         || filters.agents
-        || filters.commands);
+        || commands
+        || filters.memory
+        || filters.settings);
 
     // Get search filter for matching
     let search_filter = filters.search.as_ref();
 
     ClaudeInfo {
-        plugins: filter_plugin_list(info.plugins, show_all || filters.plugins, search_filter),
-        skills: filter_skill_list(info.skills, show_all || filters.skills, search_filter),
+        plugins: filter_plugin_list(info.plugins, show_all || plugins, &filters, search_filter),
+        skills: filter_skill_list(info.skills, show_all || skills, &filters, search_filter),
         sessions: if show_all || filters.sessions {
             info.sessions
         } else {
@@ -108,20 +324,51 @@ pub fn filter_components(info: ClaudeInfo, filters: FilterFlags) -> ClaudeInfo {
         mcp_servers: filter_mcp_list(info.mcp_servers, show_all || filters.mcp, search_filter),
         hooks: filter_hook_list(info.hooks, show_all || filters.hooks, search_filter),
         agents: filter_agent_list(info.agents, show_all || filters.agents, search_filter),
-        commands: filter_command_list(info.commands, show_all || filters.commands, search_filter),
+        commands: filter_command_list(info.commands, show_all || commands, &filters, search_filter),
+        settings: filter_settings(info.settings, show_all || filters.settings, search_filter),
+        memory: filter_memory_list(info.memory, show_all || filters.memory, search_filter),
         version: info.version,
         config_dir: info.config_dir,
+        project_dir: info.project_dir,
+        warnings: info
+            .warnings
+            .into_iter()
+            .filter(|w| {
+                show_all
+                    || match w.component {
+                        ComponentKind::Plugin => plugins,
+                        ComponentKind::Skill => skills,
+                        ComponentKind::Session => filters.sessions,
+                        ComponentKind::Mcp => filters.mcp,
+                        ComponentKind::Hook => filters.hooks,
+                        ComponentKind::Agent => filters.agents,
+                        ComponentKind::Command => commands,
+                        ComponentKind::Memory => filters.memory,
+                        ComponentKind::Settings => filters.settings,
+                    }
+            })
+            .collect(),
     }
 }
 
 fn filter_plugin_list(
     plugins: Vec<PluginInfo>,
     include: bool,
+    filters: &FilterFlags,
     search: Option<&SearchFilter>,
 ) -> Vec<PluginInfo> {
     if !include {
         return vec![];
     }
+    // Passing both --enabled and --disabled keeps every plugin
+    let plugins: Vec<PluginInfo> = if filters.enabled != filters.disabled {
+        plugins
+            .into_iter()
+            .filter(|p| p.enabled == filters.enabled)
+            .collect()
+    } else {
+        plugins
+    };
     if let Some(search) = search {
         plugins
             .into_iter()
@@ -135,11 +382,16 @@ fn filter_plugin_list(
 fn filter_skill_list(
     skills: Vec<SkillInfo>,
     include: bool,
+    filters: &FilterFlags,
     search: Option<&SearchFilter>,
 ) -> Vec<SkillInfo> {
     if !include {
         return vec![];
     }
+    let skills: Vec<SkillInfo> = skills
+        .into_iter()
+        .filter(|s| !filters.unused || s.uses == 0)
+        .collect();
     if let Some(search) = search {
         skills
             .into_iter()
@@ -203,14 +455,63 @@ fn filter_agent_list(
     }
 }
 
+fn filter_settings(
+    settings: SettingsInfo,
+    include: bool,
+    search: Option<&SearchFilter>,
+) -> SettingsInfo {
+    if !include {
+        return SettingsInfo::default();
+    }
+    if let Some(search) = search {
+        SettingsInfo {
+            values: settings
+                .values
+                .into_iter()
+                .filter(|v| search.matches(&v.key))
+                .collect(),
+            permissions: settings
+                .permissions
+                .into_iter()
+                .filter(|p| search.matches(&p.rule))
+                .collect(),
+        }
+    } else {
+        settings
+    }
+}
+
+fn filter_memory_list(
+    memory: Vec<MemoryInfo>,
+    include: bool,
+    search: Option<&SearchFilter>,
+) -> Vec<MemoryInfo> {
+    if !include {
+        return vec![];
+    }
+    if let Some(search) = search {
+        memory
+            .into_iter()
+            .filter(|m| search.matches(&m.path.to_string_lossy()))
+            .collect()
+    } else {
+        memory
+    }
+}
+
 fn filter_command_list(
     commands: Vec<CommandInfo>,
     include: bool,
+    filters: &FilterFlags,
     search: Option<&SearchFilter>,
 ) -> Vec<CommandInfo> {
     if !include {
         return vec![];
     }
+    let commands: Vec<CommandInfo> = commands
+        .into_iter()
+        .filter(|c| !filters.unused || c.uses == 0)
+        .collect();
     if let Some(search) = search {
         commands
             .into_iter()
@@ -254,17 +555,243 @@ mod tests {
     #[test]
     fn test_search_filter_empty() {
         let filter = SearchFilter::new("");
-        assert!(filter.is_active() == false);
+        assert!(!filter.is_active());
         assert!(filter.matches("anything"));
     }
 
     #[test]
     fn test_search_filter_whitespace_only() {
         let filter = SearchFilter::new("   ");
-        assert!(filter.is_active() == false);
+        assert!(!filter.is_active());
         assert!(filter.matches("anything"));
     }
 
+    #[test]
+    fn test_parse_all_with_project_merges_components() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let home = dir.path().join("home");
+        let config_dir = home.join(".claude");
+        std::fs::create_dir_all(config_dir.join("agents")).unwrap();
+        std::fs::write(config_dir.join("agents/reviewer.md"), "# Reviewer").unwrap();
+
+        let project = dir.path().join("repo");
+        let project_claude = project.join(".claude");
+        std::fs::create_dir_all(project_claude.join("agents")).unwrap();
+        std::fs::create_dir_all(project_claude.join("commands")).unwrap();
+        std::fs::create_dir_all(project_claude.join("skills/deploy")).unwrap();
+        std::fs::write(project_claude.join("agents/db-expert.md"), "# DB").unwrap();
+        std::fs::write(project_claude.join("commands/release.md"), "# Release").unwrap();
+        std::fs::write(project_claude.join("skills/deploy/SKILL.md"), "# Deploy").unwrap();
+        std::fs::write(
+            project_claude.join("settings.local.json"),
+            r#"{"hooks": {"Stop": [{"hooks": [{"type": "command", "command": "make lint"}]}]}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            project.join(".mcp.json"),
+            r#"{"mcpServers": {"repo-db": {"command": "db-mcp"}}}"#,
+        )
+        .unwrap();
+
+        let info = parse_all_with_project(config_dir, Some(project.clone())).unwrap();
+
+        assert_eq!(info.project_dir, Some(project.clone()));
+        assert_eq!(info.agents.len(), 2);
+        let reviewer = info.agents.iter().find(|a| a.name == "reviewer").unwrap();
+        assert_eq!(reviewer.origin, Origin::User);
+        let db = info.agents.iter().find(|a| a.name == "db-expert").unwrap();
+        assert_eq!(db.origin, Origin::Project);
+
+        assert_eq!(info.commands.len(), 1);
+        assert_eq!(info.commands[0].origin, Origin::Project);
+        assert_eq!(info.skills.len(), 1);
+        assert_eq!(
+            info.skills[0].location_type,
+            crate::info::SkillLocation::Project
+        );
+        assert_eq!(info.hooks.len(), 1);
+        assert_eq!(info.hooks[0].origin, Origin::Project);
+        assert_eq!(info.mcp_servers.len(), 1);
+        assert_eq!(info.mcp_servers[0].scope, McpScope::Project);
+    }
+
+    #[test]
+    fn test_parse_all_with_project_drops_other_projects_mcp() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config_dir = dir.path().join(".claude");
+        std::fs::create_dir_all(&config_dir).unwrap();
+
+        let project = dir.path().join("repo");
+        let other = dir.path().join("other");
+        std::fs::create_dir_all(&project).unwrap();
+        std::fs::create_dir_all(&other).unwrap();
+
+        let claude_json = format!(
+            r#"{{
+                "mcpServers": {{"everywhere": {{"command": "a"}}}},
+                "projects": {{
+                    "{}": {{"mcpServers": {{"mine": {{"command": "b"}}}}}},
+                    "{}": {{"mcpServers": {{"theirs": {{"command": "c"}}}}}}
+                }}
+            }}"#,
+            project.to_string_lossy().replace('\\', "/"),
+            other.to_string_lossy().replace('\\', "/")
+        );
+        std::fs::write(dir.path().join(".claude.json"), claude_json).unwrap();
+
+        let info = parse_all_with_project(config_dir, Some(project)).unwrap();
+        let names: Vec<_> = info.mcp_servers.iter().map(|s| s.name.as_str()).collect();
+        assert!(names.contains(&"everywhere"));
+        assert!(names.contains(&"mine"));
+        assert!(!names.contains(&"theirs"));
+    }
+
+    #[test]
+    fn test_malformed_project_mcp_json_reported_once() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config_dir = dir.path().join(".claude");
+        std::fs::create_dir_all(&config_dir).unwrap();
+        let project = dir.path().join("repo");
+        std::fs::create_dir_all(&project).unwrap();
+        std::fs::write(project.join(".mcp.json"), "{ not json").unwrap();
+
+        let claude_json = format!(
+            r#"{{"projects": {{"{}": {{}}}}}}"#,
+            project.to_string_lossy().replace('\\', "/")
+        );
+        std::fs::write(dir.path().join(".claude.json"), claude_json).unwrap();
+
+        let info = parse_all_with_project(config_dir, Some(project.clone())).unwrap();
+        assert_eq!(info.warnings.len(), 1);
+        assert_eq!(info.warnings[0].path, project.join(".mcp.json"));
+    }
+
+    #[test]
+    fn test_parse_all_includes_plugin_components() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config_dir = dir.path().join(".claude");
+        let plugin_root = dir.path().join("cache/toolkit/1.0.0");
+        std::fs::create_dir_all(config_dir.join("plugins")).unwrap();
+        std::fs::create_dir_all(plugin_root.join("agents")).unwrap();
+        std::fs::create_dir_all(plugin_root.join("commands")).unwrap();
+        std::fs::create_dir_all(plugin_root.join("hooks")).unwrap();
+
+        std::fs::write(
+            config_dir.join("plugins/installed_plugins.json"),
+            format!(
+                r#"{{"version": 2, "plugins": {{"toolkit@market": [{{"installPath": "{}"}}]}}}}"#,
+                plugin_root.to_string_lossy().replace('\\', "/")
+            ),
+        )
+        .unwrap();
+        std::fs::write(
+            plugin_root.join("agents/helper.md"),
+            "---\nname: helper\ndescription: Helps\n---\n",
+        )
+        .unwrap();
+        std::fs::write(plugin_root.join("commands/build.md"), "# Build\n").unwrap();
+        std::fs::write(
+            plugin_root.join("hooks/hooks.json"),
+            r#"{"hooks": {"Stop": [{"hooks": [{"type": "command", "command": "notify"}]}]}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            plugin_root.join(".mcp.json"),
+            r#"{"mcpServers": {"toolkit-server": {"command": "toolkit"}}}"#,
+        )
+        .unwrap();
+
+        let info = parse_all(config_dir).unwrap();
+        let origin = Origin::Plugin {
+            plugin_name: Some("toolkit".to_string()),
+        };
+
+        assert_eq!(info.agents.len(), 1);
+        assert_eq!(info.agents[0].origin, origin);
+        assert_eq!(info.commands.len(), 1);
+        assert_eq!(info.commands[0].origin, origin);
+        assert_eq!(info.hooks.len(), 1);
+        assert_eq!(info.hooks[0].origin, origin);
+        assert_eq!(info.hooks[0].command, Some("notify".to_string()));
+        assert_eq!(info.mcp_servers.len(), 1);
+        assert_eq!(info.mcp_servers[0].scope, McpScope::Plugin);
+        assert_eq!(info.mcp_servers[0].plugin, Some("toolkit".to_string()));
+    }
+
+    #[test]
+    fn test_parse_all_counts_command_uses() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config_dir = dir.path().join(".claude");
+        let plugin_root = dir.path().join("cache/toolkit/1.0.0");
+        std::fs::create_dir_all(config_dir.join("plugins")).unwrap();
+        std::fs::create_dir_all(config_dir.join("commands")).unwrap();
+        std::fs::create_dir_all(plugin_root.join("commands")).unwrap();
+
+        std::fs::write(
+            config_dir.join("plugins/installed_plugins.json"),
+            format!(
+                r#"{{"version": 2, "plugins": {{"toolkit@market": [{{"installPath": "{}"}}]}}}}"#,
+                plugin_root.to_string_lossy().replace('\\', "/")
+            ),
+        )
+        .unwrap();
+        std::fs::write(config_dir.join("commands/review.md"), "# Review\n").unwrap();
+        std::fs::write(plugin_root.join("commands/build.md"), "# Build\n").unwrap();
+        std::fs::write(
+            config_dir.join("history.jsonl"),
+            concat!(
+                r#"{"display":"/build","timestamp":1}"#,
+                "\n",
+                r#"{"display":"/toolkit:build --release","timestamp":2}"#,
+                "\n",
+                r#"{"display":"/review","timestamp":3}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+
+        let info = parse_all(config_dir).unwrap();
+        let uses = |name: &str| info.commands.iter().find(|c| c.name == name).unwrap().uses;
+        assert_eq!(uses("build"), 2);
+        assert_eq!(uses("review"), 1);
+
+        let unused = filter_components(
+            info,
+            FilterFlags {
+                unused: true,
+                ..Default::default()
+            },
+        );
+        assert!(unused.commands.is_empty());
+        assert!(unused.plugins.is_empty());
+    }
+
+    #[test]
+    fn test_find_project_root_walks_up() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let project = dir.path().join("repo");
+        let nested = project.join("src/module");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::create_dir_all(project.join(".claude")).unwrap();
+
+        let config_dir = dir.path().join("home/.claude");
+        assert_eq!(find_project_root(&nested, &config_dir), project);
+    }
+
+    #[test]
+    fn test_find_project_root_skips_user_config_dir() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let home = dir.path().join("home");
+        let config_dir = home.join(".claude");
+        let work = home.join("scratch");
+        std::fs::create_dir_all(&config_dir).unwrap();
+        std::fs::create_dir_all(&work).unwrap();
+        std::fs::create_dir_all(home.join(".git")).unwrap();
+
+        // home/.claude is the user config, so the .git marker decides
+        assert_eq!(find_project_root(&work, &config_dir), home);
+    }
+
     #[test]
     fn test_search_filter_three_keywords() {
         let filter = SearchFilter::new("a b c");
@@ -1,17 +1,71 @@
 //! Parse installed plugins from plugins/installed_plugins.json or settings.json
 
 use crate::error::Result;
-use crate::info::{PluginInfo, Source};
+use crate::info::{ComponentKind, Diagnostic, PluginInfo, PluginInstall, Source};
+use crate::parsers::invalid_json;
 use serde_json::Value;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// Marketplaces run by Anthropic; plugins from any other are third-party
+const OFFICIAL_MARKETPLACES: &[&str] = &["claude-plugins-official", "anthropic-agent-skills"];
+
 pub fn parse_plugins(base_path: &Path) -> Result<Vec<PluginInfo>> {
+    parse_plugins_with_warnings(base_path, &mut Vec::new())
+}
+
+/// Parse plugins, recording files that had to be skipped in `warnings`
+pub fn parse_plugins_with_warnings(
+    base_path: &Path,
+    warnings: &mut Vec<Diagnostic>,
+) -> Result<Vec<PluginInfo>> {
+    let mut plugins = parse_installed_plugins(base_path, warnings)?;
+    for file in ["settings.json", "settings.local.json"] {
+        apply_enabled_plugins(&mut plugins, &base_path.join(file));
+    }
+    Ok(plugins)
+}
+
+/// Apply the `enabledPlugins` map of a settings file on top of the current
+/// state. Keys are `name@marketplace`, or a bare name for plugins installed
+/// without a marketplace. Unreadable files are left to the settings parser
+/// to report.
+pub fn apply_enabled_plugins(plugins: &mut [PluginInfo], settings_path: &Path) {
+    let json: Value = match fs::read_to_string(settings_path)
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+    {
+        Some(j) => j,
+        None => return,
+    };
+
+    let enabled_plugins = match json.get("enabledPlugins").and_then(|v| v.as_object()) {
+        Some(obj) => obj,
+        None => return,
+    };
+
+    for plugin in plugins.iter_mut() {
+        let key = match plugin.marketplace {
+            Some(ref marketplace) => format!("{}@{}", plugin.name, marketplace),
+            None => plugin.name.clone(),
+        };
+        if let Some(enabled) = enabled_plugins.get(&key).and_then(|v| v.as_bool()) {
+            plugin.enabled = enabled;
+        }
+    }
+}
+
+/// Parse the install records, from installed_plugins.json or the legacy
+/// `installed_plugins` list in settings.json
+fn parse_installed_plugins(
+    base_path: &Path,
+    warnings: &mut Vec<Diagnostic>,
+) -> Result<Vec<PluginInfo>> {
     // Try new format: plugins/installed_plugins.json
     let installed_path = base_path.join("plugins").join("installed_plugins.json");
 
     if installed_path.exists() {
-        return parse_plugins_v2(&installed_path);
+        return parse_plugins_v2(&installed_path, warnings);
     }
 
     // Fallback to old format: settings.json
@@ -26,7 +80,7 @@ pub fn parse_plugins(base_path: &Path) -> Result<Vec<PluginInfo>> {
         Err(_) => return Ok(vec![]),
     };
 
-    // Gracefully handle malformed JSON
+    // Malformed JSON is reported by the settings parser
     let json: Value = match serde_json::from_str(&content) {
         Ok(j) => j,
         Err(_) => return Ok(vec![]),
@@ -46,7 +100,14 @@ pub fn parse_plugins(base_path: &Path) -> Result<Vec<PluginInfo>> {
                         version,
                         source: Source::Official,
                         path: settings_path.clone(),
+                        marketplace: None,
+                        enabled: true,
                         description: None,
+                        author: None,
+                        homepage: None,
+                        repository: None,
+                        keywords: vec![],
+                        installs: vec![],
                     })
                 })
                 .collect()
@@ -57,7 +118,10 @@ pub fn parse_plugins(base_path: &Path) -> Result<Vec<PluginInfo>> {
 }
 
 /// Parse new format: plugins/installed_plugins.json (version 2)
-fn parse_plugins_v2(installed_path: &Path) -> Result<Vec<PluginInfo>> {
+fn parse_plugins_v2(
+    installed_path: &Path,
+    warnings: &mut Vec<Diagnostic>,
+) -> Result<Vec<PluginInfo>> {
     let content = match fs::read_to_string(installed_path) {
         Ok(c) => c,
         Err(_) => return Ok(vec![]),
@@ -66,52 +130,219 @@ fn parse_plugins_v2(installed_path: &Path) -> Result<Vec<PluginInfo>> {
     // Gracefully handle malformed JSON
     let json: Value = match serde_json::from_str(&content) {
         Ok(j) => j,
-        Err(_) => return Ok(vec![]),
+        Err(e) => {
+            warnings.push(invalid_json(ComponentKind::Plugin, installed_path, e));
+            return Ok(vec![]);
+        }
     };
 
     let mut plugins = Vec::new();
 
     if let Some(plugins_obj) = json.get("plugins").and_then(|v| v.as_object()) {
         for (full_name, plugin_array) in plugins_obj {
-            // Parse "name@source" format - extract just the name
-            let name = full_name.split('@').next().unwrap_or(full_name).to_string();
-
-            if let Some(arr) = plugin_array.as_array() {
-                if let Some(first) = arr.first() {
-                    let version = first
-                        .get("version")
-                        .and_then(|v| v.as_str())
-                        .map(String::from);
-
-                    // Use installPath for actual plugin installation location,
-                    // fall back to projectPath (project root), then config file
-                    let path = first
-                        .get("installPath")
+            // Parse "name@source" format - the source is the marketplace
+            let (name, marketplace) = match full_name.split_once('@') {
+                Some((name, marketplace)) => (name.to_string(), Some(marketplace.to_string())),
+                None => (full_name.clone(), None),
+            };
+
+            let records = match plugin_array.as_array() {
+                Some(arr) => arr,
+                None => continue,
+            };
+            let first = match primary_record(records) {
+                Some(record) => record,
+                None => continue,
+            };
+
+            let version = first
+                .get("version")
+                .and_then(|v| v.as_str())
+                .map(String::from);
+
+            // Use installPath for actual plugin installation location,
+            // fall back to projectPath (project root), then config file
+            let path = first
+                .get("installPath")
+                .and_then(|v| v.as_str())
+                .map(PathBuf::from)
+                .or_else(|| {
+                    first
+                        .get("projectPath")
                         .and_then(|v| v.as_str())
                         .map(PathBuf::from)
-                        .or_else(|| {
-                            first
-                                .get("projectPath")
-                                .and_then(|v| v.as_str())
-                                .map(PathBuf::from)
-                        })
-                        .unwrap_or_else(|| installed_path.to_path_buf());
-
-                    plugins.push(PluginInfo {
-                        name,
-                        version,
-                        source: Source::Official,
-                        path,
-                        description: None,
-                    });
-                }
+                })
+                .unwrap_or_else(|| installed_path.to_path_buf());
+
+            let source = match marketplace.as_deref() {
+                Some(m) if !OFFICIAL_MARKETPLACES.contains(&m) => Source::ThirdParty,
+                _ => Source::Official,
+            };
+
+            let mut plugin = PluginInfo {
+                name,
+                version,
+                source,
+                path,
+                marketplace,
+                enabled: true,
+                description: None,
+                author: None,
+                homepage: None,
+                repository: None,
+                keywords: vec![],
+                installs: records.iter().map(parse_install).collect(),
+            };
+            if plugin.path.is_dir() {
+                apply_manifest(&mut plugin, warnings);
             }
+            plugins.push(plugin);
         }
     }
 
     Ok(plugins)
 }
 
+/// The install record that stands for a plugin installed in several
+/// scopes: the user-scope one (older records have no scope), else the first
+fn primary_record(records: &[Value]) -> Option<&Value> {
+    records
+        .iter()
+        .find(|record| {
+            record
+                .get("scope")
+                .and_then(|v| v.as_str())
+                .map_or(true, |scope| scope == "user")
+        })
+        .or_else(|| records.first())
+}
+
+/// Read one install record (user scope, or one per project)
+fn parse_install(record: &Value) -> PluginInstall {
+    let string = |key: &str| record.get(key).and_then(|v| v.as_str()).map(String::from);
+
+    PluginInstall {
+        scope: string("scope"),
+        version: string("version"),
+        install_path: string("installPath").map(PathBuf::from),
+        project_path: string("projectPath").map(PathBuf::from),
+        installed_at: string("installedAt"),
+        last_updated: string("lastUpdated"),
+        git_commit_sha: string("gitCommitSha"),
+        is_local: record
+            .get("isLocal")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+    }
+}
+
+/// Fill in description, author, homepage, repository and keywords from the
+/// plugin's `.claude-plugin/plugin.json` manifest. The installed version
+/// takes precedence over the manifest's.
+fn apply_manifest(plugin: &mut PluginInfo, warnings: &mut Vec<Diagnostic>) {
+    let manifest_path = plugin.path.join(".claude-plugin").join("plugin.json");
+    let content = match fs::read_to_string(&manifest_path) {
+        Ok(c) => c,
+        Err(_) => return,
+    };
+
+    let manifest: Value = match serde_json::from_str(&content) {
+        Ok(j) => j,
+        Err(e) => {
+            warnings.push(invalid_json(ComponentKind::Plugin, &manifest_path, e));
+            return;
+        }
+    };
+
+    let string = |key: &str| manifest.get(key).and_then(|v| v.as_str()).map(String::from);
+
+    plugin.description = string("description");
+    plugin.homepage = string("homepage");
+    if plugin.version.is_none() {
+        plugin.version = string("version");
+    }
+
+    // author is either "Name" or {"name": ..., "email": ..., "url": ...}
+    plugin.author = match manifest.get("author") {
+        Some(Value::String(name)) => Some(name.clone()),
+        Some(author) => author
+            .get("name")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        None => None,
+    };
+
+    // repository is either a URL or {"type": "git", "url": ...}
+    plugin.repository = match manifest.get("repository") {
+        Some(Value::String(url)) => Some(url.clone()),
+        Some(repository) => repository
+            .get("url")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        None => None,
+    };
+
+    plugin.keywords = manifest
+        .get("keywords")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|k| k.as_str())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+}
+
+/// Install location of a plugin listed in installed_plugins.json
+pub struct PluginRoot {
+    pub install_path: PathBuf,
+    pub plugin_name: String,
+}
+
+/// Get plugin install paths from installed_plugins.json, used to find the
+/// skills, agents, commands, hooks and MCP servers plugins ship. A plugin
+/// installed in several scopes is read from its primary record, the same
+/// one its version and path come from.
+pub fn plugin_roots(base_path: &Path) -> Vec<PluginRoot> {
+    let installed_path = base_path.join("plugins").join("installed_plugins.json");
+    if !installed_path.exists() {
+        return vec![];
+    }
+
+    let content = match fs::read_to_string(&installed_path) {
+        Ok(c) => c,
+        Err(_) => return vec![],
+    };
+
+    let json: Value = match serde_json::from_str(&content) {
+        Ok(j) => j,
+        Err(_) => return vec![],
+    };
+
+    let mut plugins = Vec::new();
+
+    if let Some(plugins_obj) = json.get("plugins").and_then(|v| v.as_object()) {
+        for (full_name, plugin_array) in plugins_obj {
+            // Parse "name@source" format to extract plugin name
+            let plugin_name = full_name.split('@').next().unwrap_or(full_name).to_string();
+
+            if let Some(arr) = plugin_array.as_array() {
+                if let Some(first) = primary_record(arr) {
+                    if let Some(install_path) = first.get("installPath").and_then(|v| v.as_str()) {
+                        plugins.push(PluginRoot {
+                            install_path: PathBuf::from(install_path),
+                            plugin_name: plugin_name.clone(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    plugins
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -194,9 +425,10 @@ mod tests {
         // context7 has no projectPath, so path should be the config file
         assert!(context7.path.ends_with("installed_plugins.json"));
 
-        // custom-plugin should also be Official (no source differentiation)
+        // custom-plugin comes from a third-party marketplace
         let custom = plugins.iter().find(|p| p.name == "custom-plugin").unwrap();
-        assert_eq!(custom.source, Source::Official);
+        assert_eq!(custom.source, Source::ThirdParty);
+        assert_eq!(custom.marketplace, Some("custom-source".to_string()));
         // custom-plugin has installPath, so path should be that (prioritized over projectPath)
         assert_eq!(
             custom.path,
@@ -246,6 +478,250 @@ mod tests {
         assert!(plugins.is_empty());
     }
 
+    #[test]
+    fn test_malformed_installed_json_records_warning() {
+        let dir = TempDir::new().unwrap();
+        let plugins_dir = dir.path().join("plugins");
+        std::fs::create_dir_all(&plugins_dir).unwrap();
+        File::create(plugins_dir.join("installed_plugins.json"))
+            .unwrap()
+            .write_all(b"{ invalid }")
+            .unwrap();
+
+        let mut warnings = Vec::new();
+        let plugins = parse_plugins_with_warnings(dir.path(), &mut warnings).unwrap();
+        assert!(plugins.is_empty());
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].component, ComponentKind::Plugin);
+        assert!(warnings[0].message.contains("invalid JSON"));
+    }
+
+    #[test]
+    fn test_plugin_manifest_fields() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+        let install_path = path.join("cache/toolkit/1.0.0");
+        std::fs::create_dir_all(install_path.join(".claude-plugin")).unwrap();
+        std::fs::create_dir_all(path.join("plugins")).unwrap();
+
+        File::create(install_path.join(".claude-plugin/plugin.json"))
+            .unwrap()
+            .write_all(
+                br#"{
+                    "name": "toolkit",
+                    "version": "9.9.9",
+                    "description": "Handy tools",
+                    "author": {"name": "Jane Doe", "email": "jane@example.com"},
+                    "homepage": "https://example.com/toolkit",
+                    "repository": {"type": "git", "url": "https://github.com/example/toolkit"},
+                    "keywords": ["tools", "productivity"]
+                }"#,
+            )
+            .unwrap();
+
+        let installed = format!(
+            r#"{{"version": 2, "plugins": {{"toolkit@market": [{{"installPath": "{}", "version": "1.0.0"}}]}}}}"#,
+            install_path.to_string_lossy().replace('\\', "/")
+        );
+        File::create(path.join("plugins/installed_plugins.json"))
+            .unwrap()
+            .write_all(installed.as_bytes())
+            .unwrap();
+
+        let plugins = parse_plugins(path).unwrap();
+        assert_eq!(plugins.len(), 1);
+        let plugin = &plugins[0];
+        assert_eq!(plugin.version, Some("1.0.0".to_string()));
+        assert_eq!(plugin.description, Some("Handy tools".to_string()));
+        assert_eq!(plugin.author, Some("Jane Doe".to_string()));
+        assert_eq!(
+            plugin.homepage,
+            Some("https://example.com/toolkit".to_string())
+        );
+        assert_eq!(
+            plugin.repository,
+            Some("https://github.com/example/toolkit".to_string())
+        );
+        assert_eq!(plugin.keywords, vec!["tools", "productivity"]);
+    }
+
+    #[test]
+    fn test_user_install_record_preferred() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+        let plugins_dir = path.join("plugins");
+        std::fs::create_dir_all(&plugins_dir).unwrap();
+
+        let installed_json = r#"{
+            "version": 2,
+            "plugins": {
+                "formatter@team": [
+                    {"scope": "project", "version": "0.9.0", "projectPath": "/work/app", "installPath": "/cache/formatter/0.9.0"},
+                    {"scope": "user", "version": "1.0.0", "installPath": "/cache/formatter/1.0.0"}
+                ],
+                "linter@team": [
+                    {"scope": "local", "version": "2.0.0", "projectPath": "/work/app", "installPath": "/cache/linter/2.0.0"}
+                ]
+            }
+        }"#;
+        std::fs::write(plugins_dir.join("installed_plugins.json"), installed_json).unwrap();
+
+        let plugins = parse_plugins(path).unwrap();
+        let formatter = plugins.iter().find(|p| p.name == "formatter").unwrap();
+        assert_eq!(formatter.version, Some("1.0.0".to_string()));
+        assert_eq!(formatter.path, PathBuf::from("/cache/formatter/1.0.0"));
+        let linter = plugins.iter().find(|p| p.name == "linter").unwrap();
+        assert_eq!(linter.version, Some("2.0.0".to_string()));
+
+        let mut roots: Vec<(String, PathBuf)> = plugin_roots(path)
+            .into_iter()
+            .map(|root| (root.plugin_name, root.install_path))
+            .collect();
+        roots.sort();
+        assert_eq!(
+            roots,
+            [
+                (
+                    "formatter".to_string(),
+                    PathBuf::from("/cache/formatter/1.0.0")
+                ),
+                ("linter".to_string(), PathBuf::from("/cache/linter/2.0.0")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_all_install_records_kept() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+        let plugins_dir = path.join("plugins");
+        std::fs::create_dir_all(&plugins_dir).unwrap();
+
+        let installed_json = r#"{
+            "version": 2,
+            "plugins": {
+                "formatter@team": [
+                    {
+                        "scope": "user",
+                        "version": "1.0.0",
+                        "installedAt": "2026-01-14T04:28:57.441Z",
+                        "lastUpdated": "2026-02-01T09:00:00.000Z",
+                        "gitCommitSha": "d49ad3558669"
+                    },
+                    {
+                        "scope": "project",
+                        "version": "0.9.0",
+                        "projectPath": "/work/app",
+                        "installedAt": "2026-01-02T10:00:00.000Z",
+                        "isLocal": true
+                    }
+                ]
+            }
+        }"#;
+        File::create(plugins_dir.join("installed_plugins.json"))
+            .unwrap()
+            .write_all(installed_json.as_bytes())
+            .unwrap();
+
+        let plugins = parse_plugins(path).unwrap();
+        assert_eq!(plugins.len(), 1);
+        let plugin = &plugins[0];
+        assert_eq!(plugin.version, Some("1.0.0".to_string()));
+        assert_eq!(plugin.installs.len(), 2);
+
+        let user = &plugin.installs[0];
+        assert_eq!(user.scope, Some("user".to_string()));
+        assert_eq!(
+            user.installed_at,
+            Some("2026-01-14T04:28:57.441Z".to_string())
+        );
+        assert_eq!(
+            user.last_updated,
+            Some("2026-02-01T09:00:00.000Z".to_string())
+        );
+        assert_eq!(user.git_commit_sha, Some("d49ad3558669".to_string()));
+        assert!(!user.is_local);
+
+        let project = &plugin.installs[1];
+        assert_eq!(project.scope, Some("project".to_string()));
+        assert_eq!(project.version, Some("0.9.0".to_string()));
+        assert_eq!(project.project_path, Some(PathBuf::from("/work/app")));
+        assert!(project.is_local);
+    }
+
+    #[test]
+    fn test_official_marketplace_source() {
+        let dir = TempDir::new().unwrap();
+        let plugins_dir = dir.path().join("plugins");
+        std::fs::create_dir_all(&plugins_dir).unwrap();
+        File::create(plugins_dir.join("installed_plugins.json"))
+            .unwrap()
+            .write_all(
+                br#"{"version": 2, "plugins": {
+                    "context7@claude-plugins-official": [{"version": "1.0.0"}],
+                    "formatter@team": [{"version": "1.0.0"}]
+                }}"#,
+            )
+            .unwrap();
+
+        let plugins = parse_plugins(dir.path()).unwrap();
+        let context7 = plugins.iter().find(|p| p.name == "context7").unwrap();
+        assert_eq!(context7.source, Source::Official);
+        let formatter = plugins.iter().find(|p| p.name == "formatter").unwrap();
+        assert_eq!(formatter.source, Source::ThirdParty);
+    }
+
+    #[test]
+    fn test_enabled_plugins_layers() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+        let plugins_dir = path.join("plugins");
+        std::fs::create_dir_all(&plugins_dir).unwrap();
+        File::create(plugins_dir.join("installed_plugins.json"))
+            .unwrap()
+            .write_all(
+                br#"{"version": 2, "plugins": {
+                    "context7@claude-plugins-official": [{"version": "1.0.0"}],
+                    "formatter@team": [{"version": "1.0.0"}],
+                    "linter@team": [{"version": "1.0.0"}]
+                }}"#,
+            )
+            .unwrap();
+        File::create(path.join("settings.json"))
+            .unwrap()
+            .write_all(
+                br#"{"enabledPlugins": {
+                    "context7@claude-plugins-official": true,
+                    "formatter@team": false,
+                    "linter@team": false
+                }}"#,
+            )
+            .unwrap();
+        // The local layer overrides the shared one
+        File::create(path.join("settings.local.json"))
+            .unwrap()
+            .write_all(br#"{"enabledPlugins": {"linter@team": true}}"#)
+            .unwrap();
+
+        let plugins = parse_plugins(path).unwrap();
+        let enabled = |name: &str| plugins.iter().find(|p| p.name == name).unwrap().enabled;
+        assert!(enabled("context7"));
+        assert!(!enabled("formatter"));
+        assert!(enabled("linter"));
+    }
+
+    #[test]
+    fn test_plugins_enabled_by_default() {
+        let dir = TempDir::new().unwrap();
+        File::create(dir.path().join("settings.json"))
+            .unwrap()
+            .write_all(br#"{"installed_plugins": [{"name": "context7"}]}"#)
+            .unwrap();
+
+        let plugins = parse_plugins(dir.path()).unwrap();
+        assert!(plugins[0].enabled);
+    }
+
     #[test]
     fn test_plugin_name_extraction_from_key() {
         // Test that plugin name is correctly extracted from "name@source" key
@@ -272,5 +748,6 @@ mod tests {
         let plugins = parse_plugins(path).unwrap();
         assert_eq!(plugins.len(), 1);
         assert_eq!(plugins[0].name, "my-awesome-plugin");
+        assert_eq!(plugins[0].marketplace, Some("some-source".to_string()));
     }
 }
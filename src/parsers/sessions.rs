@@ -1,31 +1,68 @@
-//! Parse session history from history.jsonl
-
-use crate::error::Result;
-use crate::info::SessionInfo;
+//! Parse session history from history.jsonl and the per-project session
+//! transcripts under projects/
+
+use crate::error::{Error, Result};
+use crate::info::{
+    ComponentKind, Diagnostic, ProjectSessions, SessionInfo, Timestamp, TokenUsage, UsageRecord,
+};
+use crate::parsers::invalid_json;
 use serde_json::Value;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 pub fn parse_sessions(base_path: &Path) -> Result<SessionInfo> {
+    parse_sessions_with_warnings(base_path, &mut Vec::new())
+}
+
+/// Parse session history, recording malformed files and lines in `warnings`
+pub fn parse_sessions_with_warnings(
+    base_path: &Path,
+    warnings: &mut Vec<Diagnostic>,
+) -> Result<SessionInfo> {
+    parse_sessions_with_invocations(base_path, warnings).map(|(sessions, _)| sessions)
+}
+
+/// Parse session history along with how often each slash command was
+/// entered, keyed by the name after the `/` (e.g. `review` or `plugin:cmd`)
+pub fn parse_sessions_with_invocations(
+    base_path: &Path,
+    warnings: &mut Vec<Diagnostic>,
+) -> Result<(SessionInfo, HashMap<String, usize>)> {
     // Try new format: history.jsonl (JSON Lines)
     let history_path = base_path.join("history.jsonl");
 
     if history_path.exists() {
-        return parse_sessions_from_jsonl(&history_path);
+        return parse_sessions_from_jsonl(&history_path, warnings);
     }
 
     // Fallback to old format: session_history.json
     let sessions_path = base_path.join("session_history.json");
 
     if !sessions_path.exists() {
-        return Ok(SessionInfo {
-            count: 0,
-            last_session: None,
-        });
+        return Ok((
+            SessionInfo {
+                count: 0,
+                last_session: None,
+            },
+            HashMap::new(),
+        ));
     }
 
     let content = fs::read_to_string(&sessions_path)?;
-    let json: Value = serde_json::from_str(&content)?;
+    let json: Value = match serde_json::from_str(&content) {
+        Ok(j) => j,
+        Err(e) => {
+            warnings.push(invalid_json(ComponentKind::Session, &sessions_path, e));
+            return Ok((
+                SessionInfo {
+                    count: 0,
+                    last_session: None,
+                },
+                HashMap::new(),
+            ));
+        }
+    };
 
     let sessions = json
         .get("sessions")
@@ -36,23 +73,33 @@ pub fn parse_sessions(base_path: &Path) -> Result<SessionInfo> {
     let last_session = json
         .get("sessions")
         .and_then(|v| v.as_array())
-        .and_then(|arr| arr.last())
-        .and_then(|s| s.get("id").or(s.get("timestamp")).or(s.get("time")))
-        .and_then(|v| v.as_str())
-        .map(String::from);
-
-    Ok(SessionInfo {
-        count: sessions,
-        last_session,
-    })
+        .and_then(|arr| {
+            arr.iter()
+                .filter_map(|s| s.get("timestamp").or(s.get("time")))
+                .filter_map(Timestamp::from_json)
+                .max()
+        });
+
+    Ok((
+        SessionInfo {
+            count: sessions,
+            last_session,
+        },
+        HashMap::new(),
+    ))
 }
 
 /// Parse history.jsonl (JSON Lines format)
-fn parse_sessions_from_jsonl(history_path: &Path) -> Result<SessionInfo> {
+fn parse_sessions_from_jsonl(
+    history_path: &Path,
+    warnings: &mut Vec<Diagnostic>,
+) -> Result<(SessionInfo, HashMap<String, usize>)> {
     let content = fs::read_to_string(history_path)?;
 
     let mut count = 0;
-    let mut last_timestamp: Option<String> = None;
+    let mut skipped = 0;
+    let mut last_timestamp: Option<Timestamp> = None;
+    let mut invocations = HashMap::new();
 
     for line in content.lines() {
         if line.trim().is_empty() {
@@ -62,28 +109,302 @@ fn parse_sessions_from_jsonl(history_path: &Path) -> Result<SessionInfo> {
         if let Ok(json) = serde_json::from_str::<Value>(line) {
             count += 1;
 
-            // Get timestamp (can be u64 or string)
-            if let Some(ts) = json.get("timestamp") {
-                let ts_str = match ts {
-                    Value::Number(n) => n.as_u64().map(|v| v.to_string()),
-                    Value::String(s) => Some(s.clone()),
-                    _ => None,
-                };
-                if let Some(ref ts_val) = ts_str {
-                    if last_timestamp.is_none() || ts_val > &last_timestamp.clone().unwrap() {
-                        last_timestamp = Some(ts_val.clone());
+            // "/review please" invokes the review command
+            if let Some(name) = json
+                .get("display")
+                .and_then(|v| v.as_str())
+                .and_then(|d| d.strip_prefix('/'))
+                .and_then(|d| d.split_whitespace().next())
+            {
+                *invocations.entry(name.to_string()).or_insert(0) += 1;
+            }
+
+            // Timestamps are epoch milliseconds or RFC 3339 strings
+            if let Some(ts) = json.get("timestamp").and_then(Timestamp::from_json) {
+                last_timestamp = last_timestamp.max(Some(ts));
+            }
+        } else {
+            skipped += 1;
+        }
+    }
+
+    if skipped > 0 {
+        warnings.push(Diagnostic::warning(
+            ComponentKind::Session,
+            history_path,
+            Error::InvalidComponent(
+                history_path.to_path_buf(),
+                format!("{} malformed line(s) skipped", skipped),
+            ),
+        ));
+    }
+
+    Ok((
+        SessionInfo {
+            count,
+            last_session: last_timestamp,
+        },
+        invocations,
+    ))
+}
+
+/// Summarise the session transcripts in `projects/<encoded-path>/*.jsonl`,
+/// one entry per project, most recently active first
+pub fn parse_project_sessions(
+    base_path: &Path,
+    warnings: &mut Vec<Diagnostic>,
+) -> Vec<ProjectSessions> {
+    let mut projects: Vec<ProjectSessions> = project_dirs(base_path)
+        .iter()
+        .filter_map(|dir| summarize_project(dir, warnings))
+        .collect();
+
+    projects.sort_by(|a, b| {
+        b.last_activity
+            .cmp(&a.last_activity)
+            .then_with(|| a.project.cmp(&b.project))
+    });
+    projects
+}
+
+/// Aggregate every session transcript of one project folder. Subagent
+/// transcripts (`agent-*.jsonl`) belong to their parent session and are
+/// not counted.
+fn summarize_project(dir: &Path, warnings: &mut Vec<Diagnostic>) -> Option<ProjectSessions> {
+    let mut summary = ProjectSessions {
+        project: decode_project_dir(dir)?,
+        path: dir.to_path_buf(),
+        sessions: 0,
+        messages: 0,
+        first_activity: None,
+        last_activity: None,
+        duration_secs: 0,
+    };
+    let mut cwd: Option<String> = None;
+
+    for transcript in transcript_files(dir, false) {
+        let content = match fs::read_to_string(&transcript) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+
+        let mut messages = 0;
+        let mut skipped = 0;
+        let mut first: Option<Timestamp> = None;
+        let mut last: Option<Timestamp> = None;
+
+        for line in content.lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let json: Value = match serde_json::from_str(line) {
+                Ok(j) => j,
+                Err(_) => {
+                    skipped += 1;
+                    continue;
+                }
+            };
+
+            if cwd.is_none() {
+                cwd = json.get("cwd").and_then(|v| v.as_str()).map(String::from);
+            }
+            if matches!(
+                json.get("type").and_then(|v| v.as_str()),
+                Some("user") | Some("assistant")
+            ) {
+                messages += 1;
+            }
+
+            if let Some(ts) = json.get("timestamp").and_then(Timestamp::from_json) {
+                first = Some(first.map_or(ts, |f| f.min(ts)));
+                last = last.max(Some(ts));
+            }
+        }
+
+        if skipped > 0 {
+            warnings.push(skipped_lines(&transcript, skipped));
+        }
+
+        // Transcripts with neither messages nor timestamps hold no session
+        if messages == 0 && first.is_none() {
+            continue;
+        }
+        summary.sessions += 1;
+        summary.messages += messages;
+
+        if let (Some(first), Some(last)) = (first, last) {
+            summary.duration_secs +=
+                (last.as_millis().saturating_sub(first.as_millis()) / 1000) as u64;
+            summary.first_activity = Some(summary.first_activity.map_or(first, |f| f.min(first)));
+            summary.last_activity = summary.last_activity.max(Some(last));
+        }
+    }
+
+    if summary.sessions == 0 {
+        return None;
+    }
+    if let Some(cwd) = cwd {
+        summary.project = cwd;
+    }
+    Some(summary)
+}
+
+/// Token usage of every assistant message in the transcripts under
+/// `projects/`, subagent transcripts included. Streamed responses repeat the
+/// same message on several lines; each message is counted once.
+pub fn parse_usage_records(base_path: &Path, warnings: &mut Vec<Diagnostic>) -> Vec<UsageRecord> {
+    let mut records = Vec::new();
+
+    for dir in project_dirs(base_path) {
+        let mut project = match decode_project_dir(&dir) {
+            Some(p) => p,
+            None => continue,
+        };
+        let mut cwd: Option<String> = None;
+        let mut seen = HashSet::new();
+        let first_record = records.len();
+
+        for transcript in transcript_files(&dir, true) {
+            let content = match fs::read_to_string(&transcript) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+
+            let mut skipped = 0;
+            for line in content.lines() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let json: Value = match serde_json::from_str(line) {
+                    Ok(j) => j,
+                    Err(_) => {
+                        skipped += 1;
+                        continue;
                     }
+                };
+
+                if cwd.is_none() {
+                    cwd = json.get("cwd").and_then(|v| v.as_str()).map(String::from);
+                }
+                if let Some(record) = usage_record(&json, &mut seen) {
+                    records.push(record);
                 }
             }
+
+            if skipped > 0 {
+                warnings.push(skipped_lines(&transcript, skipped));
+            }
+        }
+
+        if let Some(cwd) = cwd {
+            project = cwd;
+        }
+        for record in &mut records[first_record..] {
+            record.project = project.clone();
+        }
+    }
+
+    records
+}
+
+/// The usage block of an assistant message, unless it was already counted
+/// or reports no tokens at all
+fn usage_record(json: &Value, seen: &mut HashSet<String>) -> Option<UsageRecord> {
+    if json.get("type").and_then(|v| v.as_str()) != Some("assistant") {
+        return None;
+    }
+    let message = json.get("message")?;
+    let usage = message.get("usage")?;
+
+    let message_id = message.get("id").and_then(|v| v.as_str());
+    let request_id = json.get("requestId").and_then(|v| v.as_str());
+    if message_id.is_some() || request_id.is_some() {
+        let key = format!(
+            "{}:{}",
+            message_id.unwrap_or_default(),
+            request_id.unwrap_or_default()
+        );
+        if !seen.insert(key) {
+            return None;
         }
     }
 
-    Ok(SessionInfo {
-        count,
-        last_session: last_timestamp,
+    let tokens = |key: &str| usage.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+    let usage = TokenUsage {
+        input_tokens: tokens("input_tokens"),
+        output_tokens: tokens("output_tokens"),
+        cache_creation_tokens: tokens("cache_creation_input_tokens"),
+        cache_read_tokens: tokens("cache_read_input_tokens"),
+    };
+    if usage.total() == 0 {
+        return None;
+    }
+
+    Some(UsageRecord {
+        project: String::new(),
+        model: message
+            .get("model")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown")
+            .to_string(),
+        timestamp: json.get("timestamp").and_then(Timestamp::from_json),
+        usage,
     })
 }
 
+/// Per-project transcript folders under `projects/`
+fn project_dirs(base_path: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = match fs::read_dir(base_path.join("projects")) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .collect(),
+        Err(_) => vec![],
+    };
+    dirs.sort();
+    dirs
+}
+
+/// The `.jsonl` transcripts of a project folder, optionally including the
+/// subagent transcripts (`agent-*.jsonl`)
+fn transcript_files(dir: &Path, include_subagents: bool) -> Vec<PathBuf> {
+    let mut transcripts: Vec<PathBuf> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| {
+                p.extension().and_then(|e| e.to_str()) == Some("jsonl")
+                    && (include_subagents
+                        || !p
+                            .file_stem()
+                            .and_then(|s| s.to_str())
+                            .is_some_and(|s| s.starts_with("agent-")))
+            })
+            .collect(),
+        Err(_) => vec![],
+    };
+    transcripts.sort();
+    transcripts
+}
+
+/// Folder names encode the project path with every '/' turned into '-'. The
+/// sessions' `cwd` is preferred when available since the encoding is lossy.
+fn decode_project_dir(dir: &Path) -> Option<String> {
+    Some(dir.file_name()?.to_string_lossy().replace('-', "/"))
+}
+
+fn skipped_lines(path: &Path, skipped: usize) -> Diagnostic {
+    Diagnostic::warning(
+        ComponentKind::Session,
+        path,
+        Error::InvalidComponent(
+            path.to_path_buf(),
+            format!("{} malformed line(s) skipped", skipped),
+        ),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -133,7 +454,55 @@ mod tests {
         let result = parse_sessions(path).unwrap();
         assert_eq!(result.count, 3);
         // Last timestamp should be 1766567624402
-        assert_eq!(result.last_session, Some("1766567624402".to_string()));
+        assert_eq!(result.last_session, Some(Timestamp(1_766_567_624_402)));
+    }
+
+    #[test]
+    fn test_last_session_compares_mixed_formats() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+
+        // As strings "999999999999" > "1766567624402" and
+        // "2025-..." > "1766..."; as times the ISO entry is the latest
+        let jsonl_content = r#"{"display":"/help","timestamp":1766567624402}
+{"display":"/help","timestamp":999999999999}
+{"display":"/help","timestamp":"2025-12-24T10:00:00Z"}
+{"display":"/help","timestamp":"2025-12-24T08:00:00+05:00"}
+"#;
+        File::create(path.join("history.jsonl"))
+            .unwrap()
+            .write_all(jsonl_content.as_bytes())
+            .unwrap();
+
+        let result = parse_sessions(path).unwrap();
+        assert_eq!(
+            result.last_session,
+            Timestamp::parse("2025-12-24T10:00:00Z")
+        );
+    }
+
+    #[test]
+    fn test_slash_command_invocations() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+
+        let jsonl_content = r#"{"display":"/review the parser","timestamp":1}
+{"display":"/review","timestamp":2}
+{"display":"/toolkit:lint src","timestamp":3}
+{"display":"plain prompt mentioning /review","timestamp":4}
+{"display":"/","timestamp":5}
+"#;
+        File::create(path.join("history.jsonl"))
+            .unwrap()
+            .write_all(jsonl_content.as_bytes())
+            .unwrap();
+
+        let (sessions, invocations) =
+            parse_sessions_with_invocations(path, &mut Vec::new()).unwrap();
+        assert_eq!(sessions.count, 5);
+        assert_eq!(invocations.get("review"), Some(&2));
+        assert_eq!(invocations.get("toolkit:lint"), Some(&1));
+        assert_eq!(invocations.len(), 2);
     }
 
     #[test]
@@ -159,9 +528,30 @@ invalid json
             .write_all(jsonl_content.as_bytes())
             .unwrap();
 
-        let result = parse_sessions(path).unwrap();
+        let mut warnings = Vec::new();
+        let result = parse_sessions_with_warnings(path, &mut warnings).unwrap();
         // Should count valid lines only
         assert_eq!(result.count, 2);
+        // ...and report the skipped one
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("1 malformed line(s) skipped"));
+    }
+
+    #[test]
+    fn test_malformed_session_history_json_is_a_warning() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+
+        File::create(path.join("session_history.json"))
+            .unwrap()
+            .write_all(b"{ not json")
+            .unwrap();
+
+        let mut warnings = Vec::new();
+        let result = parse_sessions_with_warnings(path, &mut warnings).unwrap();
+        assert_eq!(result.count, 0);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].component, ComponentKind::Session);
     }
 
     #[test]
@@ -194,7 +584,7 @@ invalid json
         assert_eq!(result.count, 1);
         assert_eq!(
             result.last_session,
-            Some("2025-01-15T10:30:00Z".to_string())
+            Timestamp::parse("2025-01-15T10:30:00Z")
         );
     }
 
@@ -218,4 +608,153 @@ invalid json
         let result = parse_sessions(path).unwrap();
         assert_eq!(result.count, 2);
     }
+
+    fn write_transcript(dir: &Path, name: &str, lines: &[&str]) {
+        std::fs::create_dir_all(dir).unwrap();
+        File::create(dir.join(name))
+            .unwrap()
+            .write_all(lines.join("\n").as_bytes())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_parse_project_sessions() {
+        let dir = TempDir::new().unwrap();
+        let project_dir = dir.path().join("projects/-work-app");
+
+        write_transcript(
+            &project_dir,
+            "a1.jsonl",
+            &[
+                r#"{"type":"summary","summary":"Fix bug"}"#,
+                r#"{"type":"user","cwd":"/work/app","timestamp":"2026-01-14T10:00:00.000Z"}"#,
+                r#"{"type":"assistant","cwd":"/work/app","timestamp":"2026-01-14T10:30:00.000Z"}"#,
+            ],
+        );
+        write_transcript(
+            &project_dir,
+            "b2.jsonl",
+            &[
+                r#"{"type":"user","cwd":"/work/app","timestamp":"2026-01-15T09:00:00.000Z"}"#,
+                r#"{"type":"assistant","cwd":"/work/app","timestamp":"2026-01-15T09:10:00.000Z"}"#,
+                r#"{"type":"user","cwd":"/work/app","timestamp":"2026-01-15T09:20:00.000Z"}"#,
+            ],
+        );
+        // Subagent transcripts are part of their parent session
+        write_transcript(
+            &project_dir,
+            "agent-1234.jsonl",
+            &[r#"{"type":"user","timestamp":"2026-01-16T00:00:00.000Z"}"#],
+        );
+
+        let mut warnings = Vec::new();
+        let projects = parse_project_sessions(dir.path(), &mut warnings);
+        assert!(warnings.is_empty());
+        assert_eq!(projects.len(), 1);
+
+        let project = &projects[0];
+        assert_eq!(project.project, "/work/app");
+        assert_eq!(project.sessions, 2);
+        assert_eq!(project.messages, 5);
+        assert_eq!(
+            project.first_activity,
+            Timestamp::parse("2026-01-14T10:00:00.000Z")
+        );
+        assert_eq!(
+            project.last_activity,
+            Timestamp::parse("2026-01-15T09:20:00.000Z")
+        );
+        assert_eq!(project.duration_secs, 50 * 60);
+    }
+
+    #[test]
+    fn test_project_sessions_sorted_and_decoded() {
+        let dir = TempDir::new().unwrap();
+        write_transcript(
+            &dir.path().join("projects/-old-project"),
+            "s.jsonl",
+            &[r#"{"type":"user","timestamp":"2025-06-01T00:00:00Z"}"#],
+        );
+        write_transcript(
+            &dir.path().join("projects/-new-project"),
+            "s.jsonl",
+            &[
+                r#"{"type":"user","timestamp":"2026-06-01T00:00:00Z"}"#,
+                "not json",
+            ],
+        );
+
+        let mut warnings = Vec::new();
+        let projects = parse_project_sessions(dir.path(), &mut warnings);
+        assert_eq!(projects.len(), 2);
+        // No cwd recorded, so the folder name is decoded
+        assert_eq!(projects[0].project, "/new/project");
+        assert_eq!(projects[1].project, "/old/project");
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("1 malformed line(s) skipped"));
+    }
+
+    #[test]
+    fn test_parse_usage_records() {
+        let dir = TempDir::new().unwrap();
+        let project_dir = dir.path().join("projects/-work-app");
+        let usage = r#""usage":{"input_tokens":10,"output_tokens":20,"cache_creation_input_tokens":30,"cache_read_input_tokens":40}"#;
+
+        write_transcript(
+            &project_dir,
+            "s1.jsonl",
+            &[
+                r#"{"type":"user","cwd":"/work/app","timestamp":"2026-01-14T10:00:00.000Z"}"#,
+                // A streamed response is written once per content block
+                &format!(
+                    r#"{{"type":"assistant","requestId":"req_1","timestamp":"2026-01-14T10:00:05.000Z","message":{{"id":"msg_1","model":"claude-sonnet-4-5",{}}}}}"#,
+                    usage
+                ),
+                &format!(
+                    r#"{{"type":"assistant","requestId":"req_1","timestamp":"2026-01-14T10:00:06.000Z","message":{{"id":"msg_1","model":"claude-sonnet-4-5",{}}}}}"#,
+                    usage
+                ),
+                // Synthetic messages report no tokens
+                r#"{"type":"assistant","message":{"id":"msg_2","model":"<synthetic>","usage":{"input_tokens":0,"output_tokens":0}}}"#,
+            ],
+        );
+        // Subagent work is billed too; its timestamp is in epoch seconds
+        write_transcript(
+            &project_dir,
+            "agent-1.jsonl",
+            &[&format!(
+                r#"{{"type":"assistant","requestId":"req_2","timestamp":1768464000,"message":{{"id":"msg_3","model":"claude-haiku-4-5",{}}}}}"#,
+                usage
+            )],
+        );
+
+        let records = parse_usage_records(dir.path(), &mut Vec::new());
+        assert_eq!(records.len(), 2);
+        assert!(records.iter().all(|r| r.project == "/work/app"));
+        assert_eq!(records[0].model, "claude-haiku-4-5");
+        assert_eq!(records[1].model, "claude-sonnet-4-5");
+        assert_eq!(
+            records[1].usage,
+            TokenUsage {
+                input_tokens: 10,
+                output_tokens: 20,
+                cache_creation_tokens: 30,
+                cache_read_tokens: 40,
+            }
+        );
+        assert_eq!(
+            records[1].timestamp,
+            Timestamp::parse("2026-01-14T10:00:05.000Z")
+        );
+        assert_eq!(
+            records[0].timestamp,
+            Timestamp::parse("2026-01-15T08:00:00.000Z")
+        );
+    }
+
+    #[test]
+    fn test_no_projects_dir() {
+        let dir = TempDir::new().unwrap();
+        assert!(parse_project_sessions(dir.path(), &mut Vec::new()).is_empty());
+    }
 }
@@ -0,0 +1,195 @@
+//! Parse permissions, env keys and other options from settings files
+
+use crate::info::{
+    ComponentKind, Diagnostic, PermissionKind, PermissionRule, SettingValue, SettingsInfo,
+    SettingsScope,
+};
+use crate::parsers::invalid_json;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Single-valued keys listed in the inventory
+const KEYS: [&str; 4] = ["model", "outputStyle", "statusLine", "apiKeyHelper"];
+
+const PERMISSION_KINDS: [(&str, PermissionKind); 3] = [
+    ("allow", PermissionKind::Allow),
+    ("deny", PermissionKind::Deny),
+    ("ask", PermissionKind::Ask),
+];
+
+/// Where an administrator installs `managed-settings.json` on this platform
+pub fn managed_settings_path() -> PathBuf {
+    if cfg!(target_os = "macos") {
+        PathBuf::from("/Library/Application Support/ClaudeCode/managed-settings.json")
+    } else if cfg!(windows) {
+        PathBuf::from(r"C:\ProgramData\ClaudeCode\managed-settings.json")
+    } else {
+        PathBuf::from("/etc/claude-code/managed-settings.json")
+    }
+}
+
+pub fn parse_settings(base_path: &Path) -> SettingsInfo {
+    let mut settings = SettingsInfo::default();
+    parse_settings_file(
+        &base_path.join("settings.json"),
+        SettingsScope::User,
+        &mut settings,
+        &mut Vec::new(),
+    );
+    settings
+}
+
+/// Add the keys and permission rules of one settings file, tagged with its
+/// scope. A missing file is skipped; malformed JSON is recorded in
+/// `warnings`.
+pub fn parse_settings_file(
+    path: &Path,
+    scope: SettingsScope,
+    settings: &mut SettingsInfo,
+    warnings: &mut Vec<Diagnostic>,
+) {
+    let content = match fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(_) => return,
+    };
+    let json: Value = match serde_json::from_str(&content) {
+        Ok(j) => j,
+        Err(e) => {
+            warnings.push(invalid_json(ComponentKind::Settings, path, e));
+            return;
+        }
+    };
+
+    let value = |key: String, value: Option<String>| SettingValue {
+        key,
+        value,
+        scope,
+        path: path.to_path_buf(),
+        shadowed_by: None,
+    };
+
+    for key in KEYS {
+        if let Some(v) = json.get(key).filter(|v| !v.is_null()) {
+            settings
+                .values
+                .push(value(key.to_string(), Some(display_value(v))));
+        }
+    }
+
+    if let Some(env) = json.get("env").and_then(|v| v.as_object()) {
+        for key in env.keys() {
+            settings.values.push(value(format!("env.{}", key), None));
+        }
+    }
+
+    let permissions = json.get("permissions");
+    for (list, kind) in PERMISSION_KINDS {
+        let rules = permissions
+            .and_then(|p| p.get(list))
+            .and_then(|v| v.as_array());
+        for rule in rules.into_iter().flatten().filter_map(|r| r.as_str()) {
+            settings.permissions.push(PermissionRule {
+                kind,
+                rule: rule.to_string(),
+                scope,
+                path: path.to_path_buf(),
+            });
+        }
+    }
+}
+
+/// Strings as they are; a `statusLine` object by its command; anything
+/// else as compact JSON
+fn display_value(value: &Value) -> String {
+    if let Some(s) = value.as_str() {
+        return s.to_string();
+    }
+    if let Some(command) = value.get("command").and_then(|c| c.as_str()) {
+        return command.to_string();
+    }
+    value.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_settings_keys_and_permissions() {
+        let dir = TempDir::new().unwrap();
+        let settings = r#"{
+            "model": "opus",
+            "outputStyle": "Explanatory",
+            "apiKeyHelper": "~/bin/get-key.sh",
+            "statusLine": {"type": "command", "command": "~/.claude/statusline.sh"},
+            "env": {"DEBUG": "1", "ANTHROPIC_API_KEY": "sk-secret"},
+            "permissions": {
+                "allow": ["Bash(npm run test:*)", "Read"],
+                "deny": ["Read(./.env)"],
+                "ask": ["Bash(git push:*)"],
+                "defaultMode": "acceptEdits"
+            },
+            "installed_plugins": []
+        }"#;
+        std::fs::write(dir.path().join("settings.json"), settings).unwrap();
+
+        let settings = parse_settings(dir.path());
+        let values: Vec<(&str, Option<&str>)> = settings
+            .values
+            .iter()
+            .map(|v| (v.key.as_str(), v.value.as_deref()))
+            .collect();
+        assert_eq!(
+            values,
+            [
+                ("model", Some("opus")),
+                ("outputStyle", Some("Explanatory")),
+                ("statusLine", Some("~/.claude/statusline.sh")),
+                ("apiKeyHelper", Some("~/bin/get-key.sh")),
+                ("env.ANTHROPIC_API_KEY", None),
+                ("env.DEBUG", None),
+            ]
+        );
+        assert!(settings
+            .values
+            .iter()
+            .all(|v| v.scope == SettingsScope::User));
+
+        let rules: Vec<(PermissionKind, &str)> = settings
+            .permissions
+            .iter()
+            .map(|p| (p.kind, p.rule.as_str()))
+            .collect();
+        assert_eq!(
+            rules,
+            [
+                (PermissionKind::Allow, "Bash(npm run test:*)"),
+                (PermissionKind::Allow, "Read"),
+                (PermissionKind::Deny, "Read(./.env)"),
+                (PermissionKind::Ask, "Bash(git push:*)"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_settings_file() {
+        let dir = TempDir::new().unwrap();
+        assert!(parse_settings(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn test_malformed_settings_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("settings.local.json");
+        std::fs::write(&path, "{ not json").unwrap();
+
+        let mut settings = SettingsInfo::default();
+        let mut warnings = Vec::new();
+        parse_settings_file(&path, SettingsScope::Local, &mut settings, &mut warnings);
+        assert!(settings.is_empty());
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].component, ComponentKind::Settings);
+    }
+}
@@ -1,152 +1,276 @@
 //! Parse installed skills from skills/ directory and plugin skills directories
 
-use crate::error::Result;
-use crate::info::{SkillInfo, SkillLocation, Source};
-use serde_json::Value;
+use crate::error::{Error, Result};
+use crate::info::{
+    ComponentKind, Diagnostic, ResourceKind, SkillInfo, SkillLocation, SkillResource, Source,
+};
+use crate::parsers::frontmatter::{self, StringList};
+use crate::parsers::plugins::plugin_roots;
+use crate::parsers::SKIPPED_DIRS;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
 use serde_yaml::Value as YamlValue;
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// Files that describe a skill rather than ship with it
+const MANIFESTS: [&str; 2] = ["SKILL.md", "skill.yaml"];
 
 /// Scan a single skills directory and return parsed skills
-fn scan_skills_dir(skills_path: &Path, location: SkillLocation, skills: &mut Vec<SkillInfo>) {
-    if !skills_path.exists() || !skills_path.is_dir() {
-        return;
+fn scan_skills_dir(
+    skills_path: &Path,
+    location: SkillLocation,
+    skills: &mut Vec<SkillInfo>,
+    warnings: &mut Vec<Diagnostic>,
+) {
+    for skill_path in skill_folders(skills_path) {
+        skills.push(parse_skill(skill_path, &location, warnings));
     }
+}
 
-    for entry in match fs::read_dir(skills_path) {
-        Ok(entries) => entries,
-        Err(_) => return,
-    } {
-        let entry = match entry {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
-        let skill_path = entry.path();
+/// Skill folders under a skills directory. Folders without a SKILL.md or
+/// skill.yaml that hold skills further down (e.g.
+/// `skills/writing/blog-post/SKILL.md`) are searched recursively; symlinked
+/// folders are followed, each real directory at most once.
+pub fn skill_folders(skills_path: &Path) -> Vec<PathBuf> {
+    let mut folders = Vec::new();
+    if !skills_path.is_dir() {
+        return folders;
+    }
+
+    let mut visited = HashSet::new();
+    if let Ok(root) = skills_path.canonicalize() {
+        visited.insert(root);
+    }
+    find_skill_folders(skills_path, true, &mut folders, &mut visited);
+    folders
+}
 
-        if !skill_path.is_dir() {
+fn find_skill_folders(
+    dir: &Path,
+    top_level: bool,
+    folders: &mut Vec<PathBuf>,
+    visited: &mut HashSet<PathBuf>,
+) {
+    for skill_path in sorted_subdirs(dir, visited) {
+        if MANIFESTS.iter().any(|m| skill_path.join(m).is_file()) {
+            folders.push(skill_path);
             continue;
         }
 
-        let name = skill_path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .map(String::from)
-            .unwrap_or_default();
-
-        // Try SKILL.md with frontmatter first (new format)
-        let skill_md_path = skill_path.join("SKILL.md");
-        let mut version = None;
-        let mut description = None;
-        let mut used_skill_md = false;
-
-        if skill_md_path.exists() {
-            used_skill_md = true;
-            if let Ok(content) = fs::read_to_string(&skill_md_path) {
-                if content.starts_with("---") {
-                    // Parse frontmatter
-                    if let Some(frontmatter) = content.trim_start_matches("---").split("---").next()
-                    {
-                        if let Ok(yaml) = serde_yaml::from_str::<YamlValue>(frontmatter) {
-                            description = yaml
-                                .get("description")
-                                .and_then(|v| v.as_str())
-                                .map(String::from);
-                            version = yaml
-                                .get("version")
-                                .and_then(|v| v.as_str())
-                                .map(String::from);
-                        }
-                    }
-                }
-            }
+        let found = folders.len();
+        find_skill_folders(&skill_path, false, folders, visited);
+        // A top-level folder is a skill even without a manifest, unless it
+        // only groups other skills
+        if top_level && folders.len() == found {
+            folders.push(skill_path);
         }
+    }
+}
 
-        // Fallback to skill.yaml (old format) only if SKILL.md doesn't exist
-        if !used_skill_md {
-            let yaml_path = skill_path.join("skill.yaml");
-            if yaml_path.exists() {
-                if let Ok(content) = fs::read_to_string(&yaml_path) {
-                    if let Ok(yaml) = serde_yaml::from_str::<YamlValue>(&content) {
-                        version = yaml
-                            .get("version")
-                            .and_then(|v| v.as_str())
-                            .map(String::from);
-                        description = yaml
-                            .get("description")
-                            .and_then(|v| v.as_str())
-                            .map(String::from);
-                    }
+/// Subdirectories of `dir` (symlinks resolved) not seen before, by name
+fn sorted_subdirs(dir: &Path, visited: &mut HashSet<PathBuf>) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .collect(),
+        Err(_) => return vec![],
+    };
+    dirs.sort();
+    dirs.retain(|p| p.canonicalize().is_ok_and(|real| visited.insert(real)));
+    dirs
+}
+
+/// Read one skill folder: its SKILL.md (or legacy skill.yaml) and the files
+/// it bundles
+fn parse_skill(
+    skill_path: PathBuf,
+    location: &SkillLocation,
+    warnings: &mut Vec<Diagnostic>,
+) -> SkillInfo {
+    let name = skill_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .map(String::from)
+        .unwrap_or_default();
+
+    // Try SKILL.md with frontmatter first (new format)
+    let skill_md_path = skill_path.join("SKILL.md");
+    let mut manifest = SkillManifest::default();
+    let mut used_skill_md = false;
+
+    if skill_md_path.exists() {
+        used_skill_md = true;
+        if let Ok(content) = fs::read_to_string(&skill_md_path) {
+            manifest = frontmatter::read(&skill_md_path, &content, ComponentKind::Skill, warnings);
+        }
+    }
+
+    // Fallback to skill.yaml (old format) only if SKILL.md doesn't exist
+    if !used_skill_md {
+        let yaml_path = skill_path.join("skill.yaml");
+        if yaml_path.exists() {
+            if let Ok(content) = fs::read_to_string(&yaml_path) {
+                match serde_yaml::from_str::<YamlValue>(&content) {
+                    Ok(yaml) => manifest = SkillManifest::from_yaml(&yaml),
+                    Err(_) => warnings.push(Diagnostic::warning(
+                        ComponentKind::Skill,
+                        &yaml_path,
+                        Error::InvalidComponent(yaml_path.clone(), "invalid YAML".to_string()),
+                    )),
                 }
             }
         }
+    }
 
-        skills.push(SkillInfo {
-            name,
-            version,
-            source: Source::Official,
-            path: skill_path,
-            description,
-            location_type: location.clone(),
-        });
+    let mut resources = Vec::new();
+    let mut visited = HashSet::new();
+    if let Ok(root) = skill_path.canonicalize() {
+        visited.insert(root);
+    }
+    collect_resources(&skill_path, Path::new(""), &mut resources, &mut visited);
+
+    SkillInfo {
+        name,
+        version: manifest.version,
+        source: Source::Official,
+        path: skill_path,
+        description: manifest.description,
+        declared_name: manifest.name,
+        allowed_tools: manifest.allowed_tools,
+        license: manifest.license,
+        metadata: manifest.metadata,
+        location_type: location.clone(),
+        resources,
+        uses: 0,
+        shadowed_by: None,
     }
 }
 
-/// Plugin info extracted from installed_plugins.json
-struct PluginInfo {
-    install_path: std::path::PathBuf,
-    plugin_name: String,
+/// Fields read from SKILL.md frontmatter or skill.yaml. Each is taken on its
+/// own, so one field of an unexpected type doesn't lose the others.
+#[derive(Debug, Default)]
+struct SkillManifest {
+    name: Option<String>,
+    description: Option<String>,
+    version: Option<String>,
+    allowed_tools: Vec<String>,
+    license: Option<String>,
+    metadata: BTreeMap<String, JsonValue>,
 }
 
-/// Get plugin install paths from installed_plugins.json
-fn get_plugins(base_path: &Path) -> Vec<PluginInfo> {
-    let installed_path = base_path.join("plugins").join("installed_plugins.json");
-    if !installed_path.exists() {
-        return vec![];
+impl<'de> Deserialize<'de> for SkillManifest {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        YamlValue::deserialize(deserializer).map(|yaml| SkillManifest::from_yaml(&yaml))
     }
+}
 
-    let content = match fs::read_to_string(&installed_path) {
-        Ok(c) => c,
-        Err(_) => return vec![],
-    };
+impl SkillManifest {
+    fn from_yaml(yaml: &YamlValue) -> Self {
+        let string = |key: &str| yaml.get(key).and_then(|v| v.as_str()).map(String::from);
+        SkillManifest {
+            name: string("name"),
+            description: string("description"),
+            version: string("version"),
+            allowed_tools: yaml
+                .get("allowed-tools")
+                .and_then(|v| serde_yaml::from_value::<StringList>(v.clone()).ok())
+                .map(StringList::items)
+                .unwrap_or_default(),
+            license: string("license"),
+            metadata: yaml
+                .get("metadata")
+                .and_then(|v| serde_json::to_value(v).ok())
+                .and_then(|v| serde_json::from_value(v).ok())
+                .unwrap_or_default(),
+        }
+    }
+}
 
-    let json: Value = match serde_json::from_str(&content) {
-        Ok(j) => j,
-        Err(_) => return vec![],
+/// Every file bundled in a skill folder except its manifest, with paths
+/// relative to the folder. Hidden files and dependency or build folders are
+/// skipped and symlinked directories followed once.
+fn collect_resources(
+    dir: &Path,
+    relative: &Path,
+    resources: &mut Vec<SkillResource>,
+    visited: &mut HashSet<PathBuf>,
+) {
+    let mut entries: Vec<PathBuf> = match fs::read_dir(dir) {
+        Ok(entries) => entries.filter_map(|e| e.ok()).map(|e| e.path()).collect(),
+        Err(_) => return,
     };
+    entries.sort();
 
-    let mut plugins = Vec::new();
-
-    if let Some(plugins_obj) = json.get("plugins").and_then(|v| v.as_object()) {
-        for (full_name, plugin_array) in plugins_obj {
-            // Parse "name@source" format to extract plugin name
-            let plugin_name = full_name.split('@').next().unwrap_or(full_name).to_string();
-
-            if let Some(arr) = plugin_array.as_array() {
-                if let Some(first) = arr.first() {
-                    if let Some(install_path) = first.get("installPath").and_then(|v| v.as_str()) {
-                        plugins.push(PluginInfo {
-                            install_path: std::path::PathBuf::from(install_path),
-                            plugin_name: plugin_name.clone(),
-                        });
-                    }
-                }
+    for path in entries {
+        let file_name = match path.file_name().and_then(|n| n.to_str()) {
+            Some(name) if !name.starts_with('.') => name,
+            _ => continue,
+        };
+        let relative_path = relative.join(file_name);
+
+        if path.is_dir() {
+            if SKIPPED_DIRS.contains(&file_name) {
+                continue;
+            }
+            if path.canonicalize().is_ok_and(|real| visited.insert(real)) {
+                collect_resources(&path, &relative_path, resources, visited);
+            }
+        } else if let Ok(metadata) = fs::metadata(&path) {
+            if relative.as_os_str().is_empty() && MANIFESTS.contains(&file_name) {
+                continue;
             }
+            resources.push(SkillResource {
+                kind: ResourceKind::classify(&relative_path),
+                path: relative_path,
+                size: metadata.len(),
+            });
         }
     }
+}
 
-    plugins
+/// Parse skills from a project's `.claude/skills/` directory
+pub fn parse_project_skills(
+    project_claude_dir: &Path,
+    warnings: &mut Vec<Diagnostic>,
+) -> Vec<SkillInfo> {
+    let mut skills = Vec::new();
+    scan_skills_dir(
+        &project_claude_dir.join("skills"),
+        SkillLocation::Project,
+        &mut skills,
+        warnings,
+    );
+    skills
 }
 
 pub fn parse_skills(base_path: &Path) -> Result<Vec<SkillInfo>> {
+    parse_skills_with_warnings(base_path, &mut Vec::new())
+}
+
+/// Parse skills, recording unreadable SKILL.md/skill.yaml files in `warnings`
+pub fn parse_skills_with_warnings(
+    base_path: &Path,
+    warnings: &mut Vec<Diagnostic>,
+) -> Result<Vec<SkillInfo>> {
     let mut skills = Vec::new();
 
     // Scan global skills directory
     let global_skills_dir = base_path.join("skills");
-    scan_skills_dir(&global_skills_dir, SkillLocation::Global, &mut skills);
+    scan_skills_dir(
+        &global_skills_dir,
+        SkillLocation::Global,
+        &mut skills,
+        warnings,
+    );
 
     // Scan skills from installed plugins
-    let plugins = get_plugins(base_path);
-    for plugin in plugins {
+    for plugin in plugin_roots(base_path) {
         let plugin_skills_dir = plugin.install_path.join("skills");
         scan_skills_dir(
             &plugin_skills_dir,
@@ -154,6 +278,7 @@ pub fn parse_skills(base_path: &Path) -> Result<Vec<SkillInfo>> {
                 plugin_name: Some(plugin.plugin_name),
             },
             &mut skills,
+            warnings,
         );
     }
 
@@ -350,10 +475,182 @@ description: A custom tool for special tasks
             .unwrap();
 
         // Should gracefully degrade
-        let skills = parse_skills(path).unwrap();
+        let mut warnings = Vec::new();
+        let skills = parse_skills_with_warnings(path, &mut warnings).unwrap();
         // Skill should still be parsed with default values
         assert_eq!(skills.len(), 1);
         assert_eq!(skills[0].name, "test-skill");
+        // ...and the bad frontmatter reported
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("Malformed frontmatter"));
+        assert!(warnings[0].message.contains("SKILL.md:2:"));
+    }
+
+    #[test]
+    fn test_skill_md_invalid_yaml_read_line_by_line() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+
+        create_dir_all(path.join("skills/review")).unwrap();
+        std::fs::write(
+            path.join("skills/review/SKILL.md"),
+            "---\nname: review\ndescription: Use when: reviewing\nallowed-tools:\n  - Read\n  - Grep\nlicense: MIT\n---\n",
+        )
+        .unwrap();
+
+        let mut warnings = Vec::new();
+        let skills = parse_skills_with_warnings(path, &mut warnings).unwrap();
+        assert_eq!(skills[0].declared_name, Some("review".to_string()));
+        assert_eq!(
+            skills[0].description,
+            Some("Use when: reviewing".to_string())
+        );
+        assert_eq!(skills[0].allowed_tools, ["Read", "Grep"]);
+        assert_eq!(skills[0].license, Some("MIT".to_string()));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("Malformed frontmatter"));
+    }
+
+    #[test]
+    fn test_skill_md_dashes_in_value_and_body() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+
+        create_dir_all(path.join("skills/notes")).unwrap();
+        File::create(path.join("skills/notes/SKILL.md"))
+            .unwrap()
+            .write_all(b"---\ndescription: before --- after\nversion: 1.2.0\n---\n# Notes\n\n---\n")
+            .unwrap();
+
+        let mut warnings = Vec::new();
+        let skills = parse_skills_with_warnings(path, &mut warnings).unwrap();
+        assert_eq!(skills[0].description, Some("before --- after".to_string()));
+        assert_eq!(skills[0].version, Some("1.2.0".to_string()));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_skill_frontmatter_fields() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+
+        create_dir_all(path.join("skills/pdf")).unwrap();
+        create_dir_all(path.join("skills/legacy")).unwrap();
+        std::fs::write(
+            path.join("skills/pdf/SKILL.md"),
+            "---\nname: pdf-tools\ndescription: PDFs\nallowed-tools:\n  - Read\n  - Bash(python:*)\nlicense: Apache-2.0\nmetadata:\n  owner: docs-team\n  revision: 3\n---\n",
+        )
+        .unwrap();
+        std::fs::write(
+            path.join("skills/legacy/skill.yaml"),
+            "name: legacy\nallowed-tools: Read, Grep\nlicense: [not, a, string]\n",
+        )
+        .unwrap();
+
+        let skills = parse_skills(path).unwrap();
+        let legacy = &skills[0];
+        assert_eq!(legacy.allowed_tools, ["Read", "Grep"]);
+        assert_eq!(legacy.license, None);
+        assert_eq!(legacy.mismatched_name(), None);
+
+        let pdf = &skills[1];
+        assert_eq!(pdf.name, "pdf");
+        assert_eq!(pdf.declared_name, Some("pdf-tools".to_string()));
+        assert_eq!(pdf.mismatched_name(), Some("pdf-tools"));
+        assert_eq!(pdf.allowed_tools, ["Read", "Bash(python:*)"]);
+        assert_eq!(pdf.license, Some("Apache-2.0".to_string()));
+        assert_eq!(pdf.metadata["owner"], "docs-team");
+        assert_eq!(pdf.metadata["revision"], 3);
+    }
+
+    #[test]
+    fn test_nested_skill_folders() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+
+        create_dir_all(path.join("skills/writing/blog-post")).unwrap();
+        create_dir_all(path.join("skills/writing/changelog")).unwrap();
+        create_dir_all(path.join("skills/bare")).unwrap();
+        std::fs::write(
+            path.join("skills/writing/blog-post/SKILL.md"),
+            "---\ndescription: Drafts posts\n---\n",
+        )
+        .unwrap();
+        std::fs::write(
+            path.join("skills/writing/changelog/skill.yaml"),
+            "description: Changelogs\n",
+        )
+        .unwrap();
+
+        let skills = parse_skills(path).unwrap();
+        let names: Vec<&str> = skills.iter().map(|s| s.name.as_str()).collect();
+        // "writing" only groups skills; "bare" is a top-level skill folder
+        assert_eq!(names, ["bare", "blog-post", "changelog"]);
+        assert_eq!(skills[1].description, Some("Drafts posts".to_string()));
+    }
+
+    #[test]
+    fn test_skill_resources() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+        let skill = path.join("skills/pdf");
+
+        create_dir_all(skill.join("scripts")).unwrap();
+        create_dir_all(skill.join("templates")).unwrap();
+        std::fs::write(skill.join("SKILL.md"), "---\ndescription: PDFs\n---\n").unwrap();
+        std::fs::write(skill.join("scripts/fill.py"), "print('hi')\n").unwrap();
+        std::fs::write(skill.join("templates/form.html"), "<form></form>").unwrap();
+        std::fs::write(skill.join("FORMS.md"), "# Forms\n").unwrap();
+        std::fs::write(skill.join(".DS_Store"), "junk").unwrap();
+        create_dir_all(skill.join("scripts/node_modules/pdf-lib")).unwrap();
+        std::fs::write(skill.join("scripts/node_modules/pdf-lib/index.js"), "").unwrap();
+        create_dir_all(skill.join("venv/lib")).unwrap();
+        std::fs::write(skill.join("venv/lib/site.py"), "").unwrap();
+
+        let skills = parse_skills(path).unwrap();
+        let resources: Vec<(String, u64, ResourceKind)> = skills[0]
+            .resources
+            .iter()
+            .map(|r| (r.path.to_string_lossy().replace('\\', "/"), r.size, r.kind))
+            .collect();
+        assert_eq!(
+            resources,
+            [
+                ("FORMS.md".to_string(), 8, ResourceKind::Reference),
+                ("scripts/fill.py".to_string(), 12, ResourceKind::Script),
+                (
+                    "templates/form.html".to_string(),
+                    13,
+                    ResourceKind::Template
+                ),
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinked_skills_followed_without_cycles() {
+        use std::os::unix::fs::symlink;
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+        let shared = dir.path().join("shared/review");
+
+        create_dir_all(&shared).unwrap();
+        create_dir_all(path.join("skills")).unwrap();
+        std::fs::write(shared.join("SKILL.md"), "---\ndescription: Reviews\n---\n").unwrap();
+        // A skill linked in from elsewhere, which links back to its parent
+        symlink(&shared, path.join("skills/review")).unwrap();
+        symlink(dir.path().join("shared"), shared.join("loop")).unwrap();
+        // A folder without a manifest that links back to the skills root
+        create_dir_all(path.join("skills/group")).unwrap();
+        symlink(path.join("skills"), path.join("skills/group/again")).unwrap();
+
+        let skills = parse_skills(path).unwrap();
+        let names: Vec<&str> = skills.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["group", "review"]);
+        assert_eq!(skills[1].description, Some("Reviews".to_string()));
+        assert!(skills[1].resources.is_empty());
     }
 
     #[test]
@@ -0,0 +1,557 @@
+//! Effective configuration resolution
+//!
+//! When the same name is defined at several levels, Claude Code only loads
+//! one of them. This module applies those precedence rules on top of
+//! `parse_all` and marks the losing items with `shadowed_by`. Items shipped
+//! by disabled plugins are not loaded and take no part.
+//!
+//! Precedence (highest first):
+//! - Agents and commands: project, user
+//! - Skills: user, project
+//! - MCP servers: local, project, user; local and project servers only take
+//!   part for the project being inspected
+//! - Settings keys: managed, local, project, user
+//!
+//! Plugin components are namespaced by their plugin (`plugin:name`), so they
+//! only shadow or are shadowed by items of the same plugin.
+
+use crate::info::{
+    AgentInfo, ClaudeInfo, CommandInfo, McpInfo, McpScope, Origin, PluginInfo, SettingValue,
+    SettingsScope, SkillInfo, SkillLocation,
+};
+use crate::parsers::same_path;
+use std::collections::{HashMap, HashSet};
+
+/// A component that can shadow or be shadowed by another of the same name.
+trait Resolvable {
+    fn name(&self) -> &str;
+    /// Lower rank wins
+    fn rank(&self) -> u8;
+    /// Label used in `shadowed_by` to describe this item's level
+    fn level(&self) -> String;
+    /// Name of the plugin that ships this item, if any
+    fn plugin(&self) -> Option<&str>;
+    /// Name items compete under. Plugin items are namespaced as
+    /// `plugin:name`, so they only clash within their own plugin.
+    fn key(&self) -> String {
+        match self.plugin() {
+            Some(plugin) => format!("{}:{}", plugin, self.name()),
+            None => self.name().to_string(),
+        }
+    }
+    fn set_shadowed_by(&mut self, by: Option<String>);
+}
+
+impl Resolvable for AgentInfo {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn rank(&self) -> u8 {
+        origin_rank(&self.origin)
+    }
+    fn level(&self) -> String {
+        self.origin.to_string()
+    }
+    fn plugin(&self) -> Option<&str> {
+        origin_plugin(&self.origin)
+    }
+    fn set_shadowed_by(&mut self, by: Option<String>) {
+        self.shadowed_by = by;
+    }
+}
+
+impl Resolvable for CommandInfo {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn rank(&self) -> u8 {
+        origin_rank(&self.origin)
+    }
+    fn level(&self) -> String {
+        self.origin.to_string()
+    }
+    fn plugin(&self) -> Option<&str> {
+        origin_plugin(&self.origin)
+    }
+    fn set_shadowed_by(&mut self, by: Option<String>) {
+        self.shadowed_by = by;
+    }
+}
+
+impl Resolvable for SkillInfo {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn rank(&self) -> u8 {
+        match self.location_type {
+            SkillLocation::Global => 0,
+            SkillLocation::Project => 1,
+            SkillLocation::Plugin { .. } => 2,
+        }
+    }
+    fn level(&self) -> String {
+        match &self.location_type {
+            SkillLocation::Global => "user".to_string(),
+            SkillLocation::Project => "project".to_string(),
+            SkillLocation::Plugin { plugin_name } => match plugin_name {
+                Some(name) => format!("plugin:{}", name),
+                None => "plugin".to_string(),
+            },
+        }
+    }
+    fn plugin(&self) -> Option<&str> {
+        match &self.location_type {
+            SkillLocation::Plugin { plugin_name } => plugin_name.as_deref(),
+            _ => None,
+        }
+    }
+    fn set_shadowed_by(&mut self, by: Option<String>) {
+        self.shadowed_by = by;
+    }
+}
+
+impl Resolvable for McpInfo {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn rank(&self) -> u8 {
+        match self.scope {
+            McpScope::Local => 0,
+            McpScope::Project => 1,
+            McpScope::User => 2,
+            McpScope::Plugin => 3,
+        }
+    }
+    fn level(&self) -> String {
+        self.scope.to_string()
+    }
+    fn plugin(&self) -> Option<&str> {
+        self.plugin.as_deref()
+    }
+    fn set_shadowed_by(&mut self, by: Option<String>) {
+        self.shadowed_by = by;
+    }
+}
+
+impl Resolvable for SettingValue {
+    fn name(&self) -> &str {
+        &self.key
+    }
+    fn rank(&self) -> u8 {
+        match self.scope {
+            SettingsScope::Managed => 0,
+            SettingsScope::Local => 1,
+            SettingsScope::Project => 2,
+            SettingsScope::User => 3,
+        }
+    }
+    fn level(&self) -> String {
+        self.scope.to_string()
+    }
+    fn plugin(&self) -> Option<&str> {
+        None
+    }
+    fn set_shadowed_by(&mut self, by: Option<String>) {
+        self.shadowed_by = by;
+    }
+}
+
+fn origin_rank(origin: &Origin) -> u8 {
+    match origin {
+        Origin::Project => 0,
+        Origin::User => 1,
+        Origin::Plugin { .. } => 2,
+    }
+}
+
+fn origin_plugin(origin: &Origin) -> Option<&str> {
+    match origin {
+        Origin::Plugin { plugin_name } => plugin_name.as_deref(),
+        _ => None,
+    }
+}
+
+/// Names of installed plugins with no enabled install; what they ship is not
+/// loaded
+fn disabled_plugins(plugins: &[PluginInfo]) -> HashSet<&str> {
+    let enabled: HashSet<&str> = plugins
+        .iter()
+        .filter(|p| p.enabled)
+        .map(|p| p.name.as_str())
+        .collect();
+    plugins
+        .iter()
+        .map(|p| p.name.as_str())
+        .filter(|name| !enabled.contains(name))
+        .collect()
+}
+
+fn is_active<T: Resolvable>(item: &T, disabled: &HashSet<&str>) -> bool {
+    item.plugin()
+        .map_or(true, |plugin| !disabled.contains(plugin))
+}
+
+/// Mark every item that loses to a higher-precedence item of the same name.
+/// On a tie the item listed first wins. Items of disabled plugins take no
+/// part.
+fn resolve_list<T: Resolvable>(items: &mut [T], disabled: &HashSet<&str>) {
+    resolve_list_where(items, |item| is_active(item, disabled));
+}
+
+/// Like `resolve_list`, but only items for which `competes` holds take part;
+/// the others neither shadow nor are shadowed.
+fn resolve_list_where<T: Resolvable>(items: &mut [T], competes: impl Fn(&T) -> bool) {
+    let mut winners: HashMap<String, usize> = HashMap::new();
+    for (index, item) in items.iter().enumerate() {
+        if !competes(item) {
+            continue;
+        }
+        winners
+            .entry(item.key())
+            .and_modify(|winner| {
+                if item.rank() < items[*winner].rank() {
+                    *winner = index;
+                }
+            })
+            .or_insert(index);
+    }
+
+    let labels: Vec<Option<String>> = items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let winner = *winners.get(&item.key())?;
+            (winner != index && competes(item)).then(|| items[winner].level())
+        })
+        .collect();
+
+    for (item, label) in items.iter_mut().zip(labels) {
+        item.set_shadowed_by(label);
+    }
+}
+
+/// Apply Claude Code's precedence rules, setting `shadowed_by` on every
+/// agent, command, skill, MCP server and settings key that is hidden by
+/// another one. Items shipped by disabled plugins neither shadow nor are
+/// shadowed.
+pub fn resolve_effective(info: &mut ClaudeInfo) {
+    let disabled = disabled_plugins(&info.plugins);
+    resolve_list(&mut info.agents, &disabled);
+    resolve_list(&mut info.commands, &disabled);
+    resolve_list(&mut info.skills, &disabled);
+    // Local and project scoped servers only apply inside their own project
+    let project_dir = info.project_dir.as_deref();
+    resolve_list_where(&mut info.mcp_servers, |server| {
+        let in_scope = match &server.project {
+            Some(project) => project_dir.is_some_and(|dir| same_path(project, dir)),
+            None => true,
+        };
+        in_scope && is_active(server, &disabled)
+    });
+    resolve_list(&mut info.settings.values, &disabled);
+}
+
+/// Keep only the items Claude Code would actually load: nothing shadowed and
+/// nothing shipped by a disabled plugin.
+/// Expects `resolve_effective` to have been applied.
+pub fn effective_only(mut info: ClaudeInfo) -> ClaudeInfo {
+    let disabled = disabled_plugins(&info.plugins);
+
+    info.agents
+        .retain(|a| a.shadowed_by.is_none() && is_active(a, &disabled));
+    info.commands
+        .retain(|c| c.shadowed_by.is_none() && is_active(c, &disabled));
+    info.skills
+        .retain(|s| s.shadowed_by.is_none() && is_active(s, &disabled));
+    info.mcp_servers
+        .retain(|m| m.shadowed_by.is_none() && is_active(m, &disabled));
+    info.hooks
+        .retain(|h| origin_plugin(&h.origin).map_or(true, |p| !disabled.contains(p)));
+    info.settings.values.retain(|v| v.shadowed_by.is_none());
+    info
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::info::{HookInfo, SessionInfo, SettingsInfo, Source};
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    fn agent(name: &str, origin: Origin) -> AgentInfo {
+        AgentInfo {
+            name: name.to_string(),
+            description: None,
+            path: PathBuf::from(format!("/{}/{}.md", origin, name)),
+            origin,
+            shadowed_by: None,
+            tools: vec![],
+            model: None,
+            color: None,
+        }
+    }
+
+    fn skill(name: &str, location_type: SkillLocation) -> SkillInfo {
+        SkillInfo {
+            name: name.to_string(),
+            version: None,
+            source: Source::Official,
+            path: PathBuf::from("/skills").join(name),
+            description: None,
+            location_type,
+            shadowed_by: None,
+            uses: 0,
+            resources: vec![],
+            declared_name: None,
+            allowed_tools: vec![],
+            license: None,
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    fn mcp(name: &str, scope: McpScope) -> McpInfo {
+        McpInfo {
+            name: name.to_string(),
+            status: "configured".to_string(),
+            command: None,
+            path: PathBuf::from("/test/.claude.json"),
+            description: None,
+            scope,
+            project: None,
+            plugin: None,
+            transport: Default::default(),
+            args: vec![],
+            env: vec![],
+            url: None,
+            headers: vec![],
+            shadowed_by: None,
+        }
+    }
+
+    fn empty_info() -> ClaudeInfo {
+        ClaudeInfo {
+            version: "0.1.0".to_string(),
+            config_dir: PathBuf::from("/test/.claude"),
+            project_dir: None,
+            plugins: vec![],
+            skills: vec![],
+            sessions: SessionInfo {
+                count: 0,
+                last_session: None,
+            },
+            mcp_servers: vec![],
+            hooks: vec![],
+            agents: vec![],
+            commands: vec![],
+            settings: SettingsInfo::default(),
+            memory: vec![],
+            warnings: vec![],
+        }
+    }
+
+    #[test]
+    fn test_project_agent_shadows_user_agent() {
+        let mut info = empty_info();
+        info.agents = vec![
+            agent("reviewer", Origin::User),
+            agent("reviewer", Origin::Project),
+            agent("writer", Origin::User),
+        ];
+
+        resolve_effective(&mut info);
+
+        assert_eq!(info.agents[0].shadowed_by, Some("project".to_string()));
+        assert_eq!(info.agents[1].shadowed_by, None);
+        assert_eq!(info.agents[2].shadowed_by, None);
+    }
+
+    #[test]
+    fn test_user_skill_shadows_project_skill() {
+        let mut info = empty_info();
+        info.skills = vec![
+            skill("deploy", SkillLocation::Project),
+            skill("deploy", SkillLocation::Global),
+        ];
+
+        resolve_effective(&mut info);
+
+        assert_eq!(info.skills[0].shadowed_by, Some("user".to_string()));
+        assert_eq!(info.skills[1].shadowed_by, None);
+    }
+
+    #[test]
+    fn test_mcp_local_scope_wins() {
+        let mut info = empty_info();
+        info.mcp_servers = vec![
+            mcp("db", McpScope::User),
+            mcp("db", McpScope::Project),
+            mcp("db", McpScope::Local),
+        ];
+
+        resolve_effective(&mut info);
+
+        assert_eq!(info.mcp_servers[0].shadowed_by, Some("local".to_string()));
+        assert_eq!(info.mcp_servers[1].shadowed_by, Some("local".to_string()));
+        assert_eq!(info.mcp_servers[2].shadowed_by, None);
+    }
+
+    #[test]
+    fn test_mcp_project_servers_only_shadow_in_their_project() {
+        let in_project = |name: &str, scope: McpScope, project: &str| McpInfo {
+            project: Some(PathBuf::from(project)),
+            ..mcp(name, scope)
+        };
+        let mut info = empty_info();
+        info.project_dir = Some(PathBuf::from("/work/app"));
+        info.mcp_servers = vec![
+            mcp("db", McpScope::User),
+            in_project("db", McpScope::Local, "/work/other"),
+            in_project("db", McpScope::Project, "/work/other"),
+            in_project("db", McpScope::Local, "/work/app"),
+        ];
+
+        resolve_effective(&mut info);
+
+        let shadowed: Vec<Option<&str>> = info
+            .mcp_servers
+            .iter()
+            .map(|m| m.shadowed_by.as_deref())
+            .collect();
+        assert_eq!(shadowed, [Some("local"), None, None, None]);
+    }
+
+    #[test]
+    fn test_settings_key_precedence() {
+        let setting = |key: &str, scope: SettingsScope| SettingValue {
+            key: key.to_string(),
+            value: Some(scope.to_string()),
+            scope,
+            path: PathBuf::from("/settings.json"),
+            shadowed_by: None,
+        };
+        let mut info = empty_info();
+        info.settings.values = vec![
+            setting("model", SettingsScope::User),
+            setting("model", SettingsScope::Project),
+            setting("model", SettingsScope::Local),
+            setting("env.DEBUG", SettingsScope::Project),
+            setting("model", SettingsScope::Managed),
+        ];
+
+        resolve_effective(&mut info);
+
+        let shadowed: Vec<Option<&str>> = info
+            .settings
+            .values
+            .iter()
+            .map(|v| v.shadowed_by.as_deref())
+            .collect();
+        assert_eq!(
+            shadowed,
+            [
+                Some("managed"),
+                Some("managed"),
+                Some("managed"),
+                None,
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_same_level_duplicates_first_wins() {
+        let mut info = empty_info();
+        info.agents = vec![agent("dup", Origin::User), agent("dup", Origin::User)];
+
+        resolve_effective(&mut info);
+
+        assert_eq!(info.agents[0].shadowed_by, None);
+        assert_eq!(info.agents[1].shadowed_by, Some("user".to_string()));
+    }
+
+    #[test]
+    fn test_plugin_items_namespaced_by_plugin() {
+        let from = |name: &str| Origin::Plugin {
+            plugin_name: Some(name.to_string()),
+        };
+        let mut info = empty_info();
+        info.agents = vec![
+            agent("review", from("lint")),
+            agent("review", Origin::User),
+            agent("review", from("docs")),
+            agent("review", from("lint")),
+        ];
+
+        resolve_effective(&mut info);
+
+        let shadowed: Vec<Option<&str>> = info
+            .agents
+            .iter()
+            .map(|a| a.shadowed_by.as_deref())
+            .collect();
+        assert_eq!(shadowed, [None, None, None, Some("plugin:lint")]);
+    }
+
+    #[test]
+    fn test_disabled_plugin_items_inactive() {
+        let plugin = |name: &str, enabled: bool| PluginInfo {
+            name: name.to_string(),
+            version: None,
+            source: Source::ThirdParty,
+            path: PathBuf::from("/plugins").join(name),
+            marketplace: None,
+            enabled,
+            description: None,
+            author: None,
+            homepage: None,
+            repository: None,
+            keywords: vec![],
+            installs: vec![],
+        };
+        let from = |name: &str| Origin::Plugin {
+            plugin_name: Some(name.to_string()),
+        };
+        let mut info = empty_info();
+        info.plugins = vec![plugin("off", false), plugin("on", true)];
+        info.agents = vec![agent("helper", from("off")), agent("helper", from("on"))];
+        info.hooks = vec![HookInfo {
+            name: "PreToolUse".to_string(),
+            hook_type: "command".to_string(),
+            path: PathBuf::from("/plugins/off/hooks/hooks.json"),
+            description: None,
+            event: Some("PreToolUse".to_string()),
+            matcher: None,
+            command: Some("lint".to_string()),
+            timeout: None,
+            settings_file: None,
+            origin: from("off"),
+        }];
+
+        resolve_effective(&mut info);
+        assert_eq!(info.agents[0].shadowed_by, None);
+        assert_eq!(info.agents[1].shadowed_by, None);
+
+        let info = effective_only(info);
+        assert_eq!(info.agents.len(), 1);
+        assert_eq!(info.agents[0].origin, from("on"));
+        assert!(info.hooks.is_empty());
+    }
+
+    #[test]
+    fn test_effective_only_drops_shadowed() {
+        let mut info = empty_info();
+        info.agents = vec![
+            agent("reviewer", Origin::User),
+            agent("reviewer", Origin::Project),
+        ];
+        info.mcp_servers = vec![mcp("db", McpScope::User), mcp("db", McpScope::Local)];
+
+        resolve_effective(&mut info);
+        let info = effective_only(info);
+
+        assert_eq!(info.agents.len(), 1);
+        assert_eq!(info.agents[0].origin, Origin::Project);
+        assert_eq!(info.mcp_servers.len(), 1);
+        assert_eq!(info.mcp_servers[0].scope, McpScope::Local);
+    }
+}
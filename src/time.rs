@@ -0,0 +1,236 @@
+//! RFC 3339 timestamps and civil dates, without a date-time dependency
+//!
+//! Times are milliseconds since the Unix epoch, dates are proleptic
+//! Gregorian and everything is UTC.
+
+/// Largest four-digit year RFC 3339 allows
+const MAX_YEAR: i64 = 9999;
+
+/// Milliseconds since the Unix epoch for an RFC 3339 timestamp such as
+/// `2026-01-14T04:28:57.441Z` or `2026-01-14T12:28:57+08:00`
+pub fn parse_timestamp(ts: &str) -> Option<i64> {
+    let (date, time) = ts.split_once('T')?;
+
+    let mut date_parts = date.split('-').map(|p| p.parse::<i64>().ok());
+    let year = date_parts.next()??;
+    let month = date_parts.next()??;
+    let day = date_parts.next()??;
+    if !(0..=MAX_YEAR).contains(&year)
+        || !(1..=12).contains(&month)
+        || !(1..=days_in_month(year, month)).contains(&day)
+    {
+        return None;
+    }
+
+    let (clock, offset_secs) = match time.strip_suffix('Z') {
+        Some(clock) => (clock, 0),
+        None => {
+            let idx = time.rfind(['+', '-'])?;
+            let (clock, offset) = time.split_at(idx);
+            let sign = if offset.starts_with('-') { -1 } else { 1 };
+            let (hours, minutes) = offset[1..].split_once(':')?;
+            let hours = hours.parse::<i64>().ok()?;
+            let minutes = minutes.parse::<i64>().ok()?;
+            if !(0..=59).contains(&minutes) || !(0..=24).contains(&hours) {
+                return None;
+            }
+            let offset = hours * 3600 + minutes * 60;
+            if offset > 86_400 {
+                return None;
+            }
+            (clock, sign * offset)
+        }
+    };
+
+    let (hms, fraction) = clock.split_once('.').unwrap_or((clock, ""));
+    let mut hms_parts = hms.split(':').map(|p| p.parse::<i64>().ok());
+    let hour = hms_parts.next()??;
+    let minute = hms_parts.next()??;
+    let second = hms_parts.next()??;
+    if hms_parts.next().is_some()
+        || !(0..=23).contains(&hour)
+        || !(0..=59).contains(&minute)
+        || !(0..=59).contains(&second)
+    {
+        return None;
+    }
+    if !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let millis = if fraction.is_empty() {
+        0
+    } else {
+        let digits: String = fraction.chars().take(3).collect();
+        format!("{:0<3}", digits).parse::<i64>().ok()?
+    };
+
+    let days = days_from_civil(year, month, day)?;
+    let secs = days
+        .checked_mul(86_400)?
+        .checked_add(hour.checked_mul(3600)?)?
+        .checked_add(minute.checked_mul(60)?)?
+        .checked_add(second)?
+        .checked_sub(offset_secs)?;
+    secs.checked_mul(1000)?.checked_add(millis)
+}
+
+/// `YYYY-MM-DD` (UTC) for milliseconds since the Unix epoch
+pub fn utc_date(millis: i64) -> String {
+    let days = millis.div_euclid(86_400_000);
+    let (year, month, day) = civil_from_days(days);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// RFC 3339 timestamp in UTC for milliseconds since the Unix epoch, e.g.
+/// `2026-01-14T04:28:57.441Z` (milliseconds omitted when zero)
+pub fn utc_datetime(millis: i64) -> String {
+    let ms = millis.rem_euclid(1000);
+    let secs = millis.div_euclid(1000).rem_euclid(86_400);
+    let fraction = if ms == 0 {
+        String::new()
+    } else {
+        format!(".{:03}", ms)
+    };
+    format!(
+        "{}T{:02}:{:02}:{:02}{}Z",
+        utc_date(millis),
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60,
+        fraction
+    )
+}
+
+/// Milliseconds since the Unix epoch at the start (UTC) of a `YYYY-MM-DD` day
+pub fn parse_date(date: &str) -> Option<i64> {
+    if date.len() != 10 {
+        return None;
+    }
+    parse_timestamp(&format!("{}T00:00:00Z", date))
+}
+
+/// Number of days in a month (1-12) of a proleptic Gregorian year
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Proleptic Gregorian date for a number of days since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date, or `None` if it
+/// does not fit in an `i64`
+fn days_from_civil(year: i64, month: i64, day: i64) -> Option<i64> {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = month.checked_add(9)?.rem_euclid(12);
+    let day_of_year = ((153 * month_index + 2) / 5).checked_add(day)? - 1;
+    let day_of_era =
+        (year_of_era * 365 + year_of_era / 4 - year_of_era / 100).checked_add(day_of_year)?;
+    era.checked_mul(146_097)?
+        .checked_add(day_of_era)?
+        .checked_sub(719_468)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(
+            parse_timestamp("2026-01-14T04:28:57.441Z"),
+            Some(1_768_364_937_441)
+        );
+        assert_eq!(
+            parse_timestamp("2026-01-14T12:28:57.441+08:00"),
+            parse_timestamp("2026-01-14T04:28:57.441Z")
+        );
+        assert_eq!(parse_timestamp("yesterday"), None);
+        assert_eq!(parse_timestamp("2026-01-14T04:28:57.12éZ"), None);
+        assert_eq!(
+            parse_timestamp("2026-01-14T04:28:57.4Z"),
+            Some(1_768_364_937_400)
+        );
+    }
+
+    #[test]
+    fn test_parse_timestamp_out_of_range() {
+        assert_eq!(parse_timestamp("99999999999999999-01-01T00:00:00Z"), None);
+        assert_eq!(parse_timestamp("10000-01-01T00:00:00Z"), None);
+        assert_eq!(parse_timestamp("-1-01-01T00:00:00Z"), None);
+        assert_eq!(parse_timestamp("2026-01-01T9999999999999999:00:00Z"), None);
+        assert_eq!(
+            parse_timestamp("2026-9223372036854775807-01T00:00:00Z"),
+            None
+        );
+        assert_eq!(
+            parse_timestamp("2026-01-01T00:00:00+9999999999999999:00"),
+            None
+        );
+        assert!(parse_timestamp("9999-12-31T23:59:59.999Z").is_some());
+    }
+
+    #[test]
+    fn test_parse_timestamp_rejects_invalid_fields() {
+        assert_eq!(parse_timestamp("2026-01-14T99:99:99Z"), None);
+        assert_eq!(parse_timestamp("2026-01-14T24:00:00Z"), None);
+        assert_eq!(parse_timestamp("2026-01-14T23:60:00Z"), None);
+        assert_eq!(parse_timestamp("2026-01-14T23:59:60Z"), None);
+        assert_eq!(parse_timestamp("2026-01-14T23:59:59:00Z"), None);
+        assert_eq!(parse_timestamp("2026-13-01T00:00:00Z"), None);
+        assert_eq!(parse_timestamp("2026-00-01T00:00:00Z"), None);
+        assert_eq!(parse_timestamp("2026-01-00T00:00:00Z"), None);
+        assert_eq!(parse_timestamp("2026-04-31T00:00:00Z"), None);
+        assert_eq!(parse_timestamp("2026-01-14T00:00:00+25:00"), None);
+        assert_eq!(parse_timestamp("2026-01-14T00:00:00+24:30"), None);
+        assert_eq!(parse_timestamp("2026-01-14T00:00:00-05:60"), None);
+        assert!(parse_timestamp("2026-01-14T00:00:00-24:00").is_some());
+    }
+
+    #[test]
+    fn test_utc_datetime() {
+        assert_eq!(utc_datetime(0), "1970-01-01T00:00:00Z");
+        assert_eq!(utc_datetime(1_768_364_937_441), "2026-01-14T04:28:57.441Z");
+        assert_eq!(utc_datetime(-1), "1969-12-31T23:59:59.999Z");
+    }
+
+    #[test]
+    fn test_dates() {
+        assert_eq!(utc_date(0), "1970-01-01");
+        assert_eq!(utc_date(1_768_364_937_441), "2026-01-14");
+        assert_eq!(utc_date(parse_date("2024-02-29").unwrap()), "2024-02-29");
+        assert_eq!(
+            parse_date("2026-01-14"),
+            parse_timestamp("2026-01-14T00:00:00Z")
+        );
+        assert_eq!(parse_date("2026-02-31"), None);
+        assert_eq!(parse_date("2025-02-29"), None);
+        assert_eq!(parse_date("2100-02-29"), None);
+        assert!(parse_date("2000-02-29").is_some());
+        assert_eq!(parse_date("2026-1-4"), None);
+        assert_eq!(parse_date("soon"), None);
+    }
+}
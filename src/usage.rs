@@ -0,0 +1,314 @@
+//! Token usage and estimated cost from session transcripts, for the `usage`
+//! subcommand
+
+use crate::info::{Diagnostic, Timestamp, TokenUsage, UsageRecord};
+use crate::time::utc_date;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::io::Write;
+
+const KEY_WIDTH: usize = 40;
+const TOKEN_WIDTH: usize = 13;
+const COST_WIDTH: usize = 10;
+
+/// List prices in USD per million input and output tokens, matched against
+/// the model id in order. Cache writes cost 1.25x and cache reads 0.1x the
+/// input price. Models not listed here are reported without a cost.
+const PRICING: &[(&str, f64, f64)] = &[
+    ("opus-4-5", 5.0, 25.0),
+    ("opus", 15.0, 75.0),
+    ("sonnet", 3.0, 15.0),
+    ("haiku-4-5", 1.0, 5.0),
+    ("3-5-haiku", 0.8, 4.0),
+    ("haiku", 0.25, 1.25),
+];
+
+/// Time window of a report; `until` is exclusive
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UsageWindow {
+    pub since: Option<i64>,
+    pub until: Option<i64>,
+}
+
+impl UsageWindow {
+    /// Records without a timestamp only count when no window is set
+    fn contains(&self, timestamp: Option<Timestamp>) -> bool {
+        if self.since.is_none() && self.until.is_none() {
+            return true;
+        }
+        match timestamp.map(|ts| ts.as_millis()) {
+            Some(ts) => {
+                self.since.map_or(true, |since| ts >= since)
+                    && self.until.map_or(true, |until| ts < until)
+            }
+            None => false,
+        }
+    }
+}
+
+/// Usage summed over one day, project or model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageRow {
+    pub key: String,
+    #[serde(flatten)]
+    pub usage: TokenUsage,
+    /// Estimated from list prices; absent when no model in the row is priced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_usd: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageReport {
+    pub total: UsageRow,
+    pub days: Vec<UsageRow>,
+    pub projects: Vec<UsageRow>,
+    pub models: Vec<UsageRow>,
+    /// Transcript files that could not be fully read
+    #[serde(default)]
+    pub warnings: Vec<Diagnostic>,
+}
+
+/// Sum the records inside `window` per day (UTC), per project and per model
+pub fn build_usage_report(records: &[UsageRecord], window: UsageWindow) -> UsageReport {
+    let mut total = UsageRow::new("total".to_string());
+    let mut days = BTreeMap::new();
+    let mut projects = BTreeMap::new();
+    let mut models = BTreeMap::new();
+
+    for record in records.iter().filter(|r| window.contains(r.timestamp)) {
+        let cost = estimate_cost(&record.model, &record.usage);
+        let day = record
+            .timestamp
+            .map(|ts| utc_date(ts.as_millis()))
+            .unwrap_or_else(|| "unknown".to_string());
+
+        total.add(&record.usage, cost);
+        for (rows, key) in [
+            (&mut days, day),
+            (&mut projects, record.project.clone()),
+            (&mut models, record.model.clone()),
+        ] {
+            rows.entry(key.clone())
+                .or_insert_with(|| UsageRow::new(key))
+                .add(&record.usage, cost);
+        }
+    }
+
+    // Days in order; projects and models by token count, largest first
+    let by_tokens = |rows: BTreeMap<String, UsageRow>| {
+        let mut rows: Vec<UsageRow> = rows.into_values().collect();
+        rows.sort_by_key(|row| Reverse(row.usage.total()));
+        rows
+    };
+
+    UsageReport {
+        total,
+        days: days.into_values().collect(),
+        projects: by_tokens(projects),
+        models: by_tokens(models),
+        warnings: vec![],
+    }
+}
+
+impl UsageRow {
+    fn new(key: String) -> Self {
+        Self {
+            key,
+            usage: TokenUsage::default(),
+            cost_usd: None,
+        }
+    }
+
+    fn add(&mut self, usage: &TokenUsage, cost: Option<f64>) {
+        self.usage.add(usage);
+        if let Some(cost) = cost {
+            *self.cost_usd.get_or_insert(0.0) += cost;
+        }
+    }
+}
+
+/// Estimated cost in USD of one message, from the list prices in `PRICING`
+fn estimate_cost(model: &str, usage: &TokenUsage) -> Option<f64> {
+    let (_, input, output) = PRICING
+        .iter()
+        .find(|(family, _, _)| model.contains(family))?;
+    let per_token = |price: f64, tokens: u64| price * tokens as f64 / 1_000_000.0;
+
+    Some(
+        per_token(*input, usage.input_tokens)
+            + per_token(*output, usage.output_tokens)
+            + per_token(input * 1.25, usage.cache_creation_tokens)
+            + per_token(input * 0.1, usage.cache_read_tokens),
+    )
+}
+
+/// Print the totals followed by the per-day, per-project and per-model tables
+pub fn write_usage(report: &UsageReport, output: &mut dyn Write) -> std::io::Result<()> {
+    if report.total.usage.total() == 0 {
+        writeln!(output, "No token usage found")?;
+        return Ok(());
+    }
+
+    writeln!(
+        output,
+        "TOTAL      {} tokens, estimated cost {}",
+        report.total.usage.total(),
+        format_cost(report.total.cost_usd)
+    )?;
+    writeln!(output)?;
+
+    for (title, heading, rows) in [
+        ("BY DAY", "DATE", &report.days),
+        ("BY PROJECT", "PROJECT", &report.projects),
+        ("BY MODEL", "MODEL", &report.models),
+    ] {
+        writeln!(output, "{}", title)?;
+        writeln!(
+            output,
+            "  {:<key$} {:>tokens$} {:>tokens$} {:>tokens$} {:>tokens$} {:>cost$}",
+            heading,
+            "INPUT",
+            "OUTPUT",
+            "CACHE WRITE",
+            "CACHE READ",
+            "COST",
+            key = KEY_WIDTH,
+            tokens = TOKEN_WIDTH,
+            cost = COST_WIDTH
+        )?;
+        for row in rows {
+            writeln!(
+                output,
+                "  {:<key$} {:>tokens$} {:>tokens$} {:>tokens$} {:>tokens$} {:>cost$}",
+                crate::output::truncate_with_ellipsis(&row.key, KEY_WIDTH, "..."),
+                row.usage.input_tokens,
+                row.usage.output_tokens,
+                row.usage.cache_creation_tokens,
+                row.usage.cache_read_tokens,
+                format_cost(row.cost_usd),
+                key = KEY_WIDTH,
+                tokens = TOKEN_WIDTH,
+                cost = COST_WIDTH
+            )?;
+        }
+        writeln!(output)?;
+    }
+
+    writeln!(output, "Costs are estimates from list prices.")?;
+
+    Ok(())
+}
+
+fn format_cost(cost: Option<f64>) -> String {
+    match cost {
+        Some(cost) => format!("${:.2}", cost),
+        None => "-".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::parse_date;
+
+    fn record(project: &str, model: &str, timestamp: &str, input: u64, output: u64) -> UsageRecord {
+        UsageRecord {
+            project: project.to_string(),
+            model: model.to_string(),
+            timestamp: Timestamp::parse(timestamp),
+            usage: TokenUsage {
+                input_tokens: input,
+                output_tokens: output,
+                cache_creation_tokens: 0,
+                cache_read_tokens: 0,
+            },
+        }
+    }
+
+    #[test]
+    fn test_estimate_cost() {
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            output_tokens: 1_000_000,
+            cache_creation_tokens: 1_000_000,
+            cache_read_tokens: 1_000_000,
+        };
+        // 3 + 15 + 3.75 + 0.3
+        let cost = estimate_cost("claude-sonnet-4-5-20250929", &usage).unwrap();
+        assert!((cost - 22.05).abs() < 1e-9);
+        assert!(estimate_cost("gpt-4", &usage).is_none());
+    }
+
+    #[test]
+    fn test_build_usage_report() {
+        let records = vec![
+            record("/a", "claude-sonnet-4-5", "2026-01-14T10:00:00Z", 100, 10),
+            record("/a", "claude-haiku-4-5", "2026-01-14T23:59:59Z", 50, 5),
+            record("/b", "claude-sonnet-4-5", "2026-01-15T00:00:00Z", 1000, 100),
+            record("/b", "custom-model", "2026-01-16T00:00:00Z", 1, 1),
+        ];
+
+        let report = build_usage_report(&records, UsageWindow::default());
+        assert_eq!(report.total.usage.input_tokens, 1151);
+        assert_eq!(report.days.len(), 3);
+        assert_eq!(report.days[0].key, "2026-01-14");
+        assert_eq!(report.days[0].usage.output_tokens, 15);
+        assert_eq!(report.projects[0].key, "/b");
+        assert_eq!(report.models[0].key, "claude-sonnet-4-5");
+        let custom = report
+            .models
+            .iter()
+            .find(|m| m.key == "custom-model")
+            .unwrap();
+        assert_eq!(custom.cost_usd, None);
+        assert!(report.days[0].cost_usd.is_some());
+    }
+
+    #[test]
+    fn test_usage_window() {
+        let records = vec![
+            record("/a", "claude-sonnet-4-5", "2026-01-14T10:00:00Z", 100, 10),
+            record("/a", "claude-sonnet-4-5", "2026-01-15T10:00:00Z", 200, 20),
+            record("/a", "claude-sonnet-4-5", "2026-01-16T10:00:00Z", 400, 40),
+        ];
+
+        // --since 2026-01-15 --until 2026-01-15 (whole day, inclusive)
+        let window = UsageWindow {
+            since: parse_date("2026-01-15"),
+            until: parse_date("2026-01-16"),
+        };
+        let report = build_usage_report(&records, window);
+        assert_eq!(report.total.usage.input_tokens, 200);
+        assert_eq!(report.days.len(), 1);
+    }
+
+    #[test]
+    fn test_write_usage() {
+        let records = vec![record(
+            "/work/app",
+            "claude-sonnet-4-5",
+            "2026-01-14T10:00:00Z",
+            1_000_000,
+            0,
+        )];
+        let report = build_usage_report(&records, UsageWindow::default());
+
+        let mut buffer = Vec::new();
+        write_usage(&report, &mut buffer).unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(output.contains("TOTAL      1000000 tokens, estimated cost $3.00"));
+        assert!(output.contains("BY DAY"));
+        assert!(output.contains("2026-01-14"));
+        assert!(output.contains("/work/app"));
+        assert!(output.contains("claude-sonnet-4-5"));
+
+        let mut buffer = Vec::new();
+        write_usage(
+            &build_usage_report(&[], UsageWindow::default()),
+            &mut buffer,
+        )
+        .unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), "No token usage found\n");
+    }
+}
@@ -1,8 +1,12 @@
 //! Integration tests for claude-list CLI
 
+// `Command::cargo_bin` is deprecated in newer assert_cmd releases, but the
+// replacement macro is not available in every version we support.
+#![allow(deprecated)]
+
 use assert_cmd::Command;
 use predicates::prelude::*;
-use std::fs::{self, File};
+use std::fs::File;
 use std::io::Write;
 use tempfile::TempDir;
 
@@ -497,6 +501,28 @@ fn test_empty_session_history() {
     assert!(!stdout.contains("SESSIONS"));
 }
 
+#[test]
+fn test_history_with_out_of_range_timestamp() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = dir.path().join(".claude");
+    std::fs::create_dir_all(&claude_dir).unwrap();
+
+    let history = concat!(
+        r#"{"display":"hi","timestamp":"99999999999999999-01-01T00:00:00Z"}"#,
+        "\n",
+        r#"{"display":"/help","timestamp":-9223372036854775808}"#,
+        "\n",
+    );
+    std::fs::write(claude_dir.join("history.jsonl"), history).unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config").arg(&claude_dir);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("SESSIONS"));
+}
+
 #[test]
 fn test_very_long_plugin_name() {
     let dir = TempDir::new().unwrap();
@@ -1711,6 +1737,60 @@ hook: pre-commit
     assert!(!stdout.contains("SKILLS"));
 }
 
+#[test]
+fn test_hooks_from_settings_json() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = dir.path().join(".claude");
+    std::fs::create_dir_all(&claude_dir).unwrap();
+
+    let settings = r#"{
+        "hooks": {
+            "PreToolUse": [
+                {
+                    "matcher": "Edit|Write",
+                    "hooks": [{"type": "command", "command": "cargo fmt", "timeout": 60}]
+                }
+            ],
+            "Stop": [
+                {"hooks": [{"type": "command", "command": "say done"}]}
+            ]
+        }
+    }"#;
+    std::fs::write(claude_dir.join("settings.json"), settings).unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config")
+        .arg(&claude_dir)
+        .arg("--hooks")
+        .arg("-l");
+
+    let output = cmd.output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.contains("2 configured"));
+    assert!(stdout.contains("PreToolUse:Edit|Write"));
+    assert!(stdout.contains("cargo fmt"));
+    assert!(stdout.contains("Stop"));
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config")
+        .arg(&claude_dir)
+        .arg("--hooks")
+        .arg("--json");
+
+    let output = cmd.output().unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let hooks = json["hooks"].as_array().unwrap();
+    assert_eq!(hooks[0]["event"], "PreToolUse");
+    assert_eq!(hooks[0]["matcher"], "Edit|Write");
+    assert_eq!(hooks[0]["command"], "cargo fmt");
+    assert_eq!(hooks[0]["timeout"], 60);
+    assert!(hooks[0]["settings_file"]
+        .as_str()
+        .unwrap()
+        .ends_with("settings.json"));
+}
+
 #[test]
 fn test_filter_agents_only() {
     let dir = TempDir::new().unwrap();
@@ -2038,6 +2118,108 @@ fn test_mcp_empty_servers_directory() {
     assert!(!stdout.contains("MCP"));
 }
 
+#[test]
+fn test_mcp_from_claude_json_with_scopes() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = dir.path().join(".claude");
+    std::fs::create_dir_all(&claude_dir).unwrap();
+
+    let project = dir.path().join("repo");
+    std::fs::create_dir_all(&project).unwrap();
+    std::fs::write(
+        project.join(".mcp.json"),
+        r#"{"mcpServers": {"shared-db": {"command": "db-mcp"}}}"#,
+    )
+    .unwrap();
+
+    let claude_json = format!(
+        r#"{{
+            "mcpServers": {{"user-server": {{"command": "npx"}}}},
+            "projects": {{"{}": {{"mcpServers": {{"local-server": {{"command": "uvx"}}}}}}}}
+        }}"#,
+        project.to_string_lossy().replace('\\', "/")
+    );
+    std::fs::write(dir.path().join(".claude.json"), claude_json).unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config")
+        .arg(&claude_dir)
+        .arg("--mcp")
+        .arg("--json");
+
+    let output = cmd.output().unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let servers = json["mcp_servers"].as_array().unwrap();
+    assert_eq!(servers.len(), 3);
+
+    let scope_of = |name: &str| {
+        servers
+            .iter()
+            .find(|s| s["name"] == name)
+            .map(|s| s["scope"].as_str().unwrap().to_string())
+            .unwrap()
+    };
+    assert_eq!(scope_of("user-server"), "user");
+    assert_eq!(scope_of("local-server"), "local");
+    assert_eq!(scope_of("shared-db"), "project");
+}
+
+#[test]
+fn test_mcp_transport_details_in_detailed_and_json() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = dir.path().join(".claude");
+    std::fs::create_dir_all(&claude_dir).unwrap();
+
+    let mcp_config = r#"{
+        "mcpServers": {
+            "github": {
+                "command": "npx",
+                "args": ["-y", "@modelcontextprotocol/server-github"],
+                "env": {"GITHUB_PERSONAL_ACCESS_TOKEN": "ghp_secret"}
+            },
+            "linear": {
+                "type": "sse",
+                "url": "https://mcp.linear.app/sse",
+                "headers": {"Authorization": "Bearer secret"}
+            }
+        }
+    }"#;
+    std::fs::write(claude_dir.join("mcp.json"), mcp_config).unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config").arg(&claude_dir).arg("--mcp").arg("-l");
+
+    let output = cmd.output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.contains("stdio: npx -y @modelcontextprotocol/server-github"));
+    assert!(stdout.contains("env: GITHUB_PERSONAL_ACCESS_TOKEN"));
+    assert!(stdout.contains("sse: https://mcp.linear.app/sse"));
+    assert!(stdout.contains("headers: Authorization"));
+    // Secret values are never printed
+    assert!(!stdout.contains("ghp_secret"));
+    assert!(!stdout.contains("Bearer secret"));
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config")
+        .arg(&claude_dir)
+        .arg("--mcp")
+        .arg("--json");
+
+    let output = cmd.output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains("ghp_secret"));
+
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let servers = json["mcp_servers"].as_array().unwrap();
+    let github = servers.iter().find(|s| s["name"] == "github").unwrap();
+    assert_eq!(github["transport"], "stdio");
+    assert_eq!(github["args"][1], "@modelcontextprotocol/server-github");
+    let linear = servers.iter().find(|s| s["name"] == "linear").unwrap();
+    assert_eq!(linear["transport"], "sse");
+    assert_eq!(linear["url"], "https://mcp.linear.app/sse");
+}
+
 // ==================== Search Combination Tests ====================
 
 #[test]
@@ -2437,6 +2619,1062 @@ fn test_json_skill_has_all_fields() {
     }
 }
 
+// ==================== Project-Aware Mode Tests ====================
+
+fn create_project_dir(base: &TempDir) -> std::path::PathBuf {
+    let project = base.path().join("repo");
+    let claude = project.join(".claude");
+    std::fs::create_dir_all(claude.join("commands")).unwrap();
+    std::fs::create_dir_all(claude.join("agents")).unwrap();
+    std::fs::create_dir_all(project.join("src")).unwrap();
+    std::fs::write(
+        claude.join("commands/ship.md"),
+        "---\ndescription: Ship the repo\n---\n",
+    )
+    .unwrap();
+    std::fs::write(
+        claude.join("agents/repo-guide.md"),
+        "---\nname: repo-guide\ndescription: Knows this repo\n---\n",
+    )
+    .unwrap();
+    std::fs::write(
+        project.join(".mcp.json"),
+        r#"{"mcpServers": {"repo-server": {"command": "node", "args": ["server.js"]}}}"#,
+    )
+    .unwrap();
+    project
+}
+
+#[test]
+fn test_project_flag_merges_project_components() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = create_mock_claude_dir(&dir);
+    let project = create_project_dir(&dir);
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config")
+        .arg(&claude_dir)
+        .arg(format!("--project={}", project.display()))
+        .arg("-l");
+
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.contains("PROJECT:"));
+    assert!(stdout.contains("ship"));
+    assert!(stdout.contains("repo-guide"));
+    assert!(stdout.contains("project"));
+    assert!(stdout.contains("repo-server"));
+    // User-level components are still listed
+    assert!(stdout.contains("test-skill"));
+}
+
+#[test]
+fn test_project_flag_without_value_walks_up_from_cwd() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = create_mock_claude_dir(&dir);
+    let project = create_project_dir(&dir);
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.current_dir(project.join("src"))
+        .arg("--config")
+        .arg(&claude_dir)
+        .arg("--project")
+        .arg("--json");
+
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert_eq!(
+        json["project_dir"].as_str().unwrap(),
+        project.canonicalize().unwrap().to_str().unwrap()
+    );
+    let command = &json["commands"][0];
+    assert_eq!(command["name"], "ship");
+    assert_eq!(command["origin"]["type"], "Project");
+}
+
+#[test]
+fn test_project_flag_before_subcommand() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = create_mock_claude_dir(&dir);
+    let project = create_project_dir(&dir);
+
+    // A bare --project does not take the subcommand as its DIR
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.current_dir(project.join("src"))
+        .arg("--config")
+        .arg(&claude_dir)
+        .arg("--project")
+        .arg("sessions");
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains("Project directory not found").not());
+
+    // A directory has to be attached with `=`
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config")
+        .arg(&claude_dir)
+        .arg("--project")
+        .arg(&project);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("unrecognized subcommand"));
+}
+
+#[test]
+fn test_project_flag_missing_directory() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = create_mock_claude_dir(&dir);
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config").arg(&claude_dir).arg(format!(
+        "--project={}",
+        dir.path().join("does-not-exist").display()
+    ));
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Project directory not found"));
+}
+
+#[test]
+fn test_without_project_flag_no_project_components() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = create_mock_claude_dir(&dir);
+    let project = create_project_dir(&dir);
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.current_dir(&project).arg("--config").arg(&claude_dir);
+
+    let output = cmd.output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains("PROJECT:"));
+    assert!(!stdout.contains("repo-guide"));
+}
+
+#[test]
+fn test_project_command_shadows_user_command() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = create_mock_claude_dir(&dir);
+    let project = create_project_dir(&dir);
+    std::fs::create_dir_all(claude_dir.join("commands")).unwrap();
+    std::fs::write(
+        claude_dir.join("commands/ship.md"),
+        "---\ndescription: User ship\n---\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config")
+        .arg(&claude_dir)
+        .arg(format!("--project={}", project.display()))
+        .arg("--commands")
+        .arg("--json");
+
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let commands = json["commands"].as_array().unwrap();
+    let user_ship = commands
+        .iter()
+        .find(|c| c["name"] == "ship" && c["origin"]["type"] == "User")
+        .unwrap();
+    assert_eq!(user_ship["shadowed_by"], "project");
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config")
+        .arg(&claude_dir)
+        .arg(format!("--project={}", project.display()))
+        .arg("--commands")
+        .arg("--effective")
+        .arg("-l");
+
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Ship the repo"));
+    assert!(!stdout.contains("User ship"));
+}
+
+// ==================== Doctor Subcommand Tests ====================
+
+#[test]
+fn test_doctor_clean_config_succeeds() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = dir.path().join(".claude");
+    std::fs::create_dir_all(claude_dir.join("agents")).unwrap();
+    std::fs::write(
+        claude_dir.join("agents/helper.md"),
+        "---\nname: helper\ndescription: Helps out\n---\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("doctor").arg("--config").arg(&claude_dir);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("No problems found"));
+}
+
+#[test]
+fn test_doctor_reports_problems_and_fails() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = dir.path().join(".claude");
+    std::fs::create_dir_all(claude_dir.join("agents")).unwrap();
+    std::fs::create_dir_all(claude_dir.join("skills/broken")).unwrap();
+    std::fs::write(
+        claude_dir.join("agents/quiet.md"),
+        "---\nname: quiet\n---\n",
+    )
+    .unwrap();
+    std::fs::write(
+        claude_dir.join("skills/broken/SKILL.md"),
+        "---\ndescription: never closed\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config").arg(&claude_dir).arg("doctor");
+
+    cmd.assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::contains("ERRORS     2 found"))
+        .stdout(predicate::str::contains("Malformed frontmatter"))
+        .stdout(predicate::str::contains("SKILL.md:1:1: no closing `---`"))
+        .stdout(predicate::str::contains(
+            "Missing required field: description",
+        ));
+}
+
+#[test]
+fn test_doctor_json_output() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = dir.path().join(".claude");
+    std::fs::create_dir_all(claude_dir.join("mcp-servers/empty")).unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("doctor")
+        .arg("--config")
+        .arg(&claude_dir)
+        .arg("--json");
+
+    let output = cmd.output().unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let diagnostics = json.as_array().unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0]["severity"], "error");
+    assert!(diagnostics[0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("launch config"));
+}
+
+// ==================== Parse Warnings Tests ====================
+
+#[test]
+fn test_parse_warnings_in_output_and_json() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = create_mock_claude_dir(&dir);
+    std::fs::write(
+        claude_dir.join("history.jsonl"),
+        "{\"timestamp\": 1}\nnot json\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config").arg(&claude_dir);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("WARNINGS   1 found"))
+        .stdout(predicate::str::contains("[session]"));
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config").arg(&claude_dir).arg("--json");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let warnings = json["warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0]["component"], "session");
+    assert_eq!(warnings[0]["severity"], "warning");
+}
+
+#[test]
+fn test_strict_fails_on_parse_warnings() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = create_mock_claude_dir(&dir);
+    std::fs::write(claude_dir.join("history.jsonl"), "not json\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config").arg(&claude_dir).arg("--strict");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("--strict"));
+
+    // Filtering out the affected component makes the run clean again
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config")
+        .arg(&claude_dir)
+        .arg("--strict")
+        .arg("--skills");
+    cmd.assert().success();
+}
+
+#[test]
+fn test_malformed_settings_reported_once() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = create_mock_claude_dir(&dir);
+    std::fs::write(claude_dir.join("settings.json"), "{ not json").unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config").arg(&claude_dir).arg("--json");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let warnings = json["warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0]["component"], "settings");
+}
+
+#[test]
+fn test_unreadable_agent_file_does_not_abort() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = create_mock_claude_dir(&dir);
+    let agents_dir = claude_dir.join("agents");
+    std::fs::create_dir_all(&agents_dir).unwrap();
+    std::fs::write(agents_dir.join("binary.md"), [0xff, 0xfe, 0x00]).unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config").arg(&claude_dir);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("WARNINGS   1 found"))
+        .stdout(predicate::str::contains("binary.md"));
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("doctor").arg("--config").arg(&claude_dir);
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("binary.md"));
+}
+
+// ==================== Snapshot and Diff Tests ====================
+
+#[test]
+fn test_snapshot_then_diff_against_live_dir() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = create_mock_claude_dir(&dir);
+    let snapshot = dir.path().join("before.json");
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("snapshot")
+        .arg("--config")
+        .arg(&claude_dir)
+        .arg("-o")
+        .arg(&snapshot);
+    cmd.assert().success();
+    assert!(snapshot.exists());
+
+    // Unchanged directory: no differences
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("diff").arg(&snapshot).arg(&claude_dir);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("No differences"));
+
+    // Add an agent and compare again
+    std::fs::create_dir_all(claude_dir.join("agents")).unwrap();
+    std::fs::write(
+        claude_dir.join("agents/new-agent.md"),
+        "---\nname: new-agent\ndescription: Fresh\n---\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("diff").arg(&snapshot).arg(&claude_dir);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("+ new-agent (user)"))
+        .stdout(predicate::str::contains("1 added, 0 removed, 0 changed"));
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("diff")
+        .arg(&claude_dir)
+        .arg(&snapshot)
+        .arg("--json");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["changes"][0]["change"], "removed");
+    assert_eq!(json["changes"][0]["component"], "agent");
+}
+
+#[test]
+fn test_diff_missing_input_fails() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = create_mock_claude_dir(&dir);
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("diff")
+        .arg(dir.path().join("missing.json"))
+        .arg(&claude_dir);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Failed to load inventory"));
+}
+
+// ==================== Plugin Component Tests ====================
+
+#[test]
+fn test_plugin_agents_and_mcp_servers_listed() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = dir.path().join(".claude");
+    let plugin_root = dir.path().join("plugin-cache/toolkit");
+    std::fs::create_dir_all(claude_dir.join("plugins")).unwrap();
+    std::fs::create_dir_all(plugin_root.join("agents")).unwrap();
+    std::fs::write(
+        claude_dir.join("plugins/installed_plugins.json"),
+        format!(
+            r#"{{"version": 2, "plugins": {{"toolkit@market": [{{"installPath": "{}", "version": "1.0.0"}}]}}}}"#,
+            plugin_root.to_string_lossy().replace('\\', "/")
+        ),
+    )
+    .unwrap();
+    std::fs::write(
+        plugin_root.join("agents/toolkit-agent.md"),
+        "---\nname: toolkit-agent\ndescription: From a plugin\n---\n",
+    )
+    .unwrap();
+    std::fs::write(
+        plugin_root.join(".mcp.json"),
+        r#"{"mcpServers": {"toolkit-server": {"command": "toolkit"}}}"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config").arg(&claude_dir).arg("-l");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("toolkit-agent"))
+        .stdout(predicate::str::contains("plugin:toolkit"))
+        .stdout(predicate::str::contains("toolkit-server"));
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config").arg(&claude_dir).arg("--json");
+    let output = cmd.output().unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["agents"][0]["origin"]["type"], "Plugin");
+    assert_eq!(json["agents"][0]["origin"]["plugin_name"], "toolkit");
+    assert_eq!(json["mcp_servers"][0]["scope"], "plugin");
+    assert_eq!(json["mcp_servers"][0]["plugin"], "toolkit");
+}
+
+#[test]
+fn test_marketplaces_view() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = dir.path().join(".claude");
+    let catalog_dir = claude_dir.join("plugins/marketplaces/team-market/.claude-plugin");
+    std::fs::create_dir_all(&catalog_dir).unwrap();
+    std::fs::write(
+        claude_dir.join("plugins/known_marketplaces.json"),
+        r#"{"team-market": {"source": {"source": "github", "repo": "team/plugins"}, "lastUpdated": "2026-03-01T10:00:00.000Z"}}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        catalog_dir.join("marketplace.json"),
+        r#"{"name": "team-market", "plugins": [
+            {"name": "formatter", "description": "Formats code"},
+            {"name": "linter", "description": "Lints code"}
+        ]}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        claude_dir.join("plugins/installed_plugins.json"),
+        r#"{"version": 2, "plugins": {"formatter@team-market": [{"version": "1.0.0"}]}}"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config").arg(&claude_dir).arg("--marketplaces");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("team-market"))
+        .stdout(predicate::str::contains("github:team/plugins"))
+        .stdout(predicate::str::contains("2 plugins, 1 installed"))
+        .stdout(predicate::str::contains("linter"));
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config")
+        .arg(&claude_dir)
+        .arg("--marketplaces")
+        .arg("--json");
+    let output = cmd.output().unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let plugins = &json["marketplaces"][0]["plugins"];
+    assert_eq!(plugins[0]["name"], "formatter");
+    assert_eq!(plugins[0]["installed"], true);
+    assert_eq!(plugins[1]["installed"], false);
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config").arg(&claude_dir).arg("--json");
+    let output = cmd.output().unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["plugins"][0]["marketplace"], "team-market");
+}
+
+#[test]
+fn test_outdated_reports_stale_plugins() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = dir.path().join(".claude");
+    let catalog_dir = claude_dir.join("plugins/marketplaces/team-market/.claude-plugin");
+    std::fs::create_dir_all(&catalog_dir).unwrap();
+    std::fs::write(
+        claude_dir.join("plugins/known_marketplaces.json"),
+        r#"{"team-market": {"source": {"source": "github", "repo": "team/plugins"}}}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        catalog_dir.join("marketplace.json"),
+        r#"{"plugins": [{"name": "formatter", "version": "1.2.0"}]}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        claude_dir.join("plugins/installed_plugins.json"),
+        r#"{"version": 2, "plugins": {"formatter@team-market": [{"version": "1.0.0"}]}}"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("outdated").arg("--config").arg(&claude_dir);
+    cmd.assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::contains("formatter"))
+        .stdout(predicate::str::contains("1.2.0"))
+        .stdout(predicate::str::contains("1 outdated, 0 current, 0 unknown"));
+
+    // Once the catalog matches, nothing is stale
+    std::fs::write(
+        catalog_dir.join("marketplace.json"),
+        r#"{"plugins": [{"name": "formatter", "version": "1.0.0"}]}"#,
+    )
+    .unwrap();
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("outdated")
+        .arg("--config")
+        .arg(&claude_dir)
+        .arg("--json");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json[0]["name"], "formatter");
+    assert_eq!(json[0]["status"], "current");
+}
+
+#[test]
+fn test_enabled_and_disabled_plugin_filters() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = dir.path().join(".claude");
+    let project_dir = dir.path().join("project");
+    std::fs::create_dir_all(claude_dir.join("plugins")).unwrap();
+    std::fs::create_dir_all(project_dir.join(".claude")).unwrap();
+    std::fs::write(
+        claude_dir.join("plugins/installed_plugins.json"),
+        r#"{"version": 2, "plugins": {
+            "formatter@team": [{"version": "1.0.0"}],
+            "linter@team": [{"version": "1.0.0"}]
+        }}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        claude_dir.join("settings.json"),
+        r#"{"enabledPlugins": {"formatter@team": true, "linter@team": false}}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        project_dir.join(".claude/settings.json"),
+        r#"{"enabledPlugins": {"formatter@team": false}}"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config").arg(&claude_dir).arg("--disabled");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("linter (disabled)"))
+        .stdout(predicate::str::contains("formatter").not());
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config").arg(&claude_dir).arg("--enabled");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("formatter"))
+        .stdout(predicate::str::contains("linter").not());
+
+    // The project layer disables formatter as well
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config")
+        .arg(&claude_dir)
+        .arg(format!("--project={}", project_dir.display()))
+        .arg("--json");
+    let output = cmd.output().unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let plugins = json["plugins"].as_array().unwrap();
+    assert!(plugins.iter().all(|p| p["enabled"] == false));
+}
+
+// ==================== Sessions Subcommand Tests ====================
+
+#[test]
+fn test_sessions_subcommand_tables_projects() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = dir.path().join(".claude");
+    let app_dir = dir.path().join("app");
+    std::fs::create_dir_all(&app_dir).unwrap();
+    let transcripts = claude_dir.join("projects/-app");
+    std::fs::create_dir_all(&transcripts).unwrap();
+    let cwd = app_dir.to_string_lossy().replace('\\', "/");
+    std::fs::write(
+        transcripts.join("s1.jsonl"),
+        format!(
+            "{{\"type\":\"user\",\"cwd\":\"{cwd}\",\"timestamp\":\"2026-01-14T10:00:00.000Z\"}}\n\
+             {{\"type\":\"assistant\",\"cwd\":\"{cwd}\",\"timestamp\":\"2026-01-14T11:30:00.000Z\"}}\n"
+        ),
+    )
+    .unwrap();
+    std::fs::create_dir_all(claude_dir.join("projects/-other")).unwrap();
+    std::fs::write(
+        claude_dir.join("projects/-other/s2.jsonl"),
+        r#"{"type":"user","cwd":"/elsewhere","timestamp":"2026-01-10T10:00:00.000Z"}"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("sessions").arg("--config").arg(&claude_dir);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("/elsewhere"))
+        .stdout(predicate::str::contains("1h 30m"))
+        .stdout(predicate::str::contains("2 session(s) in 2 project(s)"));
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("sessions")
+        .arg("--config")
+        .arg(&claude_dir)
+        .arg(format!("--project={}", app_dir.display()))
+        .arg("--json");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let projects = json["projects"].as_array().unwrap();
+    assert_eq!(projects.len(), 1);
+    assert_eq!(projects[0]["sessions"], 1);
+    assert_eq!(projects[0]["messages"], 2);
+    assert_eq!(projects[0]["duration_secs"], 5400);
+}
+
+#[test]
+fn test_usage_subcommand_with_window() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = dir.path().join(".claude");
+    let transcripts = claude_dir.join("projects/-work-app");
+    std::fs::create_dir_all(&transcripts).unwrap();
+    let line = |id: &str, day: &str, input: u64| {
+        format!(
+            r#"{{"type":"assistant","cwd":"/work/app","requestId":"{id}","timestamp":"{day}T12:00:00.000Z","message":{{"id":"{id}","model":"claude-sonnet-4-5","usage":{{"input_tokens":{input},"output_tokens":0}}}}}}"#
+        )
+    };
+    std::fs::write(
+        transcripts.join("s1.jsonl"),
+        [
+            line("a", "2026-01-14", 1_000_000),
+            line("b", "2026-01-15", 2_000_000),
+            line("c", "2026-01-16", 4_000_000),
+            "not json".to_string(),
+        ]
+        .join("\n"),
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("usage").arg("--config").arg(&claude_dir);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("TOTAL      7000000 tokens"))
+        .stdout(predicate::str::contains("$21.00"))
+        .stdout(predicate::str::contains("/work/app"))
+        .stdout(predicate::str::contains("claude-sonnet-4-5"));
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("usage")
+        .arg("--config")
+        .arg(&claude_dir)
+        .arg("--since")
+        .arg("2026-01-15")
+        .arg("--until")
+        .arg("2026-01-15")
+        .arg("--json");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["total"]["input_tokens"], 2_000_000);
+    assert_eq!(json["days"][0]["key"], "2026-01-15");
+    assert_eq!(json["models"][0]["key"], "claude-sonnet-4-5");
+    assert_eq!(json["warnings"].as_array().unwrap().len(), 1);
+    assert_eq!(json["warnings"][0]["component"], "session");
+    assert!(output.stderr.is_empty());
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("usage")
+        .arg("--config")
+        .arg(&claude_dir)
+        .arg("--since")
+        .arg("yesterday");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("YYYY-MM-DD"));
+
+    for (flag, day) in [("--since", "2026-02-31"), ("--until", "2026-13-40")] {
+        let mut cmd = Command::cargo_bin("claude-list").unwrap();
+        cmd.arg("usage")
+            .arg("--config")
+            .arg(&claude_dir)
+            .arg(flag)
+            .arg(day);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("YYYY-MM-DD"));
+    }
+}
+
+// ==================== Usage Frequency Tests ====================
+
+#[test]
+fn test_unused_lists_commands_and_skills_never_run() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = create_mock_claude_dir(&dir);
+    let commands_dir = claude_dir.join("commands");
+    std::fs::create_dir_all(&commands_dir).unwrap();
+    std::fs::write(commands_dir.join("review.md"), "Review the diff\n").unwrap();
+    std::fs::write(commands_dir.join("lint.md"), "Lint the tree\n").unwrap();
+    std::fs::write(
+        claude_dir.join("history.jsonl"),
+        [
+            r#"{"display":"/review src/main.rs","timestamp":1}"#,
+            r#"{"display":"/review","timestamp":2}"#,
+            r#"{"display":"/test-skill","timestamp":3}"#,
+        ]
+        .join("\n"),
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config").arg(&claude_dir).arg("--json");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let review = json["commands"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["name"] == "review")
+        .unwrap();
+    assert_eq!(review["uses"], 2);
+    assert_eq!(json["skills"][0]["uses"], 1);
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config")
+        .arg(&claude_dir)
+        .arg("--unused")
+        .arg("--json");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let commands = json["commands"].as_array().unwrap();
+    assert_eq!(commands.len(), 1);
+    assert_eq!(commands[0]["name"], "lint");
+    assert!(json["skills"].as_array().unwrap().is_empty());
+    assert!(json["plugins"].as_array().unwrap().is_empty());
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config")
+        .arg(&claude_dir)
+        .arg("-l")
+        .arg("--commands");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("USES"))
+        .stdout(predicate::str::is_match(r"review\s+user\s+2 ").unwrap());
+}
+
+// ==================== Last Session Tests ====================
+
+#[test]
+fn test_last_session_is_iso_with_relative_time() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = create_mock_claude_dir(&dir);
+    std::fs::write(
+        claude_dir.join("history.jsonl"),
+        "{\"display\":\"/help\",\"timestamp\":1766567624402}\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config").arg(&claude_dir).arg("--json");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["sessions"]["last_session"], "2025-12-24T09:13:44.402Z");
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config")
+        .arg(&claude_dir)
+        .arg("-l")
+        .arg("--sessions");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Last session: 2025-12-24T09:13:44.402Z (",
+        ))
+        .stdout(predicate::str::contains(" ago)"));
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config").arg(&claude_dir).arg("--sessions");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("SESSIONS   1 recorded, last "));
+}
+
+// ==================== Namespaced Commands Tests ====================
+
+#[test]
+fn test_nested_commands_are_namespaced_and_grouped() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = create_mock_claude_dir(&dir);
+    let commands_dir = claude_dir.join("commands");
+    std::fs::create_dir_all(commands_dir.join("frontend")).unwrap();
+    std::fs::write(commands_dir.join("review.md"), "# Review\n").unwrap();
+    std::fs::write(
+        commands_dir.join("frontend/component.md"),
+        "---\ndescription: Scaffold a component\n---\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config")
+        .arg(&claude_dir)
+        .arg("--commands")
+        .arg("--no-color");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "  /review\n  frontend:\n    /frontend:component\n",
+    ));
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config")
+        .arg(&claude_dir)
+        .arg("--commands")
+        .arg("--json");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let component = json["commands"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["name"] == "frontend:component")
+        .unwrap();
+    assert_eq!(component["namespace"], "frontend");
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config")
+        .arg(&claude_dir)
+        .arg("--commands")
+        .arg("-l")
+        .arg("--no-color");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "  frontend:\n    frontend:component",
+    ));
+}
+
+// ==================== Skill Frontmatter Tests ====================
+
+#[test]
+fn test_skill_allowed_tools_in_json_and_detailed() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = create_mock_claude_dir(&dir);
+    let skill_dir = claude_dir.join("skills/pdf");
+    std::fs::create_dir_all(&skill_dir).unwrap();
+    std::fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: pdf\ndescription: PDFs\nallowed-tools: Read, Bash(python:*)\nlicense: MIT\n---\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config")
+        .arg(&claude_dir)
+        .arg("--skills")
+        .arg("--json");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let skill = json["skills"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|s| s["name"] == "pdf")
+        .unwrap();
+    assert_eq!(
+        skill["allowed_tools"],
+        serde_json::json!(["Read", "Bash(python:*)"])
+    );
+    assert_eq!(skill["license"], "MIT");
+    assert_eq!(skill["declared_name"], "pdf");
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config")
+        .arg(&claude_dir)
+        .arg("--skills")
+        .arg("-l")
+        .arg("--no-color");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "      allowed tools: Read, Bash(python:*)\n",
+        ))
+        .stdout(predicate::str::contains("declared name").not());
+}
+
+// ==================== Memory Tests ====================
+
+#[test]
+fn test_memory_hierarchy_for_project() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = create_mock_claude_dir(&dir);
+    let project = dir.path().join("app");
+    std::fs::create_dir_all(project.join("docs")).unwrap();
+    std::fs::create_dir_all(project.join("web")).unwrap();
+    std::fs::write(claude_dir.join("CLAUDE.md"), "Prefer small commits\n").unwrap();
+    std::fs::write(project.join("CLAUDE.md"), "# App\nStyle: @docs/style.md\n").unwrap();
+    std::fs::write(project.join("CLAUDE.local.md"), "Local notes\n").unwrap();
+    std::fs::write(project.join("docs/style.md"), "Use tabs\n").unwrap();
+    std::fs::write(project.join("web/CLAUDE.md"), "Web rules\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config")
+        .arg(&claude_dir)
+        .arg(format!("--project={}", project.display()))
+        .arg("--memory")
+        .arg("--no-color");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("MEMORY     5 files, 6 lines\n"))
+        .stdout(predicate::str::contains(
+            "  CLAUDE.md\n    docs/style.md\n  CLAUDE.local.md\n  web/CLAUDE.md\n",
+        ))
+        .stdout(predicate::str::contains("SKILLS").not());
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config")
+        .arg(&claude_dir)
+        .arg(format!("--project={}", project.display()))
+        .arg("--memory")
+        .arg("--json");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let scopes: Vec<&str> = json["memory"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["scope"].as_str().unwrap())
+        .collect();
+    assert_eq!(scopes, ["user", "project", "project", "local", "nested"]);
+    assert_eq!(json["memory"][1]["lines"], 2);
+    assert_eq!(json["memory"][2]["depth"], 1);
+
+    // Without --project only the user's memory applies
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config")
+        .arg(&claude_dir)
+        .arg("--memory")
+        .arg("--no-color");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("MEMORY     1 files, 1 lines\n"));
+}
+
+// ==================== Settings Tests ====================
+
+#[test]
+fn test_settings_with_project_origins() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = create_mock_claude_dir(&dir);
+    let project = dir.path().join("app");
+    std::fs::create_dir_all(project.join(".claude")).unwrap();
+    std::fs::write(
+        claude_dir.join("settings.json"),
+        r#"{"model": "sonnet", "env": {"API_TOKEN": "secret"}, "permissions": {"deny": ["Read(./.env)"]}}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        project.join(".claude/settings.json"),
+        r#"{"model": "opus", "permissions": {"allow": ["Bash(npm test)"]}}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        project.join(".claude/settings.local.json"),
+        r#"{"outputStyle": "Explanatory"}"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config")
+        .arg(&claude_dir)
+        .arg(format!("--project={}", project.display()))
+        .arg("--settings")
+        .arg("--no-color");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "SETTINGS   4 keys, 2 permission rules\n",
+        ))
+        .stdout(predicate::str::contains("  model: sonnet (user)\n"))
+        .stdout(predicate::str::contains("  env.API_TOKEN (user)\n"))
+        .stdout(predicate::str::contains(
+            "  outputStyle: Explanatory (local)\n",
+        ))
+        .stdout(predicate::str::contains("  deny Read(./.env) (user)\n"))
+        .stdout(predicate::str::contains(
+            "  allow Bash(npm test) (project)\n",
+        ))
+        .stdout(predicate::str::contains("secret").not())
+        .stdout(predicate::str::contains("PLUGINS").not());
+
+    let mut cmd = Command::cargo_bin("claude-list").unwrap();
+    cmd.arg("--config")
+        .arg(&claude_dir)
+        .arg(format!("--project={}", project.display()))
+        .arg("--settings")
+        .arg("--effective")
+        .arg("--json");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let values = json["settings"]["values"].as_array().unwrap();
+    let model: Vec<&serde_json::Value> = values.iter().filter(|v| v["key"] == "model").collect();
+    assert_eq!(model.len(), 1);
+    assert_eq!(model[0]["value"], "opus");
+    assert_eq!(model[0]["scope"], "project");
+    assert_eq!(json["settings"]["permissions"].as_array().unwrap().len(), 2);
+}
+
 // ==================== Config Path Edge Cases ====================
 
 #[test]